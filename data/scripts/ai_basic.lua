@@ -110,6 +110,11 @@ function ai_action(parent, params)
 
     local targets = sort_attack_targets(parent, hostiles, weights)
     if targets == nil then
+        if search_last_known_position(parent) then
+            game:log("  No valid attack target.  Searching last known position")
+            return parent:state_wait(WAIT_TIME)
+        end
+
         game:log("  No valid attack target.  End")
         return end_turn(parent)
     end
@@ -153,6 +158,15 @@ function end_turn(parent)
     return parent:state_end()
 end
 
+function search_last_known_position(parent)
+    local pos = parent:last_known_position()
+    if pos == nil then
+        return false
+    end
+
+    return parent:move_towards_point(pos.x, pos.y, 1)
+end
+
 function attempt_run_away(parent, hostiles)
     local parent_x = parent:x()
     local parent_y = parent:y()
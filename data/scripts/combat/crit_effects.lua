@@ -0,0 +1,31 @@
+-- Functions fired by crit table entries.  The parent is the attacker and
+-- the target is the entity suffering the outcome.
+
+function bleed(parent, target)
+  local effect = target:create_effect("Bleeding", 3)
+  effect:set_tag("bleed")
+
+  -- global callbacks only receive their parent, so the bleeding
+  -- target is used as the parent here
+  local cb = game:create_callback(target, "crit_effects")
+  cb:set_on_round_elapsed_fn("bleed_round_elapsed")
+  effect:add_callback(cb)
+
+  local anim = target:create_particle_generator("particles/circle4")
+  anim:set_moves_with_parent()
+  anim:set_initial_gen(4.0)
+  anim:set_color(anim:param(0.8), anim:param(0.0), anim:param(0.0))
+  anim:set_gen_rate(anim:param(8.0))
+  anim:set_position(anim:param(0.0), anim:param(-1.0))
+  anim:set_particle_size_dist(anim:fixed_dist(0.3), anim:fixed_dist(0.3))
+  anim:set_particle_position_dist(anim:dist_param(anim:uniform_dist(-0.5, 0.5), anim:uniform_dist(-0.5, 0.5)),
+    anim:dist_param(anim:fixed_dist(0.0), anim:uniform_dist(1.0, 2.0)))
+  anim:set_particle_duration_dist(anim:fixed_dist(0.5))
+  effect:add_anim(anim)
+
+  effect:apply()
+end
+
+function bleed_round_elapsed(parent)
+  parent:take_damage(parent, 2, 4, "Raw")
+end
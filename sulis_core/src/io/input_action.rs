@@ -44,9 +44,12 @@ pub enum InputActionKind {
     ToggleCharacter,
     ToggleMap,
     ToggleJournal,
+    ToggleCombatLog,
     ToggleFormation,
     Back,
     EndTurn,
+    Delay,
+    Ready,
     Rest,
     ScrollUp,
     ScrollDown,
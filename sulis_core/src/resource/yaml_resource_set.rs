@@ -49,8 +49,10 @@ pub enum YamlResourceKind {
     Actor,
     AiTemplate,
     Area,
+    Banter,
     Class,
     Conversation,
+    CritTable,
     Cutscene,
     Encounter,
     Item,
@@ -59,7 +61,9 @@ pub enum YamlResourceKind {
     Prop,
     Quest,
     Race,
+    Sequence,
     Size,
+    SurfaceType,
     Tile,
     Generator,
 }
@@ -99,8 +103,10 @@ impl YamlResourceKind {
             "actors" => Actor,
             "ai" => AiTemplate,
             "areas" => Area,
+            "banters" => Banter,
             "classes" => Class,
             "conversations" => Conversation,
+            "crit_tables" => CritTable,
             "cutscenes" => Cutscene,
             "encounters" => Encounter,
             "items" => Item,
@@ -109,7 +115,9 @@ impl YamlResourceKind {
             "props" => Prop,
             "quests" => Quest,
             "races" => Race,
+            "sequences" => Sequence,
             "sizes" => Size,
+            "surface_types" => SurfaceType,
             "tiles" => Tile,
             "generators" => Generator,
             "scripts" | "theme" => Skip,
@@ -67,6 +67,12 @@ impl ScrollPane {
         Widget::add_child_to(&self.content, child);
     }
 
+    pub fn add_to_content_front(&self, child: Rc<RefCell<Widget>>) {
+        disable_text_area_limit_recursive(&child);
+
+        Widget::add_child_to_front(&self.content, child);
+    }
+
     fn layout_vertical(&mut self, widget: &mut Widget) {
         let scroll = self.scrollbar.borrow().cur_pos();
 
@@ -0,0 +1,166 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2019 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+//! Exports conversations to Graphviz DOT files and reports unreachable
+//! nodes, nodes that can never end, dead end responses without `on_select`
+//! triggers, and cycles.  Run from the repository root:
+//!
+//! `cargo run -p sulis_editor --bin convo_graph -- <campaign_id> [--out <dir>] [--svg] [conversation_id ...]`
+//!
+//! With `--svg`, the Graphviz `dot` program is used to also render each
+//! graph to SVG.  Exits with an error code if any conversation has
+//! unreachable or endless nodes.
+
+#[macro_use]
+extern crate log;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+
+use sulis_core::config::Config;
+use sulis_core::resource::ResourceSet;
+use sulis_core::util;
+use sulis_module::{Conversation, ConversationGraph, Module};
+
+fn main() {
+    let _logger_handle = util::setup_logger();
+
+    let mut args = std::env::args().skip(1);
+    let mut campaign = None;
+    let mut out_dir = PathBuf::from(".");
+    let mut svg = false;
+    let mut ids = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--out" => match args.next() {
+                None => usage(),
+                Some(dir) => out_dir = PathBuf::from(dir),
+            },
+            "--svg" => svg = true,
+            _ if campaign.is_none() => campaign = Some(arg),
+            _ => ids.push(arg),
+        }
+    }
+
+    let campaign = match campaign {
+        None => usage(),
+        Some(campaign) => campaign,
+    };
+
+    let resources_config = Config::resources_config();
+    let dirs = vec![
+        resources_config.directory,
+        format!("{}/{}", resources_config.campaigns_directory, campaign),
+    ];
+
+    let yaml = match ResourceSet::load_resources(dirs.clone()) {
+        Err(e) => {
+            error!("{}", e);
+            util::error_and_exit("Fatal error reading resources.");
+            unreachable!();
+        }
+        Ok(yaml) => yaml,
+    };
+
+    if let Err(e) = Module::load_resources(yaml, dirs) {
+        error!("{}", e);
+        util::error_and_exit("Fatal error setting up module.");
+    }
+
+    let mut convos: Vec<_> = if ids.is_empty() {
+        Module::all_conversations()
+    } else {
+        let mut convos = Vec::new();
+        for id in ids.iter() {
+            match Module::conversation(id) {
+                None => util::error_and_exit(&format!("No conversation '{id}' found")),
+                Some(convo) => convos.push(convo),
+            }
+        }
+        convos
+    };
+    convos.sort_by(|a, b| a.id.cmp(&b.id));
+
+    if let Err(e) = fs::create_dir_all(&out_dir) {
+        error!("{}", e);
+        util::error_and_exit(&format!("Unable to create output dir {out_dir:?}"));
+    }
+
+    let mut has_errors = false;
+    for convo in convos {
+        has_errors |= export(&convo, &out_dir, svg);
+    }
+
+    if has_errors {
+        process::exit(1);
+    }
+}
+
+/// Writes the graph for `convo` and prints its report.  Returns true if
+/// the conversation has unreachable or endless nodes
+fn export(convo: &Conversation, out_dir: &Path, svg: bool) -> bool {
+    let graph = ConversationGraph::new(convo);
+
+    let dot_file = out_dir.join(format!("{}.dot", convo.id));
+    if let Err(e) = fs::write(&dot_file, graph.to_dot()) {
+        warn!("Unable to write {:?}: {}", dot_file, e);
+    } else if svg {
+        let svg_file = out_dir.join(format!("{}.svg", convo.id));
+        let status = Command::new("dot")
+            .arg("-Tsvg")
+            .arg("-o")
+            .arg(&svg_file)
+            .arg(&dot_file)
+            .status();
+        match status {
+            Ok(status) if status.success() => (),
+            Ok(status) => warn!("Graphviz exited with {} for {:?}", status, dot_file),
+            Err(e) => warn!("Unable to run Graphviz 'dot': {}", e),
+        }
+    }
+
+    let unreachable = graph.unreachable_nodes();
+    let endless = graph.endless_nodes();
+    let dead_ends = graph.dead_ends();
+    let cycles = graph.cycles();
+
+    println!(
+        "{}: {} nodes, {} links",
+        convo.id,
+        convo.node_ids().count(),
+        graph.edges().len()
+    );
+    if !unreachable.is_empty() {
+        println!("  unreachable: {}", unreachable.join(", "));
+    }
+    if !endless.is_empty() {
+        println!("  never end: {}", endless.join(", "));
+    }
+    for edge in dead_ends {
+        println!("  dead end: {} -> \"{}\"", edge.from, edge.label);
+    }
+    for cycle in cycles {
+        println!("  cycle: {}", cycle.join(", "));
+    }
+
+    !unreachable.is_empty() || !endless.is_empty()
+}
+
+fn usage() -> ! {
+    eprintln!("Usage: convo_graph <campaign_id> [--out <dir>] [--svg] [conversation_id ...]");
+    process::exit(1)
+}
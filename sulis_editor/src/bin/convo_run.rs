@@ -0,0 +1,745 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2019 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+//! Walks through a conversation in the terminal without loading the game.
+//! Run from the repository root:
+//!
+//! `cargo run -p sulis_editor --bin convo_run -- <campaign_id> <conversation_id> [--pc <actor_id>] [--scripts]`
+//!
+//! Flags, quest states, and the other values checked by conversations are
+//! simulated and may be set with commands; type `help` for a list.  The
+//! triggers that would fire are printed, and those that only change flags,
+//! quests, coins, or the party are applied to the simulated state.  The
+//! attributes, levels, and abilities of the `--pc` actor are used if
+//! specified.  Scripts are only run with `--scripts`, which starts the
+//! campaign with the `--pc` actor so that scripts have a game to act on.
+
+#[macro_use]
+extern crate log;
+
+use std::collections::{HashMap, HashSet};
+use std::io::{self, BufRead, Write};
+use std::process;
+use std::rc::Rc;
+
+use sulis_core::config::Config;
+use sulis_core::resource::ResourceSet;
+use sulis_core::util::{self, gen_rand};
+use sulis_module::condition::{self, ConditionContext};
+use sulis_module::conversation::{CheckKind, Response, ResponseCheck};
+use sulis_module::on_trigger::{QuestEntryState, QuestStateData};
+use sulis_module::{Actor, Attribute, Conversation, Module, OnTrigger};
+use sulis_state::script::ScriptEntity;
+use sulis_state::{GameState, QuestStateSet, Script};
+
+const HELP: &str = "Commands:
+  <number>                          select a response
+  set <player|target> <flag> [val]  set a custom flag, to 'true' if no value is given
+  clear <player|target> <flag>      clear a custom flag
+  quest <quest> [entry] <state>     set a quest or quest entry state
+  coins <amount>                    set the party coins
+  add <member|item|ability> <id>    add a party member, stash item, or player ability
+  remove <member|item|ability> <id> remove a party member, stash item, or player ability
+  attr <attribute> <value>          set a player attribute
+  level <class> <value>             set the player's level in a class
+  stat <class_stat> <value>         set the current value of a class stat
+  checks <roll|pass|fail>           roll response checks, or force them to pass or fail
+  state                             show the simulated state
+  restart                           start the conversation again, keeping the simulated state
+  help                              show this help
+  quit                              exit";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CheckMode {
+    Roll,
+    Pass,
+    Fail,
+}
+
+/// The simulated game state that conversation conditions are checked against
+struct PlaytestState {
+    player_flags: HashMap<String, String>,
+    target_flags: HashMap<String, String>,
+    attributes: HashMap<Attribute, i32>,
+    levels: HashMap<String, u32>,
+    class_stats: HashMap<String, i32>,
+    abilities: HashSet<String>,
+    party: HashSet<String>,
+    items: HashSet<String>,
+    coins: i32,
+    quests: QuestStateSet,
+    check_mode: CheckMode,
+    run_scripts: bool,
+}
+
+impl PlaytestState {
+    fn new(pc: Option<&Actor>, run_scripts: bool) -> PlaytestState {
+        let mut state = PlaytestState {
+            player_flags: HashMap::new(),
+            target_flags: HashMap::new(),
+            attributes: HashMap::new(),
+            levels: HashMap::new(),
+            class_stats: HashMap::new(),
+            abilities: HashSet::new(),
+            party: HashSet::new(),
+            items: HashSet::new(),
+            coins: 0,
+            quests: QuestStateSet::default(),
+            check_mode: CheckMode::Roll,
+            run_scripts,
+        };
+
+        if let Some(pc) = pc {
+            for attr in Attribute::iter() {
+                state
+                    .attributes
+                    .insert(attr, pc.attributes.get(attr) as i32);
+            }
+
+            for (class, level) in pc.levels.iter() {
+                state.levels.insert(class.id.to_string(), *level);
+            }
+
+            for ability in pc.abilities.iter() {
+                state.abilities.insert(ability.ability.id.to_string());
+            }
+
+            state.party.insert(pc.id.to_string());
+            state.coins = pc.inventory.pc_starting_coins();
+        }
+
+        state
+    }
+
+    fn flags_mut(&mut self, who: &str) -> Result<&mut HashMap<String, String>, String> {
+        match who {
+            "player" => Ok(&mut self.player_flags),
+            "target" => Ok(&mut self.target_flags),
+            _ => Err(format!("Expected 'player' or 'target', found '{who}'")),
+        }
+    }
+
+    fn set_quest(&mut self, data: &QuestStateData) {
+        match data.entry {
+            None => self.quests.set_state(&data.quest, data.state),
+            Some(ref entry) => self.quests.set_entry_state(&data.quest, entry, data.state),
+        }
+    }
+
+    fn check_value(&self, kind: &CheckKind) -> i32 {
+        match kind {
+            CheckKind::Attribute(attr) => self.player_attribute(*attr),
+            CheckKind::ClassLevel(id) => self.player_level(Some(id)) as i32,
+            CheckKind::ClassStat(id) => self.class_stats.get(id).copied().unwrap_or(0),
+        }
+    }
+
+    fn roll_check(&self, check: &ResponseCheck) -> bool {
+        let success = match self.check_mode {
+            CheckMode::Pass => true,
+            CheckMode::Fail => false,
+            CheckMode::Roll => {
+                let value = self.check_value(&check.kind);
+                let roll = match check.roll {
+                    None | Some(0) => 0,
+                    Some(max) => gen_rand(1, max as i32 + 1),
+                };
+                println!(
+                    "  {} check: {} + {} vs {}",
+                    check.label(),
+                    roll,
+                    value,
+                    check.difficulty
+                );
+                value.saturating_add(roll) >= check.difficulty
+            }
+        };
+
+        println!("  check {}", if success { "passed" } else { "failed" });
+        success
+    }
+
+    /// Prints each trigger as it would fire in game, applying those that
+    /// change the simulated state
+    fn activate(&mut self, triggers: &[OnTrigger]) {
+        for (index, trigger) in triggers.iter().enumerate() {
+            use sulis_module::OnTrigger::*;
+            if let Condition(ref condition) = trigger {
+                // conditions gate all remaining triggers in the list
+                if !condition.evaluate(&*self) {
+                    let remaining = triggers.len() - index - 1;
+                    println!("  condition not met, skipping {remaining} trigger(s)");
+                    return;
+                }
+                continue;
+            }
+
+            println!("  fires {trigger:?}");
+            match trigger {
+                PlayerCoins(amount) => self.coins += amount,
+                PartyMember(ref id) => {
+                    self.party.insert(id.to_string());
+                }
+                PartyItem(ref id) => {
+                    self.items.insert(id.to_string());
+                }
+                PlayerAbility(ref id) => {
+                    self.abilities.insert(id.to_string());
+                }
+                PlayerNumFlag(ref data) => {
+                    add_num_flag(&mut self.player_flags, &data.flag, data.val)
+                }
+                TargetNumFlag(ref data) => {
+                    add_num_flag(&mut self.target_flags, &data.flag, data.val)
+                }
+                NotPlayerNumFlag(ref data) => {
+                    self.player_flags.remove(&data.flag);
+                }
+                NotTargetNumFlag(ref data) => {
+                    self.target_flags.remove(&data.flag);
+                }
+                NotPlayerFlag(ref flag) => {
+                    self.player_flags.remove(flag);
+                }
+                NotTargetFlag(ref flag) => {
+                    self.target_flags.remove(flag);
+                }
+                PlayerFlag(ref flag) => {
+                    self.player_flags
+                        .insert(flag.to_string(), "true".to_string());
+                }
+                TargetFlag(ref flag) => {
+                    self.target_flags
+                        .insert(flag.to_string(), "true".to_string());
+                }
+                QuestState(ref data) => self.set_quest(data),
+                FireScript(ref script) if self.run_scripts => {
+                    let player = GameState::player();
+                    Script::trigger(
+                        &script.id,
+                        &script.func,
+                        (ScriptEntity::from(&player), ScriptEntity::from(&player)),
+                    );
+                }
+                _ => (),
+            }
+        }
+    }
+
+    fn print(&self) {
+        print_flags("player flags", &self.player_flags);
+        print_flags("target flags", &self.target_flags);
+        println!("coins: {}", self.coins);
+        print_ids("party members", &self.party);
+        print_ids("stash items", &self.items);
+        print_ids("abilities", &self.abilities);
+
+        let mut attrs: Vec<_> = self.attributes.iter().collect();
+        attrs.sort();
+        for (attr, value) in attrs {
+            println!("attribute {}: {}", attr.id(), value);
+        }
+
+        let mut levels: Vec<_> = self.levels.iter().collect();
+        levels.sort();
+        for (class, level) in levels {
+            println!("level {class}: {level}");
+        }
+
+        let mut stats: Vec<_> = self.class_stats.iter().collect();
+        stats.sort();
+        for (stat, value) in stats {
+            println!("class stat {stat}: {value}");
+        }
+
+        let mut quests = Module::all_quests();
+        quests.sort_by(|a, b| a.id.cmp(&b.id));
+        for quest in quests {
+            let state = match self.quests.quest(&quest.id) {
+                None => continue,
+                Some(state) => state,
+            };
+
+            if state.state() != QuestEntryState::Hidden {
+                println!("quest {}: {:?}", quest.id, state.state());
+            }
+
+            for (entry, entry_state) in state.iter() {
+                if *entry_state != QuestEntryState::Hidden {
+                    println!("quest {} {}: {:?}", quest.id, entry, entry_state);
+                }
+            }
+        }
+
+        println!("checks: {:?}", self.check_mode);
+    }
+}
+
+impl ConditionContext for PlaytestState {
+    fn player_flag(&self, flag: &str) -> bool {
+        self.player_flags.contains_key(flag)
+    }
+
+    fn target_flag(&self, flag: &str) -> bool {
+        self.target_flags.contains_key(flag)
+    }
+
+    fn player_num_flag(&self, flag: &str) -> f32 {
+        num_flag(&self.player_flags, flag)
+    }
+
+    fn target_num_flag(&self, flag: &str) -> f32 {
+        num_flag(&self.target_flags, flag)
+    }
+
+    fn player_attribute(&self, attribute: Attribute) -> i32 {
+        match self.attributes.get(&attribute) {
+            None => Module::rules().base_attribute,
+            Some(value) => *value,
+        }
+    }
+
+    fn player_level(&self, class: Option<&str>) -> u32 {
+        match class {
+            None => self.levels.values().sum(),
+            Some(id) => self.levels.get(id).copied().unwrap_or(0),
+        }
+    }
+
+    fn player_has_ability(&self, id: &str) -> bool {
+        self.abilities.contains(id)
+    }
+
+    fn has_party_member(&self, id: &str) -> bool {
+        self.party.contains(id)
+    }
+
+    fn has_party_item(&self, id: &str) -> bool {
+        self.items.contains(id)
+    }
+
+    fn party_coins(&self) -> i32 {
+        self.coins
+    }
+
+    fn quest_state(&self, quest: &str, entry: Option<&str>) -> QuestEntryState {
+        match entry {
+            None => self.quests.state(quest),
+            Some(entry) => self.quests.entry_state(quest, entry),
+        }
+    }
+}
+
+struct Runner {
+    convo: Rc<Conversation>,
+    state: PlaytestState,
+    cur_node: Option<String>,
+}
+
+impl Runner {
+    fn start(&mut self) {
+        let mut initial_node = "";
+        for (node, to_view) in self.convo.initial_nodes() {
+            initial_node = node;
+
+            if condition::triggers_match(to_view, &self.state) {
+                break;
+            }
+        }
+
+        let node = initial_node.to_string();
+        self.enter_node(node);
+    }
+
+    fn enter_node(&mut self, node: String) {
+        let convo = Rc::clone(&self.convo);
+        let (text, responses, on_view) = match (
+            convo.text(&node),
+            convo.responses(&node),
+            convo.on_view(&node),
+        ) {
+            (Ok(text), Ok(responses), Ok(on_view)) => (text, responses, on_view),
+            (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => {
+                println!("{e}");
+                self.cur_node = None;
+                return;
+            }
+        };
+
+        println!();
+        println!("[{}] {}", node, text.trim());
+
+        if responses.is_empty() {
+            println!("(no responses, the conversation ends)");
+            self.cur_node = None;
+            return;
+        }
+
+        self.state.activate(on_view);
+        self.cur_node = Some(node);
+        self.print_responses();
+    }
+
+    /// The responses of the current node, or none if the conversation has ended
+    fn cur_responses(&self) -> &[Response] {
+        match self.cur_node {
+            None => &[],
+            Some(ref node) => match self.convo.responses(node) {
+                Err(_) => &[],
+                Ok(responses) => responses,
+            },
+        }
+    }
+
+    fn visible_responses(&self) -> Vec<&Response> {
+        self.cur_responses()
+            .iter()
+            .filter(|response| condition::triggers_match(&response.to_view, &self.state))
+            .collect()
+    }
+
+    fn print_responses(&self) {
+        let mut index = 0;
+        for response in self.cur_responses() {
+            let mut text = response.text.trim().to_string();
+            if let Some(ref check) = response.check {
+                text = format!("[{}] {}", check.label(), text);
+            }
+
+            let to = match response.check {
+                None => response.to.as_deref().unwrap_or("end").to_string(),
+                Some(_) => format!(
+                    "{} / {}",
+                    response.next_node(Some(true)).map_or("end", |n| n),
+                    response.next_node(Some(false)).map_or("end", |n| n),
+                ),
+            };
+
+            if condition::triggers_match(&response.to_view, &self.state) {
+                index += 1;
+                println!("  {index}. {text} -> {to}");
+            } else {
+                println!("  -  {text} -> {to} (hidden)");
+            }
+        }
+    }
+
+    fn select(&mut self, index: usize) -> Result<(), String> {
+        let response = {
+            let responses = self.visible_responses();
+            if responses.is_empty() {
+                return Err("The conversation has ended.  Use 'restart' to begin again".to_string());
+            }
+
+            match index.checked_sub(1).and_then(|i| responses.get(i)) {
+                None => return Err(format!("Select a response from 1 to {}", responses.len())),
+                Some(response) => (*response).clone(),
+            }
+        };
+
+        println!("> {}", response.text.trim());
+        self.state.activate(&response.on_select);
+
+        let check_passed = response
+            .check
+            .as_ref()
+            .map(|check| self.state.roll_check(check));
+
+        match response.next_node(check_passed) {
+            None => {
+                println!("(the conversation ends)");
+                self.cur_node = None;
+            }
+            Some(to) => {
+                if let Ok(speakers) = self.convo.switch_speaker(to) {
+                    if !speakers.is_empty() {
+                        println!("(speaker switches to '{}')", speakers.join("' or '"));
+                    }
+                }
+                self.enter_node(to.to_string());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Runs the command specified by `words`.  Returns false if the
+    /// runner should exit
+    fn command(&mut self, words: &[&str]) -> Result<bool, String> {
+        let state = &mut self.state;
+        match words {
+            [] => return Ok(true),
+            ["quit"] | ["exit"] => return Ok(false),
+            ["help"] => {
+                println!("{HELP}");
+                return Ok(true);
+            }
+            ["state"] => {
+                state.print();
+                return Ok(true);
+            }
+            ["restart"] => {
+                self.start();
+                return Ok(true);
+            }
+            [index] if index.parse::<usize>().is_ok() => {
+                self.select(parse(index)?)?;
+                return Ok(true);
+            }
+            ["set", who, flag] => {
+                state
+                    .flags_mut(who)?
+                    .insert(flag.to_string(), "true".to_string());
+            }
+            ["set", who, flag, value] => {
+                state
+                    .flags_mut(who)?
+                    .insert(flag.to_string(), value.to_string());
+            }
+            ["clear", who, flag] => {
+                state.flags_mut(who)?.remove(*flag);
+            }
+            ["quest", quest, quest_state] => {
+                let data = quest_data(quest, None, quest_state)?;
+                state.set_quest(&data);
+            }
+            ["quest", quest, entry, quest_state] => {
+                let data = quest_data(quest, Some(entry), quest_state)?;
+                state.set_quest(&data);
+            }
+            ["coins", amount] => state.coins = parse(amount)?,
+            ["add", kind, id] => {
+                id_set_mut(state, kind)?.insert(id.to_string());
+            }
+            ["remove", kind, id] => {
+                id_set_mut(state, kind)?.remove(*id);
+            }
+            ["attr", id, value] => {
+                let attr = match Attribute::from(id).or_else(|| Attribute::find_short_name(id)) {
+                    None => return Err(format!("No attribute '{id}' found")),
+                    Some(attr) => attr,
+                };
+                state.attributes.insert(attr, parse(value)?);
+            }
+            ["level", class, value] => {
+                if Module::class(class).is_none() {
+                    return Err(format!("No class '{class}' found"));
+                }
+                state.levels.insert(class.to_string(), parse(value)?);
+            }
+            ["stat", stat, value] => {
+                state.class_stats.insert(stat.to_string(), parse(value)?);
+            }
+            ["checks", "roll"] => state.check_mode = CheckMode::Roll,
+            ["checks", "pass"] => state.check_mode = CheckMode::Pass,
+            ["checks", "fail"] => state.check_mode = CheckMode::Fail,
+            _ => return Err("Unknown command.  Type 'help' for a list of commands".to_string()),
+        }
+
+        // the state has changed, so the visible responses may have as well
+        self.print_responses();
+        Ok(true)
+    }
+}
+
+fn main() {
+    let _logger_handle = util::setup_logger();
+
+    let mut args = std::env::args().skip(1);
+    let mut positional = Vec::new();
+    let mut pc_id = None;
+    let mut run_scripts = false;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--pc" => match args.next() {
+                None => usage(),
+                Some(id) => pc_id = Some(id),
+            },
+            "--scripts" => run_scripts = true,
+            _ => positional.push(arg),
+        }
+    }
+
+    let (campaign, convo_id) = match positional.as_slice() {
+        [campaign, convo_id] => (campaign.to_string(), convo_id.to_string()),
+        _ => usage(),
+    };
+
+    let resources_config = Config::resources_config();
+    let dirs = vec![
+        resources_config.directory,
+        format!("{}/{}", resources_config.campaigns_directory, campaign),
+    ];
+
+    let yaml = match ResourceSet::load_resources(dirs.clone()) {
+        Err(e) => {
+            error!("{}", e);
+            util::error_and_exit("Fatal error reading resources.");
+            unreachable!();
+        }
+        Ok(yaml) => yaml,
+    };
+
+    if let Err(e) = Module::load_resources(yaml, dirs) {
+        error!("{}", e);
+        util::error_and_exit("Fatal error setting up module.");
+    }
+
+    let convo = match Module::conversation(&convo_id) {
+        None => {
+            util::error_and_exit(&format!("No conversation '{convo_id}' found"));
+            unreachable!();
+        }
+        Some(convo) => convo,
+    };
+
+    let pc = pc_id.map(|id| match Module::actor(&id) {
+        None => {
+            util::error_and_exit(&format!("No actor '{id}' found"));
+            unreachable!();
+        }
+        Some(actor) => actor,
+    });
+
+    if run_scripts {
+        let pc = match pc {
+            None => {
+                util::error_and_exit("Running scripts requires a --pc actor");
+                unreachable!();
+            }
+            Some(ref pc) => Rc::clone(pc),
+        };
+
+        if let Err(e) = GameState::init(pc, Vec::new(), HashMap::new()) {
+            error!("{}", e);
+            util::error_and_exit("Unable to start the campaign for running scripts.");
+        }
+    }
+
+    let mut runner = Runner {
+        convo,
+        state: PlaytestState::new(pc.as_deref(), run_scripts),
+        cur_node: None,
+    };
+
+    println!("Type 'help' for a list of commands");
+    runner.start();
+
+    let stdin = io::stdin();
+    loop {
+        print!("> ");
+        let _ = io::stdout().flush();
+
+        let mut line = String::new();
+        match stdin.lock().read_line(&mut line) {
+            Ok(0) => break,
+            Ok(_) => (),
+            Err(e) => {
+                error!("{}", e);
+                break;
+            }
+        }
+
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match runner.command(&words) {
+            Ok(true) => (),
+            Ok(false) => break,
+            Err(e) => println!("{e}"),
+        }
+    }
+}
+
+fn quest_data(quest: &str, entry: Option<&str>, state: &str) -> Result<QuestStateData, String> {
+    let module_quest = match Module::quest(quest) {
+        None => return Err(format!("No quest '{quest}' found")),
+        Some(quest) => quest,
+    };
+
+    if let Some(entry) = entry {
+        if !module_quest.entries.contains_key(entry) {
+            return Err(format!("No entry '{entry}' found in quest '{quest}'"));
+        }
+    }
+
+    let state = match state {
+        "Hidden" | "hidden" => QuestEntryState::Hidden,
+        "Visible" | "visible" => QuestEntryState::Visible,
+        "Active" | "active" => QuestEntryState::Active,
+        "Complete" | "complete" => QuestEntryState::Complete,
+        "Failed" | "failed" => QuestEntryState::Failed,
+        _ => return Err(format!("Invalid quest state '{state}'")),
+    };
+
+    Ok(QuestStateData {
+        quest: quest.to_string(),
+        entry: entry.map(|entry| entry.to_string()),
+        state,
+    })
+}
+
+fn id_set_mut<'a>(
+    state: &'a mut PlaytestState,
+    kind: &str,
+) -> Result<&'a mut HashSet<String>, String> {
+    match kind {
+        "member" => Ok(&mut state.party),
+        "item" => Ok(&mut state.items),
+        "ability" => Ok(&mut state.abilities),
+        _ => Err(format!(
+            "Expected 'member', 'item', or 'ability', found '{kind}'"
+        )),
+    }
+}
+
+fn parse<T: std::str::FromStr>(value: &str) -> Result<T, String> {
+    value
+        .parse::<T>()
+        .map_err(|_| format!("Invalid number '{value}'"))
+}
+
+fn num_flag(flags: &HashMap<String, String>, flag: &str) -> f32 {
+    match flags.get(flag) {
+        None => 0.0,
+        Some(val) => val.parse::<f32>().unwrap_or(0.0),
+    }
+}
+
+fn add_num_flag(flags: &mut HashMap<String, String>, flag: &str, value: f32) {
+    let cur = num_flag(flags, flag);
+    flags.insert(flag.to_string(), (cur + value).to_string());
+}
+
+fn print_flags(title: &str, flags: &HashMap<String, String>) {
+    let mut flags: Vec<_> = flags.iter().collect();
+    flags.sort();
+    for (flag, value) in flags {
+        println!("{title}: {flag} = {value}");
+    }
+}
+
+fn print_ids(title: &str, ids: &HashSet<String>) {
+    let mut ids: Vec<_> = ids.iter().collect();
+    ids.sort();
+    if !ids.is_empty() {
+        let ids: Vec<&str> = ids.iter().map(|id| id.as_str()).collect();
+        println!("{}: {}", title, ids.join(", "));
+    }
+}
+
+fn usage() -> ! {
+    eprintln!("Usage: convo_run <campaign_id> <conversation_id> [--pc <actor_id>] [--scripts]");
+    process::exit(1)
+}
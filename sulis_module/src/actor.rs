@@ -222,7 +222,7 @@ impl Actor {
             portrait: other.portrait.clone(),
             race: Rc::clone(&other.race),
             sex: other.sex,
-            attributes: other.attributes,
+            attributes: other.attributes.clone(),
             inventory,
             xp,
             total_level,
@@ -26,6 +26,9 @@ pub enum FuncKind {
     AfterAttack,
     BeforeDefense,
     OnRoundElapsed,
+    OnTargetLeftThreat,
+    OnTargetCastWhileThreatened,
+    OnAllyAttacked,
     AiAction,
 }
 
@@ -0,0 +1,127 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2019 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::collections::HashSet;
+use std::io::Error;
+use std::rc::Rc;
+
+use serde::Deserialize;
+
+use crate::condition::validate_triggers;
+use crate::rules::Time;
+use crate::{Conversation, Module, OnTrigger};
+use sulis_core::util::invalid_data_error;
+
+/// A short conversation between party members, started automatically
+/// while out of combat.  A banter may start once all of its speakers are
+/// present in the party, the party is in one of its areas, its conditions
+/// are met, and at least `delay` has passed since the previous banter.
+#[derive(Debug)]
+pub struct Banter {
+    pub id: String,
+    pub conversation: Rc<Conversation>,
+
+    /// The actor IDs of the party members taking part.  The first is the
+    /// target of the conversation, with the others available as speakers
+    pub speakers: Vec<String>,
+
+    /// The areas the banter may start in, or any area if empty
+    pub areas: Vec<String>,
+
+    /// Conditional triggers, checked with the first speaker as the target
+    pub conditions: Vec<OnTrigger>,
+    pub delay: Time,
+    pub repeatable: bool,
+}
+
+impl Banter {
+    pub fn new(
+        builder: BanterBuilder,
+        module: &Module,
+        area_ids: &HashSet<String>,
+    ) -> Result<Banter, Error> {
+        let conversation = match module.conversations.get(&builder.conversation) {
+            None => {
+                return invalid_data_error(&format!(
+                    "Invalid conversation '{}' for banter '{}'",
+                    builder.conversation, builder.id
+                ))
+            }
+            Some(convo) => Rc::clone(convo),
+        };
+
+        if builder.speakers.is_empty() {
+            return invalid_data_error(&format!(
+                "Banter '{}' must have at least one speaker",
+                builder.id
+            ));
+        }
+
+        for speaker in builder.speakers.iter() {
+            if !module.actors.contains_key(speaker) {
+                return invalid_data_error(&format!(
+                    "Invalid speaker '{}' for banter '{}'",
+                    speaker, builder.id
+                ));
+            }
+        }
+
+        for area in builder.areas.iter() {
+            if !area_ids.contains(area) {
+                return invalid_data_error(&format!(
+                    "Invalid area '{}' for banter '{}'",
+                    area, builder.id
+                ));
+            }
+        }
+
+        validate_triggers(&builder.conditions, module)?;
+
+        Ok(Banter {
+            id: builder.id,
+            conversation,
+            speakers: builder.speakers,
+            areas: builder.areas,
+            conditions: builder.conditions,
+            delay: builder.delay,
+            repeatable: builder.repeatable,
+        })
+    }
+
+    pub fn is_valid_area(&self, area_id: &str) -> bool {
+        self.areas.is_empty() || self.areas.iter().any(|area| area == area_id)
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct BanterBuilder {
+    pub id: String,
+    pub conversation: String,
+    pub speakers: Vec<String>,
+
+    #[serde(default)]
+    pub areas: Vec<String>,
+
+    #[serde(default)]
+    pub conditions: Vec<OnTrigger>,
+
+    #[serde(default)]
+    pub delay: Time,
+
+    #[serde(default)]
+    pub repeatable: bool,
+}
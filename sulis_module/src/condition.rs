@@ -0,0 +1,269 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2019 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::io::Error;
+
+use serde::{Deserialize, Serialize};
+
+use sulis_core::util::invalid_data_error;
+
+use crate::on_trigger::{QuestEntryState, QuestStateData};
+use crate::{Attribute, Module, OnTrigger};
+
+/// The game state needed to evaluate a `Condition`.  The player is the party
+/// member taking part in the conversation or activating the trigger, while
+/// the target is the entity being talked to or the trigger's owner.
+pub trait ConditionContext {
+    fn player_flag(&self, flag: &str) -> bool;
+
+    fn target_flag(&self, flag: &str) -> bool;
+
+    fn player_num_flag(&self, flag: &str) -> f32;
+
+    fn target_num_flag(&self, flag: &str) -> f32;
+
+    fn player_attribute(&self, attribute: Attribute) -> i32;
+
+    /// The player's level in the specified class, or total level if no
+    /// class is specified
+    fn player_level(&self, class: Option<&str>) -> u32;
+
+    fn player_has_ability(&self, id: &str) -> bool;
+
+    fn has_party_member(&self, id: &str) -> bool;
+
+    fn has_party_item(&self, id: &str) -> bool;
+
+    fn party_coins(&self) -> i32;
+
+    fn quest_state(&self, quest: &str, entry: Option<&str>) -> QuestEntryState;
+}
+
+/// A boolean expression over the game state, used to gate conversation
+/// responses, initial nodes, and triggers.  Conditions are placed in lists
+/// of `OnTrigger` with the `condition` variant.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub enum Condition {
+    All(Vec<Condition>),
+    Any(Vec<Condition>),
+    Not(Box<Condition>),
+    PlayerFlag(String),
+    TargetFlag(String),
+    PlayerNumFlag(NumFlagCondition),
+    TargetNumFlag(NumFlagCondition),
+    Attribute(AttributeCondition),
+    Level(LevelCondition),
+    PartyMember(String),
+    Coins(CoinsCondition),
+    QuestState(QuestStateData),
+}
+
+impl Condition {
+    pub fn evaluate(&self, ctx: &dyn ConditionContext) -> bool {
+        use self::Condition::*;
+        match self {
+            All(conditions) => conditions.iter().all(|c| c.evaluate(ctx)),
+            Any(conditions) => conditions.iter().any(|c| c.evaluate(ctx)),
+            Not(condition) => !condition.evaluate(ctx),
+            PlayerFlag(flag) => ctx.player_flag(flag),
+            TargetFlag(flag) => ctx.target_flag(flag),
+            PlayerNumFlag(data) => data.op.compare(ctx.player_num_flag(&data.flag), data.val),
+            TargetNumFlag(data) => data.op.compare(ctx.target_num_flag(&data.flag), data.val),
+            Attribute(data) => data
+                .op
+                .compare(ctx.player_attribute(data.attribute), data.val),
+            Level(data) => data
+                .op
+                .compare(ctx.player_level(data.class.as_deref()), data.val),
+            PartyMember(id) => ctx.has_party_member(id),
+            Coins(data) => data.op.compare(ctx.party_coins(), data.val),
+            QuestState(data) => ctx.quest_state(&data.quest, data.entry.as_deref()) == data.state,
+        }
+    }
+
+    /// Checks that all classes and quests referenced by this condition
+    /// exist in the module
+    pub fn validate(&self, module: &Module) -> Result<(), Error> {
+        use self::Condition::*;
+        match self {
+            All(conditions) | Any(conditions) => {
+                if conditions.is_empty() {
+                    return invalid_data_error("Condition lists must not be empty");
+                }
+
+                for condition in conditions.iter() {
+                    condition.validate(module)?;
+                }
+            }
+            Not(condition) => condition.validate(module)?,
+            Level(data) => {
+                if let Some(ref class) = data.class {
+                    if !module.classes.contains_key(class) {
+                        return invalid_data_error(&format!(
+                            "Invalid class '{class}' in level condition"
+                        ));
+                    }
+                }
+            }
+            QuestState(data) => {
+                let quest = match module.quests.get(&data.quest) {
+                    None => {
+                        return invalid_data_error(&format!(
+                            "Invalid quest '{}' in quest state condition",
+                            data.quest
+                        ))
+                    }
+                    Some(quest) => quest,
+                };
+
+                if let Some(ref entry) = data.entry {
+                    if !quest.entries.contains_key(entry) {
+                        return invalid_data_error(&format!(
+                            "Invalid entry '{}' for quest '{}' in quest state condition",
+                            entry, data.quest
+                        ));
+                    }
+                }
+            }
+            PlayerFlag(_) | TargetFlag(_) | PlayerNumFlag(_) | TargetNumFlag(_) | Attribute(_)
+            | PartyMember(_) | Coins(_) => (),
+        }
+
+        Ok(())
+    }
+}
+
+/// Validates all conditions contained in the specified list of triggers
+pub fn validate_triggers<'a, I>(triggers: I, module: &Module) -> Result<(), Error>
+where
+    I: IntoIterator<Item = &'a OnTrigger>,
+{
+    for trigger in triggers {
+        if let OnTrigger::Condition(condition) = trigger {
+            condition.validate(module)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns true if all of the conditional triggers in the list, such as
+/// flag and quest state checks, pass.  Triggers that are not conditional
+/// are ignored with a warning.
+pub fn triggers_match(triggers: &[OnTrigger], ctx: &dyn ConditionContext) -> bool {
+    for trigger in triggers.iter() {
+        use crate::OnTrigger::*;
+        let is_match = match trigger {
+            PlayerCoins(amount) => ctx.party_coins() >= *amount,
+            PartyMember(ref id) => ctx.has_party_member(id),
+            PartyItem(ref id) => ctx.has_party_item(id),
+            TargetNumFlag(ref data) => ctx.target_num_flag(&data.flag) >= data.val,
+            PlayerNumFlag(ref data) => ctx.player_num_flag(&data.flag) >= data.val,
+            NotTargetNumFlag(ref data) => ctx.target_num_flag(&data.flag) < data.val,
+            NotPlayerNumFlag(ref data) => ctx.player_num_flag(&data.flag) < data.val,
+            NotTargetFlag(ref flag) => !ctx.target_flag(flag),
+            NotPlayerFlag(ref flag) => !ctx.player_flag(flag),
+            TargetFlag(ref flag) => ctx.target_flag(flag),
+            PlayerFlag(ref flag) => ctx.player_flag(flag),
+            PlayerAbility(ref id) => ctx.player_has_ability(id),
+            QuestState(ref data) => {
+                ctx.quest_state(&data.quest, data.entry.as_deref()) == data.state
+            }
+            NotQuestState(ref data) => {
+                ctx.quest_state(&data.quest, data.entry.as_deref()) != data.state
+            }
+            Condition(ref condition) => condition.evaluate(ctx),
+            _ => {
+                warn!("Unsupported OnTrigger kind '{:?}' in validator", trigger);
+                true
+            }
+        };
+
+        if !is_match {
+            return false;
+        }
+    }
+
+    true
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    #[serde(rename = "<")]
+    Less,
+
+    #[serde(rename = "<=")]
+    LessOrEqual,
+
+    #[serde(rename = "==")]
+    Equal,
+
+    #[serde(rename = "!=")]
+    NotEqual,
+
+    #[serde(rename = ">=")]
+    GreaterOrEqual,
+
+    #[serde(rename = ">")]
+    Greater,
+}
+
+impl CompareOp {
+    pub fn compare<T: PartialOrd>(self, value: T, against: T) -> bool {
+        use self::CompareOp::*;
+        match self {
+            Less => value < against,
+            LessOrEqual => value <= against,
+            Equal => value == against,
+            NotEqual => value != against,
+            GreaterOrEqual => value >= against,
+            Greater => value > against,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct NumFlagCondition {
+    pub flag: String,
+    pub op: CompareOp,
+    pub val: f32,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct AttributeCondition {
+    pub attribute: Attribute,
+    pub op: CompareOp,
+    pub val: i32,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct LevelCondition {
+    #[serde(default)]
+    pub class: Option<String>,
+    pub op: CompareOp,
+    pub val: u32,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct CoinsCondition {
+    pub op: CompareOp,
+    pub val: i32,
+}
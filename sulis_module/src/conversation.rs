@@ -18,13 +18,14 @@ use std::collections::HashMap;
 use std::io::Error;
 use std::slice::Iter;
 
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer};
 
-use sulis_core::util::unable_to_create_error;
+use sulis_core::util::{invalid_data_error, unable_to_create_error};
 
-use crate::{Module, OnTrigger};
+use crate::condition::validate_triggers;
+use crate::{Attribute, Module, OnTrigger};
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct Response {
     pub text: String,
@@ -35,6 +36,100 @@ pub struct Response {
 
     #[serde(default)]
     pub to_view: Vec<OnTrigger>,
+
+    /// A check made by the player when selecting this response.  The
+    /// conversation then moves to `to_success` or `to_fail`, falling back
+    /// to `to` if the matching branch is not specified
+    #[serde(default)]
+    pub check: Option<ResponseCheck>,
+
+    #[serde(default)]
+    pub to_success: Option<String>,
+
+    #[serde(default)]
+    pub to_fail: Option<String>,
+}
+
+impl Response {
+    /// Returns the node to move to after selecting this response, given
+    /// the result of the check, if any
+    pub fn next_node(&self, check_passed: Option<bool>) -> Option<&String> {
+        let branch = match check_passed {
+            None => None,
+            Some(true) => self.to_success.as_ref(),
+            Some(false) => self.to_fail.as_ref(),
+        };
+
+        branch.or(self.to.as_ref())
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub enum CheckKind {
+    Attribute(Attribute),
+
+    /// The player's level in the class with the specified ID
+    ClassLevel(String),
+
+    /// The player's current value of the class stat with the specified ID
+    ClassStat(String),
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ResponseCheck {
+    pub kind: CheckKind,
+    pub difficulty: i32,
+
+    /// If specified, a random roll from 1 to this value is added to the
+    /// checked value
+    #[serde(default)]
+    pub roll: Option<u32>,
+}
+
+impl ResponseCheck {
+    /// The text shown alongside the response, such as `Intellect 14`
+    pub fn label(&self) -> String {
+        let name = match self.kind {
+            CheckKind::Attribute(attr) => attr.name(),
+            CheckKind::ClassLevel(ref id) => match Module::class(id) {
+                None => id.to_string(),
+                Some(class) => class.name.to_string(),
+            },
+            CheckKind::ClassStat(ref id) => Module::all_classes()
+                .iter()
+                .flat_map(|class| class.stats.iter())
+                .find(|stat| &stat.id == id)
+                .map_or_else(|| id.to_string(), |stat| stat.name.to_string()),
+        };
+
+        format!("{} {}", name, self.difficulty)
+    }
+
+    fn validate(&self, module: &Module) -> Result<(), String> {
+        match self.kind {
+            CheckKind::Attribute(_) => Ok(()),
+            CheckKind::ClassLevel(ref id) => {
+                if module.classes.contains_key(id) {
+                    Ok(())
+                } else {
+                    Err(format!("Invalid class '{id}' in check"))
+                }
+            }
+            CheckKind::ClassStat(ref id) => {
+                let found = module
+                    .classes
+                    .values()
+                    .any(|class| class.stats.iter().any(|stat| &stat.id == id));
+                if found {
+                    Ok(())
+                } else {
+                    Err(format!("Invalid class stat '{id}' in check"))
+                }
+            }
+        }
+    }
 }
 
 #[derive(Deserialize, Debug)]
@@ -42,8 +137,10 @@ pub struct Response {
 struct Node {
     text: String,
 
-    #[serde(default)]
-    switch_speaker: Option<String>,
+    /// The entities that may speak this node, in order of preference.  May
+    /// be a single ID or a list, with the first present entity being used
+    #[serde(default, deserialize_with = "de_speakers")]
+    switch_speaker: Vec<String>,
 
     #[serde(default)]
     on_view: Vec<OnTrigger>,
@@ -64,7 +161,7 @@ impl PartialEq for Conversation {
 }
 
 impl Conversation {
-    pub fn new(builder: ConversationBuilder, _module: &Module) -> Result<Conversation, Error> {
+    pub fn new(builder: ConversationBuilder, module: &Module) -> Result<Conversation, Error> {
         if builder.initial_nodes.is_empty() {
             warn!("Must specify at least one initial node for conversation");
             return unable_to_create_error("conversation", &builder.id);
@@ -77,17 +174,49 @@ impl Conversation {
                 return unable_to_create_error("conversation", &builder.id);
             }
 
+            if let Err(e) = validate_triggers(&node.to_view, module) {
+                warn!("Invalid to_view for initial node '{}': {}", node.id, e);
+                return unable_to_create_error("conversation", &builder.id);
+            }
+
             initial_nodes.push((node.id, node.to_view));
         }
 
-        for (_, node) in builder.nodes.iter() {
+        for (id, node) in builder.nodes.iter() {
+            if let Err(e) = validate_triggers(&node.on_view, module) {
+                warn!("Invalid on_view for node '{}': {}", id, e);
+                return unable_to_create_error("conversation", &builder.id);
+            }
+
             for response in node.responses.iter() {
-                if let Some(ref to) = response.to {
+                let triggers = response.to_view.iter().chain(response.on_select.iter());
+                if let Err(e) = validate_triggers(triggers, module) {
+                    warn!("Invalid conditions for response in node '{}': {}", id, e);
+                    return unable_to_create_error("conversation", &builder.id);
+                }
+
+                let links = [&response.to, &response.to_success, &response.to_fail];
+                for to in links.into_iter().flatten() {
                     if !builder.nodes.contains_key(to) {
                         warn!("Invalid to '{}' for node response.  Must be a node ID", to);
                         return unable_to_create_error("conversation", &builder.id);
                     }
                 }
+
+                match response.check {
+                    None => {
+                        if response.to_success.is_some() || response.to_fail.is_some() {
+                            warn!("Response in node '{}' has check branches but no check", id);
+                            return unable_to_create_error("conversation", &builder.id);
+                        }
+                    }
+                    Some(ref check) => {
+                        if let Err(e) = check.validate(module) {
+                            warn!("{} for response in node '{}'", e, id);
+                            return unable_to_create_error("conversation", &builder.id);
+                        }
+                    }
+                }
             }
         }
 
@@ -102,37 +231,56 @@ impl Conversation {
         self.initial_nodes.iter()
     }
 
-    // TODO don't panic when getting a node.
+    /// Returns the IDs of all nodes in this conversation, in no particular order
+    pub fn node_ids(&self) -> impl Iterator<Item = &String> {
+        self.nodes.keys()
+    }
 
-    pub fn on_view(&self, node: &str) -> &Vec<OnTrigger> {
+    fn node(&self, node: &str) -> Result<&Node, Error> {
         match self.nodes.get(node) {
-            None => panic!("Invalid node"),
-            Some(node) => &node.on_view,
+            None => invalid_data_error(&format!(
+                "Invalid node '{}' in conversation '{}'",
+                node, self.id
+            )),
+            Some(node) => Ok(node),
         }
     }
 
-    pub fn switch_speaker(&self, node: &str) -> &Option<String> {
-        match self.nodes.get(node) {
-            None => panic!("Invalid node"),
-            Some(node) => &node.switch_speaker,
-        }
+    pub fn on_view(&self, node: &str) -> Result<&Vec<OnTrigger>, Error> {
+        Ok(&self.node(node)?.on_view)
     }
 
-    pub fn text(&self, node: &str) -> &str {
-        match self.nodes.get(node) {
-            None => panic!("Invalid node"),
-            Some(node) => &node.text,
-        }
+    /// The candidate speakers for the specified node, in order of preference.
+    /// Each is `player` or `target` for the participants in the conversation,
+    /// `party` for any other party member, or the ID of a party member or an
+    /// entity in the current area.  Empty if the speaker does not change
+    pub fn switch_speaker(&self, node: &str) -> Result<&[String], Error> {
+        Ok(&self.node(node)?.switch_speaker)
     }
 
-    pub fn responses(&self, node: &str) -> &Vec<Response> {
-        match self.nodes.get(node) {
-            None => panic!("Invalid node"),
-            Some(node) => &node.responses,
-        }
+    pub fn text(&self, node: &str) -> Result<&str, Error> {
+        Ok(&self.node(node)?.text)
+    }
+
+    pub fn responses(&self, node: &str) -> Result<&Vec<Response>, Error> {
+        Ok(&self.node(node)?.responses)
     }
 }
 
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Speakers {
+    One(String),
+    List(Vec<String>),
+}
+
+fn de_speakers<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    Ok(match Speakers::deserialize(deserializer)? {
+        Speakers::One(id) => vec![id],
+        Speakers::List(ids) => ids,
+    })
+}
+
 #[derive(Deserialize, Debug)]
 #[serde(deny_unknown_fields)]
 pub struct InitialNode {
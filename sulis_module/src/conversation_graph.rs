@@ -0,0 +1,329 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2019 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::collections::{HashMap, HashSet};
+
+use crate::Conversation;
+
+const MAX_LABEL_LEN: usize = 40;
+const END_NODE: &str = "__end";
+
+/// A link between two nodes of a conversation, created by selecting a
+/// response.  A `to` of `None` means the response ends the conversation
+#[derive(Debug)]
+pub struct ConversationEdge {
+    pub from: String,
+    pub to: Option<String>,
+    pub label: String,
+
+    /// Whether the response has any `on_select` triggers
+    pub on_select: bool,
+}
+
+/// The structure of a `Conversation` as a directed graph of nodes and
+/// responses.  Used to export conversations for review and to report on
+/// nodes that are unreachable, can never end, or loop back on themselves,
+/// and on responses that are dead ends
+#[derive(Debug)]
+pub struct ConversationGraph {
+    pub id: String,
+    nodes: Vec<String>,
+    texts: HashMap<String, String>,
+    initial_nodes: Vec<String>,
+    edges: Vec<ConversationEdge>,
+}
+
+impl ConversationGraph {
+    pub fn new(convo: &Conversation) -> ConversationGraph {
+        let mut nodes: Vec<String> = convo.node_ids().cloned().collect();
+        nodes.sort();
+
+        let mut initial_nodes = Vec::new();
+        for (id, _) in convo.initial_nodes() {
+            if !initial_nodes.contains(id) {
+                initial_nodes.push(id.to_string());
+            }
+        }
+
+        let mut texts = HashMap::new();
+        let mut edges = Vec::new();
+        for node in nodes.iter() {
+            // the node IDs are taken from the conversation, so lookups always succeed
+            let (text, responses) = match (convo.text(node), convo.responses(node)) {
+                (Ok(text), Ok(responses)) => (text, responses),
+                _ => continue,
+            };
+            texts.insert(node.to_string(), text.to_string());
+
+            for response in responses {
+                let text = truncate(&response.text);
+                let on_select = !response.on_select.is_empty();
+                match response.check {
+                    None => edges.push(ConversationEdge {
+                        from: node.to_string(),
+                        to: response.next_node(None).cloned(),
+                        label: text,
+                        on_select,
+                    }),
+                    Some(ref check) => {
+                        for passed in [true, false] {
+                            let result = if passed { "pass" } else { "fail" };
+                            edges.push(ConversationEdge {
+                                from: node.to_string(),
+                                to: response.next_node(Some(passed)).cloned(),
+                                label: format!("[{} {}] {}", check.label(), result, text),
+                                on_select,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        ConversationGraph {
+            id: convo.id.to_string(),
+            nodes,
+            texts,
+            initial_nodes,
+            edges,
+        }
+    }
+
+    pub fn edges(&self) -> &[ConversationEdge] {
+        &self.edges
+    }
+
+    /// Returns all nodes that cannot be reached from any initial node
+    pub fn unreachable_nodes(&self) -> Vec<&str> {
+        let mut reached = HashSet::new();
+        let mut frontier: Vec<&str> = self.initial_nodes.iter().map(String::as_str).collect();
+        while let Some(node) = frontier.pop() {
+            if reached.insert(node) {
+                frontier.extend(self.successors(node));
+            }
+        }
+
+        self.nodes
+            .iter()
+            .map(String::as_str)
+            .filter(|node| !reached.contains(node))
+            .collect()
+    }
+
+    /// Returns the responses that end the conversation without firing any
+    /// `on_select` triggers, so that selecting them has no effect
+    pub fn dead_ends(&self) -> Vec<&ConversationEdge> {
+        self.edges
+            .iter()
+            .filter(|edge| edge.to.is_none() && !edge.on_select)
+            .collect()
+    }
+
+    /// Returns all nodes from which the conversation can never end.  A
+    /// conversation ends when a response without a `to` is selected, or
+    /// when a node without any responses is reached and shown as a bark.
+    pub fn endless_nodes(&self) -> Vec<&str> {
+        let mut can_end = HashSet::new();
+        for node in self.nodes.iter() {
+            let mut edges = self
+                .edges
+                .iter()
+                .filter(|edge| &edge.from == node)
+                .peekable();
+            if edges.peek().is_none() || edges.any(|edge| edge.to.is_none()) {
+                can_end.insert(node.as_str());
+            }
+        }
+
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for edge in self.edges.iter() {
+                let to = match edge.to {
+                    None => continue,
+                    Some(ref to) => to.as_str(),
+                };
+
+                if can_end.contains(to) && can_end.insert(edge.from.as_str()) {
+                    changed = true;
+                }
+            }
+        }
+
+        self.nodes
+            .iter()
+            .map(String::as_str)
+            .filter(|node| !can_end.contains(node))
+            .collect()
+    }
+
+    /// Returns each group of nodes that can loop back on itself.  Cycles
+    /// are often intentional, such as a hub node the player returns to
+    /// between topics, but are worth reviewing.
+    pub fn cycles(&self) -> Vec<Vec<&str>> {
+        let mut search = CycleSearch {
+            graph: self,
+            index: 0,
+            indices: HashMap::new(),
+            low_links: HashMap::new(),
+            stack: Vec::new(),
+            on_stack: HashSet::new(),
+            components: Vec::new(),
+        };
+
+        for node in self.nodes.iter() {
+            if !search.indices.contains_key(node.as_str()) {
+                search.visit(node);
+            }
+        }
+
+        let mut cycles: Vec<Vec<&str>> = search
+            .components
+            .into_iter()
+            .filter(|component| {
+                component.len() > 1 || self.successors(component[0]).any(|n| n == component[0])
+            })
+            .collect();
+        for cycle in cycles.iter_mut() {
+            cycle.sort_unstable();
+        }
+        cycles.sort();
+        cycles
+    }
+
+    /// Writes this conversation in the Graphviz DOT format.  Initial nodes
+    /// are drawn with a double border, while unreachable nodes, endless
+    /// nodes, and dead end responses are drawn in red.
+    pub fn to_dot(&self) -> String {
+        let unreachable = self.unreachable_nodes();
+        let endless = self.endless_nodes();
+
+        let mut out = String::new();
+        out.push_str(&format!("digraph \"{}\" {{\n", escape(&self.id)));
+        out.push_str("    node [shape=box];\n");
+
+        for node in self.nodes.iter() {
+            let mut attrs = format!(
+                "label=\"{}\\n{}\"",
+                escape(node),
+                escape(&truncate(&self.texts[node]))
+            );
+            if self.initial_nodes.contains(node) {
+                attrs.push_str(", peripheries=2");
+            }
+            if unreachable.contains(&node.as_str()) || endless.contains(&node.as_str()) {
+                attrs.push_str(", color=red");
+            }
+            out.push_str(&format!("    \"{}\" [{}];\n", escape(node), attrs));
+        }
+
+        if self.edges.iter().any(|edge| edge.to.is_none()) {
+            out.push_str(&format!(
+                "    \"{END_NODE}\" [label=\"End\", shape=oval];\n"
+            ));
+        }
+
+        for edge in self.edges.iter() {
+            let to = edge.to.as_deref().unwrap_or(END_NODE);
+            let color = if edge.to.is_none() && !edge.on_select {
+                ", color=red"
+            } else {
+                ""
+            };
+            out.push_str(&format!(
+                "    \"{}\" -> \"{}\" [label=\"{}\"{}];\n",
+                escape(&edge.from),
+                escape(to),
+                escape(&edge.label),
+                color
+            ));
+        }
+
+        out.push_str("}\n");
+        out
+    }
+
+    fn successors<'a>(&'a self, node: &'a str) -> impl Iterator<Item = &'a str> {
+        self.edges
+            .iter()
+            .filter(move |edge| edge.from == node)
+            .filter_map(|edge| edge.to.as_deref())
+    }
+}
+
+/// Tarjan's strongly connected components search over the conversation
+struct CycleSearch<'a> {
+    graph: &'a ConversationGraph,
+    index: usize,
+    indices: HashMap<&'a str, usize>,
+    low_links: HashMap<&'a str, usize>,
+    stack: Vec<&'a str>,
+    on_stack: HashSet<&'a str>,
+    components: Vec<Vec<&'a str>>,
+}
+
+impl<'a> CycleSearch<'a> {
+    fn visit(&mut self, node: &'a str) {
+        self.indices.insert(node, self.index);
+        self.low_links.insert(node, self.index);
+        self.index += 1;
+        self.stack.push(node);
+        self.on_stack.insert(node);
+
+        let graph = self.graph;
+        for next in graph.successors(node) {
+            if !self.indices.contains_key(next) {
+                self.visit(next);
+                let low = self.low_links[node].min(self.low_links[next]);
+                self.low_links.insert(node, low);
+            } else if self.on_stack.contains(next) {
+                let low = self.low_links[node].min(self.indices[next]);
+                self.low_links.insert(node, low);
+            }
+        }
+
+        if self.low_links[node] != self.indices[node] {
+            return;
+        }
+
+        let mut component = Vec::new();
+        while let Some(member) = self.stack.pop() {
+            self.on_stack.remove(member);
+            component.push(member);
+            if member == node {
+                break;
+            }
+        }
+        self.components.push(component);
+    }
+}
+
+fn truncate(text: &str) -> String {
+    let text = text.trim();
+    if text.chars().count() <= MAX_LABEL_LEN {
+        return text.to_string();
+    }
+
+    let mut out: String = text.chars().take(MAX_LABEL_LEN - 3).collect();
+    out.push_str("...");
+    out
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
@@ -0,0 +1,137 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2019 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::io::Error;
+
+use serde::Deserialize;
+
+use crate::{BonusList, Damage, DamageKind, OnTrigger, WeaponKind};
+use sulis_core::util::{gen_rand, invalid_data_error};
+
+/// A table of additional outcomes rolled when an attack scores a critical
+/// hit or fumbles.  A table applies to attacks made with any of its weapon
+/// kinds, or failing that, any of its damage kinds.  A table with neither
+/// is the default table for its kind, used when no other table matches.
+#[derive(Debug)]
+pub struct CritTable {
+    pub id: String,
+    pub kind: CritTableKind,
+    pub weapon_kinds: Vec<WeaponKind>,
+    pub damage_kinds: Vec<DamageKind>,
+    pub entries: Vec<CritTableEntry>,
+    total_weight: u32,
+}
+
+impl CritTable {
+    pub fn new(builder: CritTableBuilder) -> Result<CritTable, Error> {
+        if builder.entries.is_empty() {
+            return invalid_data_error(&format!(
+                "Crit table '{}' must have at least one entry",
+                builder.id
+            ));
+        }
+
+        let total_weight = builder.entries.iter().map(|entry| entry.weight).sum();
+        if total_weight == 0 {
+            return invalid_data_error(&format!(
+                "Crit table '{}' must have a non-zero total weight",
+                builder.id
+            ));
+        }
+
+        Ok(CritTable {
+            id: builder.id,
+            kind: builder.kind,
+            weapon_kinds: builder.weapon_kinds,
+            damage_kinds: builder.damage_kinds,
+            entries: builder.entries,
+            total_weight,
+        })
+    }
+
+    /// Returns true if this table is used when no more specific table of
+    /// the same kind matches an attack
+    pub fn is_default(&self) -> bool {
+        self.weapon_kinds.is_empty() && self.damage_kinds.is_empty()
+    }
+
+    /// Randomly picks one of the entries in this table, by weight
+    pub fn roll(&self) -> &CritTableEntry {
+        let mut roll = gen_rand(0, self.total_weight);
+        for entry in self.entries.iter() {
+            if roll < entry.weight {
+                return entry;
+            }
+            roll -= entry.weight;
+        }
+
+        unreachable!()
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CritTableKind {
+    /// Rolled against the target when an attack is a critical hit
+    Crit,
+
+    /// Rolled against the attacker when an attack is a fumble
+    Fumble,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct CritTableEntry {
+    /// The name shown in feedback text and the combat log when this entry
+    /// is rolled
+    pub name: String,
+    pub weight: u32,
+
+    #[serde(default)]
+    pub outcomes: Vec<CritOutcome>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub enum CritOutcome {
+    /// Additional damage dealt immediately
+    Damage(Damage),
+
+    /// An effect granting the specified bonuses for a number of rounds, such
+    /// as a knockdown or disarm
+    Effect { duration: u32, bonuses: BonusList },
+
+    /// The main hand weapon is unequipped and dropped on the ground
+    DropWeapon,
+
+    /// Fires the specified triggers, with the attacker as the parent and
+    /// the affected entity as the target
+    Trigger(Vec<OnTrigger>),
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct CritTableBuilder {
+    pub id: String,
+    pub kind: CritTableKind,
+
+    #[serde(default)]
+    pub weapon_kinds: Vec<WeaponKind>,
+
+    #[serde(default)]
+    pub damage_kinds: Vec<DamageKind>,
+
+    pub entries: Vec<CritTableEntry>,
+}
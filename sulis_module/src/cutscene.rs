@@ -15,13 +15,35 @@
 //  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
 
 use std::io::Error;
+use std::rc::Rc;
 
 use serde::Deserialize;
 
+use crate::condition::validate_triggers;
 use crate::{Module, OnTrigger};
+use sulis_core::image::Image;
+use sulis_core::resource::ResourceSet;
+use sulis_core::util::unable_to_create_error;
 
 pub struct Frame {
     pub text: String,
+
+    /// Drawn behind the frame text
+    pub image: Option<Rc<dyn Image>>,
+
+    /// If set, the frame automatically advances after this many millis
+    pub duration: Option<u32>,
+
+    /// The music to switch to when this frame is shown
+    pub music: Option<String>,
+    pub stop_music: bool,
+    pub sfx: Option<String>,
+
+    /// Whether this frame fades in from black
+    pub fade_in: bool,
+
+    /// Fired each time this frame is shown
+    pub on_show: Vec<OnTrigger>,
 }
 
 pub struct Cutscene {
@@ -31,11 +53,38 @@ pub struct Cutscene {
 }
 
 impl Cutscene {
-    pub fn new(builder: CutsceneBuilder, _module: &Module) -> Result<Cutscene, Error> {
+    pub fn new(builder: CutsceneBuilder, module: &Module) -> Result<Cutscene, Error> {
         let mut frames = Vec::new();
         for frame_builder in builder.frames {
+            let image = match frame_builder.image {
+                None => None,
+                Some(ref image_id) => match ResourceSet::image(image_id) {
+                    None => {
+                        warn!("No image found for image '{}'", image_id);
+                        return unable_to_create_error("cutscene", &builder.id);
+                    }
+                    Some(image) => Some(image),
+                },
+            };
+
+            for sound_id in frame_builder.music.iter().chain(frame_builder.sfx.iter()) {
+                if let Err(e) = ResourceSet::sound(sound_id) {
+                    warn!("Unable to locate sound '{}': {}", sound_id, e);
+                    return unable_to_create_error("cutscene", &builder.id);
+                }
+            }
+
+            validate_triggers(&frame_builder.on_show, module)?;
+
             let frame = Frame {
                 text: frame_builder.text,
+                image,
+                duration: frame_builder.duration,
+                music: frame_builder.music,
+                stop_music: frame_builder.stop_music,
+                sfx: frame_builder.sfx,
+                fade_in: frame_builder.fade_in,
+                on_show: frame_builder.on_show,
             };
             frames.push(frame);
         }
@@ -52,6 +101,27 @@ impl Cutscene {
 #[serde(deny_unknown_fields)]
 pub struct FrameBuilder {
     pub text: String,
+
+    #[serde(default)]
+    pub image: Option<String>,
+
+    #[serde(default)]
+    pub duration: Option<u32>,
+
+    #[serde(default)]
+    pub music: Option<String>,
+
+    #[serde(default)]
+    pub stop_music: bool,
+
+    #[serde(default)]
+    pub sfx: Option<String>,
+
+    #[serde(default)]
+    pub fade_in: bool,
+
+    #[serde(default)]
+    pub on_show: Vec<OnTrigger>,
 }
 
 #[derive(Deserialize, Debug)]
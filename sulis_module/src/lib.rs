@@ -35,12 +35,24 @@ pub use self::ai::AITemplate;
 pub mod area;
 pub use self::area::Area;
 
+pub mod banter;
+pub use self::banter::Banter;
+
 pub mod class;
 pub use self::class::Class;
 
+pub mod condition;
+pub use self::condition::Condition;
+
 pub mod conversation;
 pub use self::conversation::Conversation;
 
+pub mod conversation_graph;
+pub use self::conversation_graph::ConversationGraph;
+
+pub mod crit_table;
+pub use self::crit_table::{CritTable, CritTableKind};
+
 pub mod cutscene;
 pub use self::cutscene::Cutscene;
 
@@ -104,13 +116,21 @@ pub use self::race::Race;
 pub mod rules;
 pub use self::rules::bonus;
 pub use self::rules::{
-    AccuracyKind, Armor, ArmorKind, Attack, AttackBonuses, AttackKind, Attribute, AttributeList,
-    Bonus, BonusKind, BonusList, Damage, DamageKind, DamageList, HitFlags, HitKind, ItemKind,
-    QuickSlot, Resistance, Rules, Slot, StatList, Time, WeaponKind, WeaponStyle, ROUND_TIME_MILLIS,
+    AccuracyKind, Armor, ArmorKind, Attack, AttackBonuses, AttackKind, AttackRoll, Attribute,
+    AttributeInfo, AttributeList, Bonus, BonusKind, BonusList, Cover, Damage, DamageKind,
+    DamageKindInfo, DamageList, DamageRoll, DerivedStat, EquipmentKindInfo, Formula, HitFlags,
+    HitKind, InitiativeMode, ItemKind, QuickSlot, RangedModifiers, Resistance, Rules, Slot,
+    StatList, Time, WeaponKind, WeaponStyle, ROUND_TIME_MILLIS,
 };
 
+pub mod sequence;
+pub use self::sequence::Sequence;
+
+pub mod surface_type;
+pub use self::surface_type::SurfaceType;
+
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::ffi::OsStr;
 use std::fmt::{self, Display};
 use std::fs;
@@ -131,9 +151,11 @@ use self::area::{
     Tileset,
 };
 use self::area::{AreaBuilder, Tile};
+use self::banter::BanterBuilder;
 use self::campaign::CampaignBuilder;
 use self::class::ClassBuilder;
 use self::conversation::ConversationBuilder;
+use self::crit_table::CritTableBuilder;
 use self::cutscene::CutsceneBuilder;
 use self::encounter::EncounterBuilder;
 use self::item::ItemBuilder;
@@ -141,6 +163,8 @@ use self::loot_list::LootListBuilder;
 use self::object_size::ObjectSizeBuilder;
 use self::prop::PropBuilder;
 use self::race::RaceBuilder;
+use self::sequence::SequenceBuilder;
+use self::surface_type::SurfaceTypeBuilder;
 
 pub const MOVE_TO_THRESHOLD: f32 = 0.1;
 
@@ -157,8 +181,10 @@ pub struct Module {
     actors: HashMap<String, Rc<Actor>>,
     ai_templates: HashMap<String, Rc<AITemplate>>,
     areas: HashMap<String, Rc<Area>>,
+    banters: HashMap<String, Rc<Banter>>,
     classes: HashMap<String, Rc<Class>>,
     conversations: HashMap<String, Rc<Conversation>>,
+    crit_tables: HashMap<String, Rc<CritTable>>,
     cutscenes: HashMap<String, Rc<Cutscene>>,
     encounters: HashMap<String, Rc<Encounter>>,
     items: HashMap<String, Rc<Item>>,
@@ -167,7 +193,9 @@ pub struct Module {
     props: HashMap<String, Rc<Prop>>,
     quests: HashMap<String, Rc<Quest>>,
     races: HashMap<String, Rc<Race>>,
+    sequences: HashMap<String, Rc<Sequence>>,
     sizes: HashMap<String, Rc<ObjectSize>>,
+    surface_types: HashMap<String, Rc<SurfaceType>>,
     tiles: HashMap<String, Rc<Tile>>,
     scripts: HashMap<String, String>,
 
@@ -410,6 +438,7 @@ impl Module {
 
         let rules: Rules = read_builder(rules_yaml)?;
         rules.validate()?;
+        rules.init_kinds()?;
 
         let campaign_builder: CampaignBuilder = read_builder(campaign_yaml)?;
 
@@ -421,8 +450,10 @@ impl Module {
             module.actors.clear();
             module.ai_templates.clear();
             module.areas.clear();
+            module.banters.clear();
             module.classes.clear();
             module.conversations.clear();
+            module.crit_tables.clear();
             module.cutscenes.clear();
             module.encounters.clear();
             module.items.clear();
@@ -431,7 +462,9 @@ impl Module {
             module.quests.clear();
             module.props.clear();
             module.races.clear();
+            module.sequences.clear();
             module.sizes.clear();
+            module.surface_types.clear();
             module.tiles.clear();
             module.scripts.clear();
             module.generators.clear();
@@ -561,6 +594,67 @@ impl Module {
                 );
             }
 
+            let area_ids: HashSet<String> = builder_set.area_builders.keys().cloned().collect();
+            let location_ids: HashSet<String> = campaign_builder
+                .world_map
+                .locations
+                .keys()
+                .cloned()
+                .collect();
+            for (id, builder) in builder_set.banter_builders {
+                insert_if_ok(
+                    "banter",
+                    id,
+                    Banter::new(builder, &module, &area_ids),
+                    &mut module.banters,
+                );
+            }
+
+            for (id, builder) in builder_set.sequence_builders {
+                insert_if_ok(
+                    "sequence",
+                    id,
+                    Sequence::new(builder, &module),
+                    &mut module.sequences,
+                );
+            }
+
+            // quests are loaded early so conversations can check them, but
+            // objectives can only be checked once actors and items are loaded
+            let invalid_quests: Vec<String> = module
+                .quests
+                .values()
+                .filter_map(|quest| {
+                    let e = quest.validate(&module, &area_ids, &location_ids).err()?;
+                    warn!("{}", e);
+                    warn!("Unable to create quest '{}'", quest.id);
+                    Some(quest.id.to_string())
+                })
+                .collect();
+            for id in invalid_quests {
+                module.quests.remove(&id);
+            }
+
+            let surface_ids: HashSet<String> =
+                builder_set.surface_type_builders.keys().cloned().collect();
+            for (id, builder) in builder_set.surface_type_builders {
+                insert_if_ok(
+                    "surface type",
+                    id,
+                    SurfaceType::new(builder, &surface_ids),
+                    &mut module.surface_types,
+                );
+            }
+
+            for (id, builder) in builder_set.crit_table_builders {
+                insert_if_ok(
+                    "crit table",
+                    id,
+                    CritTable::new(builder),
+                    &mut module.crit_tables,
+                );
+            }
+
             for (id, builder) in builder_set.generator_builders {
                 insert_if_ok(
                     "generator",
@@ -579,6 +673,12 @@ impl Module {
             let area = Area::new(builder);
             MODULE.with(|module| {
                 let mut module = module.borrow_mut();
+                let area = area.and_then(|area| {
+                    for trigger in area.triggers.iter() {
+                        condition::validate_triggers(&trigger.on_activate, &module)?;
+                    }
+                    Ok(area)
+                });
                 insert_if_ok("area", id, area, &mut module.areas);
             });
         }
@@ -745,8 +845,10 @@ impl Module {
         actor, actors, Actor;
         ai_template, ai_templates, AITemplate;
         area, areas, Area;
+        banter, banters, Banter;
         class, classes, Class;
         conversation, conversations, Conversation;
+        crit_table, crit_tables, CritTable;
         cutscene, cutscenes, Cutscene;
         encounter, encounters, Encounter;
         item, items, Item;
@@ -756,9 +858,11 @@ impl Module {
         quest, quests, Quest;
         prop, props, Prop;
         race, races, Race;
+        sequence, sequences, Sequence;
         tile, tiles, Tile;
         generator, generators, AreaGenerator;
         size, sizes, ObjectSize;
+        surface_type, surface_types, SurfaceType;
         feature, features, Feature
         );
 
@@ -798,6 +902,48 @@ impl Module {
         MODULE.with(|r| all_resources(&r.borrow().classes))
     }
 
+    pub fn all_banters() -> Vec<Rc<Banter>> {
+        MODULE.with(|r| all_resources(&r.borrow().banters))
+    }
+
+    pub fn all_conversations() -> Vec<Rc<Conversation>> {
+        MODULE.with(|r| all_resources(&r.borrow().conversations))
+    }
+
+    /// Finds the table of the specified kind to roll on for an attack with
+    /// the given weapon and damage kinds.  A table matching the weapon kind
+    /// is preferred, followed by one matching the damage kind, and finally
+    /// a default table.
+    pub fn crit_table_for(
+        kind: CritTableKind,
+        weapon_kind: Option<WeaponKind>,
+        damage_kind: Option<DamageKind>,
+    ) -> Option<Rc<CritTable>> {
+        MODULE.with(|r| {
+            let module = r.borrow();
+            let mut tables: Vec<_> = module
+                .crit_tables
+                .values()
+                .filter(|table| table.kind == kind)
+                .collect();
+            tables.sort_by(|a, b| a.id.cmp(&b.id));
+
+            let by_weapon = tables.iter().find(|table| match weapon_kind {
+                None => false,
+                Some(weapon_kind) => table.weapon_kinds.contains(&weapon_kind),
+            });
+            let by_damage = tables.iter().find(|table| match damage_kind {
+                None => false,
+                Some(damage_kind) => table.damage_kinds.contains(&damage_kind),
+            });
+
+            by_weapon
+                .or(by_damage)
+                .or_else(|| tables.iter().find(|table| table.is_default()))
+                .map(|table| Rc::clone(table))
+        })
+    }
+
     pub fn all_encounters() -> Vec<Rc<Encounter>> {
         MODULE.with(|r| all_resources(&r.borrow().encounters))
     }
@@ -829,15 +975,19 @@ struct ModuleBuilder {
     actor_builders: HashMap<String, ActorBuilder>,
     ai_builders: HashMap<String, AITemplate>,
     area_builders: HashMap<String, AreaBuilder>,
+    banter_builders: HashMap<String, BanterBuilder>,
     class_builders: HashMap<String, ClassBuilder>,
     cutscene_builders: HashMap<String, CutsceneBuilder>,
     conversation_builders: HashMap<String, ConversationBuilder>,
+    crit_table_builders: HashMap<String, CritTableBuilder>,
     encounter_builders: HashMap<String, EncounterBuilder>,
     item_builders: HashMap<String, ItemBuilder>,
     loot_builders: HashMap<String, LootListBuilder>,
     prop_builders: HashMap<String, PropBuilder>,
     race_builders: HashMap<String, RaceBuilder>,
+    sequence_builders: HashMap<String, SequenceBuilder>,
     size_builders: HashMap<String, ObjectSizeBuilder>,
+    surface_type_builders: HashMap<String, SurfaceTypeBuilder>,
     tile_builders: HashMap<String, Tileset>,
     generator_builders: HashMap<String, GeneratorBuilder>,
 
@@ -854,8 +1004,10 @@ impl ModuleBuilder {
             actor_builders: read_builders(resources, Actor)?,
             ai_builders: read_builders(resources, AiTemplate)?,
             area_builders: read_builders(resources, Area)?,
+            banter_builders: read_builders(resources, Banter)?,
             class_builders: read_builders(resources, Class)?,
             conversation_builders: read_builders(resources, Conversation)?,
+            crit_table_builders: read_builders(resources, CritTable)?,
             cutscene_builders: read_builders(resources, Cutscene)?,
             encounter_builders: read_builders(resources, Encounter)?,
             item_builders: read_builders(resources, Item)?,
@@ -864,7 +1016,9 @@ impl ModuleBuilder {
             prop_builders: read_builders(resources, Prop)?,
             quests: read_builders(resources, Quest)?,
             race_builders: read_builders(resources, Race)?,
+            sequence_builders: read_builders(resources, Sequence)?,
             size_builders: read_builders(resources, Size)?,
+            surface_type_builders: read_builders(resources, SurfaceType)?,
             tile_builders: read_builders(resources, Tile)?,
             generator_builders: read_builders(resources, Generator)?,
         })
@@ -19,6 +19,7 @@ use std::collections::HashMap;
 use serde::{Serialize, Deserialize};
 
 use crate::rules::Time;
+use crate::Condition;
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
 #[serde(deny_unknown_fields)]
@@ -91,6 +92,7 @@ pub enum QuestEntryState {
     Visible,
     Active,
     Complete,
+    Failed,
 }
 
 impl QuestEntryState {
@@ -100,6 +102,7 @@ impl QuestEntryState {
             "Visible" => QuestEntryState::Visible,
             "Active" => QuestEntryState::Active,
             "Complete" => QuestEntryState::Complete,
+            "Failed" => QuestEntryState::Failed,
             _ => {
                 warn!("Invalid quest state '{}'", s);
                 QuestEntryState::Hidden
@@ -143,6 +146,7 @@ pub enum OnTrigger {
     PlayerFlag(String),
     ShowMerchant(MerchantData),
     ShowCutscene(String),
+    StartSequence(String),
     StartConversation(String),
     FireScript(ScriptData),
     SayLine(String),
@@ -155,6 +159,7 @@ pub enum OnTrigger {
     ShowMenu(MenuData),
     QuestState(QuestStateData),
     NotQuestState(QuestStateData),
+    Condition(Condition),
     FadeOutIn,
     CheckEndTurn,
 }
@@ -14,6 +14,7 @@
 //  You should have received a copy of the GNU General Public License
 //  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
 
+use std::collections::HashMap;
 use std::io::Error;
 use std::rc::Rc;
 
@@ -26,7 +27,9 @@ use sulis_core::ui::AnimationState;
 use sulis_core::util::{unable_to_create_error, Offset, Point, Rect};
 
 use crate::area::tile::verify_point;
-use crate::{LootList, Module, ObjectSize, OnTrigger};
+use crate::{
+    Ability, Armor, Damage, DamageKind, Item, LootList, Module, ObjectSize, OnTrigger, Resistance,
+};
 
 #[derive(Debug)]
 pub enum Interactive {
@@ -42,6 +45,57 @@ pub enum Interactive {
         fire_more_than_once: bool,
     },
     Hover,
+    Destructible {
+        hp: u32,
+        armor: Armor,
+        resistance: Resistance,
+        loot: Option<Rc<LootList>>,
+        closed_impass: Vec<Point>,
+        closed_invis: Vec<Point>,
+        explosion: Option<Explosion>,
+        on_destroyed: Vec<OnTrigger>,
+    },
+    Trap {
+        detect_difficulty: i32,
+        disarm_difficulty: Option<i32>,
+        effect: TrapEffect,
+    },
+}
+
+/// Area damage dealt to everything within `radius` of the prop's center
+/// when a destructible prop is destroyed
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Explosion {
+    pub damage: Damage,
+    pub radius: f32,
+}
+
+/// A lock on a door or container.  A locked prop may be opened by a party
+/// member carrying the `key` item, or picked with a mechanics check against
+/// the `difficulty`, if one is specified.
+#[derive(Debug)]
+pub struct Lock {
+    pub key: Option<Rc<Item>>,
+    pub consume_key: bool,
+    pub difficulty: Option<i32>,
+    pub ability: Option<Rc<Ability>>,
+    pub trap: Option<TrapEffect>,
+}
+
+/// Fired when a hidden trap is sprung, or an attempt to pick a lock or
+/// disarm a trap fails
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct TrapEffect {
+    #[serde(default)]
+    pub damage: Option<Damage>,
+
+    #[serde(default)]
+    pub on_trigger: Vec<OnTrigger>,
+
+    #[serde(default)]
+    pub fire_more_than_once: bool,
 }
 
 #[derive(Debug)]
@@ -57,6 +111,7 @@ pub struct Prop {
     pub interactive: Interactive,
     pub aerial: bool,
     pub status_text: Option<String>,
+    pub lock: Option<Lock>,
 }
 
 impl Prop {
@@ -131,18 +186,50 @@ impl Prop {
             InteractiveBuilder::Not => Interactive::Not,
             InteractiveBuilder::Hover => Interactive::Hover,
             InteractiveBuilder::Container { loot } => {
-                let loot = match loot {
-                    None => None,
-                    Some(loot) => match module.loot_lists.get(&loot) {
-                        None => {
-                            warn!("Unable to find loot list '{}'", loot);
-                            return unable_to_create_error("prop", &builder.id);
-                        }
-                        Some(loot) => Some(Rc::clone(loot)),
-                    },
+                let loot = match get_loot(module, loot) {
+                    Err(()) => return unable_to_create_error("prop", &builder.id),
+                    Ok(loot) => loot,
                 };
                 Interactive::Container { loot }
             }
+            InteractiveBuilder::Destructible {
+                hp,
+                armor: base_armor,
+                armor_kinds,
+                resistance: resistance_kinds,
+                loot,
+                closed_impass,
+                closed_invis,
+                explosion,
+                on_destroyed,
+            } => {
+                let loot = match get_loot(module, loot) {
+                    Err(()) => return unable_to_create_error("prop", &builder.id),
+                    Ok(loot) => loot,
+                };
+
+                let mut armor = Armor::default();
+                armor.add_base(base_armor);
+                for (kind, amount) in armor_kinds {
+                    armor.add_kind(kind, amount);
+                }
+
+                let mut resistance = Resistance::default();
+                for (kind, amount) in resistance_kinds {
+                    resistance.add_kind(kind, amount);
+                }
+
+                Interactive::Destructible {
+                    hp,
+                    armor,
+                    resistance,
+                    loot,
+                    closed_impass,
+                    closed_invis,
+                    explosion,
+                    on_destroyed,
+                }
+            }
             InteractiveBuilder::Door {
                 initially_open,
                 closed_impass,
@@ -156,6 +243,40 @@ impl Prop {
                 on_activate,
                 fire_more_than_once,
             },
+            InteractiveBuilder::Trap {
+                detect_difficulty,
+                disarm_difficulty,
+                effect,
+            } => {
+                if !impass.is_empty() {
+                    warn!("Traps must be passable");
+                    return unable_to_create_error("prop", &builder.id);
+                }
+
+                Interactive::Trap {
+                    detect_difficulty,
+                    disarm_difficulty,
+                    effect,
+                }
+            }
+        };
+
+        let lock = match builder.lock {
+            None => None,
+            Some(lock) => {
+                match interactive {
+                    Interactive::Door { .. } | Interactive::Container { .. } => (),
+                    _ => {
+                        warn!("Only doors and containers may have a lock");
+                        return unable_to_create_error("prop", &builder.id);
+                    }
+                }
+
+                match create_lock(lock, module) {
+                    Err(()) => return unable_to_create_error("prop", &builder.id),
+                    Ok(lock) => Some(lock),
+                }
+            }
         };
 
         Ok(Prop {
@@ -170,6 +291,7 @@ impl Prop {
             interactive,
             aerial: builder.aerial,
             status_text: builder.status_text,
+            lock,
         })
     }
 
@@ -192,6 +314,53 @@ impl Prop {
     }
 }
 
+fn create_lock(builder: LockBuilder, module: &Module) -> Result<Lock, ()> {
+    let key = match builder.key {
+        None => None,
+        Some(key) => match module.items.get(&key) {
+            None => {
+                warn!("Unable to find key item '{}'", key);
+                return Err(());
+            }
+            Some(item) => Some(Rc::clone(item)),
+        },
+    };
+
+    let ability = match builder.ability {
+        None => None,
+        Some(ability) => match module.abilities.get(&ability) {
+            None => {
+                warn!("Unable to find lock picking ability '{}'", ability);
+                return Err(());
+            }
+            Some(ability) => Some(Rc::clone(ability)),
+        },
+    };
+
+    Ok(Lock {
+        key,
+        consume_key: builder.consume_key,
+        difficulty: builder.difficulty,
+        ability,
+        trap: builder.trap,
+    })
+}
+
+fn get_loot(module: &Module, loot: Option<String>) -> Result<Option<Rc<LootList>>, ()> {
+    let loot = match loot {
+        None => return Ok(None),
+        Some(loot) => loot,
+    };
+
+    match module.loot_lists.get(&loot) {
+        None => {
+            warn!("Unable to find loot list '{}'", loot);
+            Err(())
+        }
+        Some(loot) => Ok(Some(Rc::clone(loot))),
+    }
+}
+
 #[derive(Deserialize, Debug)]
 #[serde(deny_unknown_fields)]
 pub enum InteractiveBuilder {
@@ -211,6 +380,41 @@ pub enum InteractiveBuilder {
         fire_more_than_once: bool,
     },
     Hover,
+    Destructible {
+        hp: u32,
+
+        #[serde(default)]
+        armor: i32,
+
+        #[serde(default)]
+        armor_kinds: HashMap<DamageKind, i32>,
+
+        #[serde(default)]
+        resistance: HashMap<DamageKind, i32>,
+
+        #[serde(default)]
+        loot: Option<String>,
+
+        #[serde(default)]
+        closed_impass: Vec<Point>,
+
+        #[serde(default)]
+        closed_invis: Vec<Point>,
+
+        #[serde(default)]
+        explosion: Option<Explosion>,
+
+        #[serde(default)]
+        on_destroyed: Vec<OnTrigger>,
+    },
+    Trap {
+        detect_difficulty: i32,
+
+        #[serde(default)]
+        disarm_difficulty: Option<i32>,
+
+        effect: TrapEffect,
+    },
 }
 
 #[derive(Deserialize, Debug)]
@@ -231,4 +435,25 @@ pub struct PropBuilder {
     pub aerial: bool,
     pub interactive: InteractiveBuilder,
     pub status_text: Option<String>,
+    #[serde(default)]
+    pub lock: Option<LockBuilder>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct LockBuilder {
+    #[serde(default)]
+    pub key: Option<String>,
+
+    #[serde(default)]
+    pub consume_key: bool,
+
+    #[serde(default)]
+    pub difficulty: Option<i32>,
+
+    #[serde(default)]
+    pub ability: Option<String>,
+
+    #[serde(default)]
+    pub trap: Option<TrapEffect>,
 }
@@ -14,10 +14,14 @@
 //  You should have received a copy of the GNU General Public License
 //  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::io::Error;
 
 use serde::Deserialize;
 
+use crate::Module;
+use sulis_core::util::{invalid_data_error, Point};
+
 #[derive(Deserialize, Debug)]
 #[serde(deny_unknown_fields)]
 pub struct Quest {
@@ -27,8 +31,196 @@ pub struct Quest {
     pub entries: HashMap<String, QuestEntry>,
 }
 
+impl Quest {
+    /// Checks that the actors, items, areas, and world map locations
+    /// referenced by the entries of this quest exist
+    pub fn validate(
+        &self,
+        module: &Module,
+        area_ids: &HashSet<String>,
+        location_ids: &HashSet<String>,
+    ) -> Result<(), Error> {
+        for (id, entry) in self.entries.iter() {
+            let objectives = entry.objective.iter().chain(entry.fail_on.iter());
+            for objective in objectives {
+                if let Err(e) = objective.validate(module, area_ids) {
+                    return invalid_data_error(&format!(
+                        "{} in entry '{}' of quest '{}'",
+                        e, id, self.id
+                    ));
+                }
+            }
+
+            if let Some(ref marker) = entry.marker {
+                let (kind, marker_id, valid) = match marker {
+                    QuestMarker::Area { area, .. } => ("area", area, area_ids.contains(area)),
+                    QuestMarker::Location(location) => {
+                        ("location", location, location_ids.contains(location))
+                    }
+                };
+
+                if !valid {
+                    return invalid_data_error(&format!(
+                        "Invalid marker {} '{}' in entry '{}' of quest '{}'",
+                        kind, marker_id, id, self.id
+                    ));
+                }
+            }
+
+            if let Some(ref reward) = entry.reward {
+                for item in reward.items.iter() {
+                    if !module.items.contains_key(item) {
+                        return invalid_data_error(&format!(
+                            "Invalid reward item '{}' in entry '{}' of quest '{}'",
+                            item, id, self.id
+                        ));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns true if there is at least one entry with an objective
+    pub fn has_objectives(&self) -> bool {
+        self.entries.values().any(|entry| entry.objective.is_some())
+    }
+}
+
 #[derive(Deserialize, Debug)]
 #[serde(deny_unknown_fields)]
 pub struct QuestEntry {
     pub description: String,
+
+    /// Completes this entry automatically while it is active
+    #[serde(default)]
+    pub objective: Option<Objective>,
+
+    /// Optional entries are not needed to complete the quest
+    #[serde(default)]
+    pub optional: bool,
+
+    /// Fails this entry if met before the objective
+    #[serde(default)]
+    pub fail_on: Option<Objective>,
+
+    #[serde(default)]
+    pub reward: Option<QuestReward>,
+
+    /// Shown on the world map and area view while this entry is active
+    #[serde(default)]
+    pub marker: Option<QuestMarker>,
+}
+
+impl QuestEntry {
+    /// The marker for this entry, falling back to the destination of a
+    /// reach objective if no marker is set
+    pub fn map_marker(&self) -> Option<QuestMarker> {
+        if let Some(ref marker) = self.marker {
+            return Some(marker.clone());
+        }
+
+        match self.objective {
+            Some(Objective::Reach {
+                ref area, point, ..
+            }) => Some(QuestMarker::Area {
+                area: area.to_string(),
+                point,
+            }),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub enum QuestMarker {
+    Area {
+        area: String,
+
+        /// If not set, the marker is only shown on the world map
+        #[serde(default)]
+        point: Option<Point>,
+    },
+
+    /// A world map location ID
+    Location(String),
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub enum Objective {
+    Kill {
+        actor: String,
+
+        #[serde(default = "one")]
+        count: u32,
+    },
+    Collect {
+        item: String,
+
+        #[serde(default = "one")]
+        count: u32,
+    },
+    Reach {
+        area: String,
+
+        /// If not set, entering the area is enough
+        #[serde(default)]
+        point: Option<Point>,
+
+        #[serde(default = "default_reach_dist")]
+        dist: f32,
+    },
+    Talk {
+        actor: String,
+    },
+}
+
+impl Objective {
+    /// The progress needed to meet this objective
+    pub fn count(&self) -> u32 {
+        match self {
+            Objective::Kill { count, .. } | Objective::Collect { count, .. } => *count,
+            Objective::Reach { .. } | Objective::Talk { .. } => 1,
+        }
+    }
+
+    fn validate(&self, module: &Module, area_ids: &HashSet<String>) -> Result<(), Error> {
+        let (kind, id, valid) = match self {
+            Objective::Kill { actor, .. } | Objective::Talk { actor } => {
+                ("actor", actor, module.actors.contains_key(actor))
+            }
+            Objective::Collect { item, .. } => ("item", item, module.items.contains_key(item)),
+            Objective::Reach { area, .. } => ("area", area, area_ids.contains(area)),
+        };
+
+        if valid {
+            Ok(())
+        } else {
+            invalid_data_error(&format!("Invalid objective {} '{}'", kind, id))
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct QuestReward {
+    #[serde(default)]
+    pub xp: u32,
+
+    #[serde(default)]
+    pub coins: i32,
+
+    #[serde(default)]
+    pub items: Vec<String>,
+}
+
+fn one() -> u32 {
+    1
+}
+
+fn default_reach_dist() -> f32 {
+    3.0
 }
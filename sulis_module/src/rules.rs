@@ -34,7 +34,9 @@ pub use self::attack::AttackKind;
 
 pub mod attribute;
 pub use self::attribute::Attribute;
+pub use self::attribute::AttributeInfo;
 pub use self::attribute::AttributeList;
+pub use self::attribute::DerivedStat;
 
 pub mod bonus;
 pub use self::bonus::AttackBonuses;
@@ -42,17 +44,37 @@ pub use self::bonus::Bonus;
 pub use self::bonus::BonusKind;
 pub use self::bonus::BonusList;
 
+pub mod cover;
+pub use self::cover::Cover;
+pub use self::cover::CoverRules;
+pub use self::cover::RangedModifiers;
+
 pub mod damage;
 pub use self::damage::Damage;
 pub use self::damage::DamageKind;
+pub use self::damage::DamageKindInfo;
 pub use self::damage::DamageList;
 
+pub mod equipment_kind;
+pub use self::equipment_kind::ArmorKind;
+pub use self::equipment_kind::EquipmentKindInfo;
+pub use self::equipment_kind::WeaponKind;
+
+pub mod formula;
+pub use self::formula::Formula;
+
+pub mod reactions;
+pub use self::reactions::ReactionRules;
+
 pub mod resistance;
 pub use self::resistance::Resistance;
 
 pub mod stat_list;
 pub use self::stat_list::StatList;
 
+pub mod stealth;
+pub use self::stealth::StealthRules;
+
 use crate::area::LocationKind;
 use sulis_core::ui::{color, Color};
 use sulis_core::util::{gen_rand, invalid_data_error};
@@ -72,19 +94,35 @@ pub struct Rules {
     pub display_ap: u32,
     pub swap_weapons_ap: u32,
     pub initiative_roll_max: i32,
+    pub initiative_mode: InitiativeMode,
     pub base_flanking_angle: i32,
     pub graze_percentile: u32,
     pub hit_percentile: u32,
     pub crit_chance: u32,
+    pub fumble_chance: u32,
 
     pub flanking_accuracy_bonus: i32,
     pub hidden_accuracy_bonus: i32,
 
+    pub stealth: StealthRules,
+    pub reactions: ReactionRules,
+    pub cover: CoverRules,
+
     pub graze_damage_multiplier: f32,
     pub crit_damage_multiplier: f32,
 
     pub dual_wield_damage_multiplier: f32,
 
+    pub damage_kinds: Vec<DamageKindInfo>,
+    pub default_damage_kind: String,
+
+    pub weapon_kinds: Vec<EquipmentKindInfo>,
+    pub armor_kinds: Vec<EquipmentKindInfo>,
+    default_weapon_kind: String,
+
+    pub attributes: Vec<AttributeInfo>,
+    #[serde(default)]
+    pub derived_stats: HashMap<DerivedStat, Formula>,
     pub base_attribute: i32,
     pub builder_max_attribute: i32,
     pub builder_min_attribute: i32,
@@ -178,6 +216,20 @@ impl Rules {
     }
 
     pub fn validate(&self) -> Result<(), Error> {
+        for group in self.reactions.provoking_groups.iter() {
+            if !self.ability_groups.contains(group) {
+                return invalid_data_error(&format!(
+                    "Invalid ability group '{group}' in reactions provoking_groups"
+                ));
+            }
+        }
+
+        if self.stealth.suspicious_threshold > self.stealth.alerted_threshold {
+            return invalid_data_error(
+                "Stealth suspicious_threshold must not exceed alerted_threshold",
+            );
+        }
+
         if self.hour_names.len() != self.hours_per_day as usize {
             return invalid_data_error(&format!(
                 "Must specify '{}' hours names to match number of hours",
@@ -197,6 +249,72 @@ impl Rules {
         Ok(())
     }
 
+    /// Sets up the attributes and the damage, weapon, and armor kinds defined
+    /// by these rules, and validates the derived stat formulas.  This must be
+    /// called before any resources using them are parsed.
+    pub fn init_kinds(&self) -> Result<(), Error> {
+        Attribute::init(&self.attributes, self.base_attribute)?;
+        attribute::validate_derived_stats(&self.derived_stats)?;
+        DamageKind::init(&self.damage_kinds, &self.default_damage_kind)?;
+        WeaponKind::init(&self.weapon_kinds)?;
+        ArmorKind::init(&self.armor_kinds)?;
+
+        if WeaponKind::find(&self.default_weapon_kind).is_none() {
+            return invalid_data_error(&format!(
+                "Default weapon kind '{}' is not a defined weapon kind",
+                self.default_weapon_kind
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Computes the specified stat for a character with the given attributes
+    /// and total level.  Stats without a formula in `derived_stats` are
+    /// `base + bonus`, except for `HpPerLevel`, which is `bonus * level`.
+    pub fn derived_stat(&self, stat: DerivedStat, attributes: &AttributeList, level: u32) -> f32 {
+        use self::DerivedStat::*;
+        let base = match stat {
+            MeleeAccuracy | RangedAccuracy | SpellAccuracy => self.base_accuracy as i32,
+            Defense | Fortitude | Reflex | Will => self.base_defense as i32,
+            Stealth => self.stealth.base_stealth,
+            Detection => self.stealth.base_detection,
+            _ => 0,
+        } as f32;
+
+        let bonus: f32 = Attribute::iter()
+            .map(|attr| {
+                let value = attributes.bonus(attr, self.base_attribute) as f32;
+                let bonus = value * attr.bonus_factor(stat);
+                if stat.is_integer() {
+                    bonus.trunc()
+                } else {
+                    bonus
+                }
+            })
+            .sum();
+
+        let level = level as f32;
+        let formula = match self.derived_stats.get(&stat) {
+            Some(formula) => formula,
+            None if stat == HpPerLevel => return bonus * level,
+            None => return base + bonus,
+        };
+
+        formula.eval(&|var: &str| match var {
+            "base" => Some(base),
+            "bonus" => Some(bonus),
+            "base_attribute" => Some(self.base_attribute as f32),
+            "level" => Some(level),
+            _ => Attribute::find_short_name(var).map(|attr| attributes.get(attr) as f32),
+        })
+    }
+
+    /// The weapon kind used for attacks made without a weapon
+    pub fn default_weapon_kind(&self) -> WeaponKind {
+        WeaponKind::find(&self.default_weapon_kind).unwrap()
+    }
+
     pub fn compute_millis(&self, time: Time) -> usize {
         let mut millis = time.millis as usize;
 
@@ -259,6 +377,22 @@ impl Rules {
         resistance: &Resistance,
         multiplier: f32,
     ) -> Vec<(DamageKind, u32)> {
+        self.roll_damage_detailed(damage, armor, resistance, multiplier)
+            .into_iter()
+            .filter(|roll| roll.amount > 0)
+            .map(|roll| (roll.kind, roll.amount))
+            .collect()
+    }
+
+    /// Rolls damage as `roll_damage`, but returns the details of each roll,
+    /// including those that were entirely absorbed
+    pub fn roll_damage_detailed(
+        &self,
+        damage: &DamageList,
+        armor: &Armor,
+        resistance: &Resistance,
+        multiplier: f32,
+    ) -> Vec<DamageRoll> {
         debug!(
             "Rolling damage from {} to {} vs {} base armor",
             damage.min(),
@@ -274,8 +408,10 @@ impl Rules {
         for damage in damage.iter() {
             let kind = damage.kind.unwrap();
 
-            let resistance = (100 - resistance.amount(kind)) as f32 / 100.0;
-            let amount = damage.roll() as f32 * multiplier * resistance;
+            let rolled = damage.roll();
+            let resistance_amount = resistance.amount(kind);
+            let resistance = (100 - resistance_amount) as f32 / 100.0;
+            let amount = rolled as f32 * multiplier * resistance;
 
             let armor = max(0, armor.amount(kind) - damage.ap as i32) as u32;
             let armor_max = self.armor_damage_reduction_cap(armor) as f32 * amount / 100.0;
@@ -285,9 +421,14 @@ impl Rules {
             let armor = if armor > amount { amount } else { armor };
 
             let amount = amount - armor;
-            if amount > 0.0 {
-                output.push((kind, amount.ceil() as u32));
-            }
+            output.push(DamageRoll {
+                kind,
+                rolled,
+                multiplier,
+                resistance: resistance_amount,
+                armor,
+                amount: amount.max(0.0).ceil() as u32,
+            });
         }
 
         output
@@ -501,6 +642,45 @@ pub struct HitFlags {
     pub concealment: bool,
 }
 
+/// The details of a single attack roll, as used by the combat log
+#[derive(Debug, Clone, Copy)]
+pub struct AttackRoll {
+    pub kind: HitKind,
+    pub roll: i32,
+    pub accuracy: i32,
+    pub defense: i32,
+
+    /// The second roll made when the first roll lands in the crit range
+    pub crit_roll: Option<i32>,
+    pub graze_threshold: i32,
+    pub hit_threshold: i32,
+}
+
+impl AttackRoll {
+    /// The amount by which the roll plus accuracy exceeded the defense
+    pub fn result(&self) -> i32 {
+        self.roll + self.accuracy - self.defense
+    }
+}
+
+/// The details of the damage of a single kind dealt by a damage roll, as
+/// used by the combat log
+#[derive(Debug, Clone, Copy)]
+pub struct DamageRoll {
+    pub kind: DamageKind,
+
+    /// The base amount rolled, before any modifiers
+    pub rolled: u32,
+    pub multiplier: f32,
+
+    /// The target's resistance percentage to this damage kind
+    pub resistance: i32,
+
+    /// The amount of damage absorbed by the target's armor
+    pub armor: f32,
+    pub amount: u32,
+}
+
 #[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord)]
 pub enum HitKind {
     Miss,
@@ -563,68 +743,15 @@ impl FromStr for WeaponStyle {
     }
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Deserialize, Serialize)]
-pub enum WeaponKind {
-    Axe,
-    Crossbow,
-    Bow,
-    SmallSword,
-    LargeSword,
-    Hammer,
-    Spear,
-    Mace,
-    Simple,
-}
-
-impl FromStr for WeaponKind {
-    type Err = Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let val = match s {
-            "axe" => WeaponKind::Axe,
-            "crossbow" => WeaponKind::Crossbow,
-            "bow" => WeaponKind::Bow,
-            "small_sword" => WeaponKind::SmallSword,
-            "large_sword" => WeaponKind::LargeSword,
-            "hammer" => WeaponKind::Hammer,
-            "spear" => WeaponKind::Spear,
-            "mace" => WeaponKind::Mace,
-            "simple" => WeaponKind::Simple,
-            _ => {
-                return Err(Error::new(
-                    ErrorKind::InvalidInput,
-                    format!("Unable to parse WeaponKind from '{s}'"),
-                ));
-            }
-        };
-
-        Ok(val)
-    }
-}
-
-#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Deserialize, Serialize)]
-pub enum ArmorKind {
-    Light,
-    Medium,
-    Heavy,
-}
-
-impl FromStr for ArmorKind {
-    type Err = Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let val = match s {
-            "light" => ArmorKind::Light,
-            "medium" => ArmorKind::Medium,
-            "heavy" => ArmorKind::Heavy,
-            _ => {
-                return Err(Error::new(
-                    ErrorKind::InvalidInput,
-                    format!("Unable to parse ArmorKind from '{s}'"),
-                ));
-            }
-        };
-
-        Ok(val)
-    }
+/// How the turn order is determined when combat begins
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub enum InitiativeMode {
+    /// Each entity takes its turn individually, in order of its initiative roll
+    Individual,
+
+    /// Each faction acts together as a phase, ordered by the best initiative
+    /// roll among its members.  Within the party's phase, the player may
+    /// activate party members in any order.
+    Group,
 }
@@ -22,7 +22,7 @@ use crate::rules::DamageKind;
 #[serde(deny_unknown_fields)]
 pub struct Armor {
     base: i32,
-    kinds: [i32; 7],
+    kinds: Vec<i32>,
 }
 
 impl Armor {
@@ -47,22 +47,29 @@ impl Armor {
     }
 
     pub fn add_kind(&mut self, kind: DamageKind, amount: i32) {
-        if kind == DamageKind::Raw {
+        if kind.ignores_armor() {
             return;
         }
 
+        // kinds without a specific entry use the base value
         let index = kind.index();
+        if index >= self.kinds.len() {
+            self.kinds.resize(index + 1, self.base);
+        }
         self.kinds[index] += amount;
     }
 
     /// Returns the amount of armor that this Armor value
     /// applies to the specified damage kind.
     pub fn amount(&self, check_kind: DamageKind) -> i32 {
-        if check_kind == DamageKind::Raw {
+        if check_kind.ignores_armor() {
             return 0;
         }
 
-        self.kinds[check_kind.index()]
+        self.kinds
+            .get(check_kind.index())
+            .copied()
+            .unwrap_or(self.base)
     }
 
     pub fn base(&self) -> i32 {
@@ -84,10 +91,10 @@ impl Armor {
     }
 
     pub fn differs_from_base(&self, kind: DamageKind) -> bool {
-        if kind == DamageKind::Raw {
+        if kind.ignores_armor() {
             return true;
         }
 
-        self.kinds[kind.index()] != self.base
+        self.amount(kind) != self.base
     }
 }
@@ -50,6 +50,9 @@ pub struct Attack {
     pub kind: AttackKind,
     pub bonuses: AttackBonuses,
     pub sounds: HitSounds,
+
+    /// The kind of weapon making this attack, or None for non weapon attacks
+    pub weapon_kind: Option<WeaponKind>,
 }
 
 impl Attack {
@@ -96,6 +99,7 @@ impl Attack {
             kind: attack_kind,
             bonuses,
             sounds: HitSounds::default(),
+            weapon_kind: None,
         }
     }
 
@@ -118,6 +122,7 @@ impl Attack {
             bonuses,
             damage,
             sounds: other.sounds.clone(),
+            weapon_kind: other.weapon_kind,
         }
     }
 
@@ -179,6 +184,7 @@ impl Attack {
             kind,
             bonuses,
             sounds: builder.sounds.clone(),
+            weapon_kind: Some(weapon_kind),
         }
     }
 
@@ -203,9 +209,15 @@ impl Attack {
             kind: self.kind.clone(),
             bonuses: self.bonuses.clone(),
             sounds: self.sounds.clone(),
+            weapon_kind: self.weapon_kind,
         }
     }
 
+    /// Returns the kind of the base damage of this attack
+    pub fn damage_kind(&self) -> Option<DamageKind> {
+        self.damage.iter().next().and_then(|damage| damage.kind)
+    }
+
     // Returns the distance that this attack can reach
     pub fn distance(&self) -> f32 {
         match self.kind {
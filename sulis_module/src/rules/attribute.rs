@@ -14,33 +14,120 @@
 //  You should have received a copy of the GNU General Public License
 //  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
 
+use std::cell::RefCell;
 use std::collections::HashMap;
-use std::slice::Iter;
+use std::fmt;
+use std::io::Error;
 
-use serde::{Serialize, Deserialize};
+use serde::de::{self, Deserializer, MapAccess, Visitor};
+use serde::ser::{SerializeMap, Serializer};
+use serde::{Deserialize, Serialize};
 
-use self::Attribute::*;
+use crate::rules::Formula;
+use sulis_core::util::invalid_data_error;
 
-#[derive(Deserialize, Serialize, Debug, Copy, Clone)]
+/// A stat derived in part from attribute values.  Each attribute specifies
+/// how much each point above or below the base attribute contributes to
+/// these stats, and the module rules may specify a formula for each stat in
+/// `derived_stats`.
+#[derive(Deserialize, Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[serde(deny_unknown_fields)]
-pub struct AttributeList {
-    #[serde(rename = "str")]
-    pub strength: u8,
+pub enum DerivedStat {
+    Initiative,
+    MeleeAccuracy,
+    RangedAccuracy,
+    SpellAccuracy,
+    Defense,
+    Fortitude,
+    Reflex,
+    Will,
+    Stealth,
+    Detection,
+
+    /// Used when picking locks and disarming traps
+    Mechanics,
 
-    #[serde(rename = "dex")]
-    pub dexterity: u8,
+    /// Hit points per character level
+    HpPerLevel,
 
-    #[serde(rename = "end")]
-    pub endurance: u8,
+    /// Damage multipliers, applied for melee or ranged attacks respectively
+    MeleeGrazeMultiplier,
+    MeleeHitMultiplier,
+    MeleeCritMultiplier,
+    RangedGrazeMultiplier,
+    RangedHitMultiplier,
+    RangedCritMultiplier,
+}
+
+impl DerivedStat {
+    /// Returns true if this stat takes whole number values.  The contribution
+    /// of each attribute to these stats is truncated separately.
+    pub fn is_integer(self) -> bool {
+        use self::DerivedStat::*;
+        !matches!(
+            self,
+            HpPerLevel
+                | MeleeGrazeMultiplier
+                | MeleeHitMultiplier
+                | MeleeCritMultiplier
+                | RangedGrazeMultiplier
+                | RangedHitMultiplier
+                | RangedCritMultiplier
+        )
+    }
+}
 
-    #[serde(rename = "per")]
-    pub perception: u8,
+/// Checks that each derived stat formula only references known variables.
+/// The attributes must already be set up via `Attribute::init`.
+pub fn validate_derived_stats(formulas: &HashMap<DerivedStat, Formula>) -> Result<(), Error> {
+    for (stat, formula) in formulas.iter() {
+        let unknown = formula.unknown_variable(|var| {
+            matches!(var, "base" | "bonus" | "base_attribute" | "level")
+                || Attribute::find_short_name(var).is_some()
+        });
 
-    #[serde(rename = "int")]
-    pub intellect: u8,
+        if let Some(var) = unknown {
+            return invalid_data_error(&format!(
+                "Unknown variable '{var}' in derived_stats formula for '{stat:?}'"
+            ));
+        }
+    }
 
-    #[serde(rename = "wis")]
-    pub wisdom: u8,
+    Ok(())
+}
+
+/// The definition of an attribute, as specified in the `attributes` list of
+/// the module rules.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct AttributeInfo {
+    pub id: String,
+    pub name: String,
+
+    /// The abbreviated name, used as the key in attribute lists
+    pub short_name: String,
+    pub description: String,
+
+    #[serde(default)]
+    pub bonuses: HashMap<DerivedStat, f32>,
+}
+
+#[derive(Default)]
+struct AttributeRegistry {
+    attributes: Vec<AttributeInfo>,
+    base_value: u8,
+}
+
+thread_local! {
+    static ATTRIBUTES: RefCell<AttributeRegistry> = RefCell::new(AttributeRegistry::default());
+}
+
+/// A set of values for each of the attributes defined by the module rules.
+/// In resource files, this is a map of attribute short names to values.
+/// Any attributes not specified take the base attribute value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttributeList {
+    values: Vec<u8>,
 }
 
 fn get_new_val(cur: u8, delta: i8) -> u8 {
@@ -57,38 +144,21 @@ fn get_new_val(cur: u8, delta: i8) -> u8 {
 impl AttributeList {
     pub fn new(base_value: u8) -> AttributeList {
         AttributeList {
-            strength: base_value,
-            dexterity: base_value,
-            endurance: base_value,
-            perception: base_value,
-            intellect: base_value,
-            wisdom: base_value,
+            values: vec![base_value; Attribute::count()],
         }
     }
 
-    pub fn bonus(self, attr: Attribute, base_attr: i32) -> i32 {
+    pub fn bonus(&self, attr: Attribute, base_attr: i32) -> i32 {
         self.get(attr) as i32 - base_attr
     }
 
-    pub fn get(self, attr: Attribute) -> u8 {
-        match attr {
-            Strength => self.strength,
-            Dexterity => self.dexterity,
-            Endurance => self.endurance,
-            Perception => self.perception,
-            Intellect => self.intellect,
-            Wisdom => self.wisdom,
-        }
+    pub fn get(&self, attr: Attribute) -> u8 {
+        self.values.get(attr.index()).copied().unwrap_or(0)
     }
 
     pub fn set(&mut self, attr: Attribute, value: u8) {
-        match attr {
-            Strength => self.strength = value,
-            Dexterity => self.dexterity = value,
-            Endurance => self.endurance = value,
-            Perception => self.perception = value,
-            Intellect => self.intellect = value,
-            Wisdom => self.wisdom = value,
+        if let Some(cur) = self.values.get_mut(attr.index()) {
+            *cur = value;
         }
     }
 
@@ -99,79 +169,172 @@ impl AttributeList {
     }
 
     pub fn add(&mut self, attr: Attribute, value: i8) {
-        match attr {
-            Strength => self.strength = get_new_val(self.strength, value),
-            Dexterity => self.dexterity = get_new_val(self.dexterity, value),
-            Endurance => self.endurance = get_new_val(self.endurance, value),
-            Perception => self.perception = get_new_val(self.perception, value),
-            Intellect => self.intellect = get_new_val(self.intellect, value),
-            Wisdom => self.wisdom = get_new_val(self.wisdom, value),
+        if let Some(cur) = self.values.get_mut(attr.index()) {
+            *cur = get_new_val(*cur, value);
         }
     }
 
-    pub fn sum(self, other: AttributeList) -> AttributeList {
-        AttributeList {
-            strength: self.strength + other.strength,
-            dexterity: self.dexterity + other.dexterity,
-            endurance: self.endurance + other.endurance,
-            perception: self.perception + other.perception,
-            intellect: self.intellect + other.intellect,
-            wisdom: self.wisdom + other.wisdom,
+    /// Returns the sum of all attribute values in this list
+    pub fn total(&self) -> i32 {
+        self.values.iter().map(|value| *value as i32).sum()
+    }
+}
+
+impl Serialize for AttributeList {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.values.len()))?;
+        for attr in Attribute::iter() {
+            map.serialize_entry(&attr.short_name(), &self.get(attr))?;
         }
+        map.end()
     }
 }
 
-#[derive(Deserialize, Serialize, Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
-#[serde(deny_unknown_fields)]
-pub enum Attribute {
-    Strength,
-    Dexterity,
-    Endurance,
-    Perception,
-    Intellect,
-    Wisdom,
+struct AttributeListVisitor;
+
+impl<'de> Visitor<'de> for AttributeListVisitor {
+    type Value = AttributeList;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map of attribute short names to values")
+    }
+
+    fn visit_map<M: MapAccess<'de>>(self, mut access: M) -> Result<AttributeList, M::Error> {
+        let base_value = ATTRIBUTES.with(|registry| registry.borrow().base_value);
+        let mut list = AttributeList::new(base_value);
+
+        while let Some((key, value)) = access.next_entry::<String, u8>()? {
+            match Attribute::find_short_name(&key) {
+                None => return Err(de::Error::custom(format!("Unknown attribute '{key}'"))),
+                Some(attr) => list.set(attr, value),
+            }
+        }
+
+        Ok(list)
+    }
+}
+
+impl<'de> Deserialize<'de> for AttributeList {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<AttributeList, D::Error> {
+        deserializer.deserialize_map(AttributeListVisitor)
+    }
 }
 
-const ATTRS_LIST: [Attribute; 6] = [
-    Strength, Dexterity, Endurance, Perception, Intellect, Wisdom,
-];
+/// An attribute such as Strength, as defined by the module rules.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Attribute(usize);
 
 impl Attribute {
+    /// Sets up the list of attributes from the rules.  This must be called
+    /// prior to parsing any resources referencing attributes.
+    pub fn init(attributes: &[AttributeInfo], base_value: i32) -> Result<(), Error> {
+        if attributes.is_empty() {
+            return invalid_data_error("Must specify at least one attribute");
+        }
+
+        for (index, attr) in attributes.iter().enumerate() {
+            if attributes[..index]
+                .iter()
+                .any(|other| other.id == attr.id || other.short_name == attr.short_name)
+            {
+                return invalid_data_error(&format!("Duplicate attribute '{}'", attr.id));
+            }
+        }
+
+        if !(0..=255).contains(&base_value) {
+            return invalid_data_error("Base attribute must be between 0 and 255");
+        }
+
+        ATTRIBUTES.with(|registry| {
+            let mut registry = registry.borrow_mut();
+            registry.attributes = attributes.to_vec();
+            registry.base_value = base_value as u8;
+        });
+
+        Ok(())
+    }
+
+    pub fn count() -> usize {
+        ATTRIBUTES.with(|registry| registry.borrow().attributes.len())
+    }
+
+    pub fn iter() -> impl Iterator<Item = Attribute> {
+        (0..Attribute::count()).map(Attribute)
+    }
+
+    /// Finds the attribute with the specified id, i.e. `Strength`
     pub fn from(text: &str) -> Option<Attribute> {
-        Some(match text {
-            "Strength" => Strength,
-            "Dexterity" => Dexterity,
-            "Endurance" => Endurance,
-            "Perception" => Perception,
-            "Intellect" => Intellect,
-            "Wisdom" => Wisdom,
-            _ => return None,
+        ATTRIBUTES.with(|registry| {
+            registry
+                .borrow()
+                .attributes
+                .iter()
+                .position(|attr| attr.id == text)
+                .map(Attribute)
         })
     }
 
-    pub fn name(&self) -> &str {
-        match *self {
-            Strength => "Strength",
-            Dexterity => "Dexterity",
-            Endurance => "Endurance",
-            Perception => "Perception",
-            Intellect => "Intellect",
-            Wisdom => "Wisdom",
-        }
+    /// Finds the attribute with the specified short name, i.e. `str`
+    pub fn find_short_name(text: &str) -> Option<Attribute> {
+        ATTRIBUTES.with(|registry| {
+            registry
+                .borrow()
+                .attributes
+                .iter()
+                .position(|attr| attr.short_name == text)
+                .map(Attribute)
+        })
     }
 
-    pub fn short_name(&self) -> &str {
-        match *self {
-            Strength => "str",
-            Dexterity => "dex",
-            Endurance => "end",
-            Perception => "per",
-            Intellect => "int",
-            Wisdom => "wis",
-        }
+    pub fn index(self) -> usize {
+        self.0
+    }
+
+    fn with_info<T, F: FnOnce(&AttributeInfo) -> T>(self, f: F) -> T {
+        ATTRIBUTES.with(|registry| f(&registry.borrow().attributes[self.0]))
     }
 
-    pub fn iter() -> Iter<'static, Attribute> {
-        ATTRS_LIST.iter()
+    pub fn id(self) -> String {
+        self.with_info(|info| info.id.clone())
+    }
+
+    pub fn name(self) -> String {
+        self.with_info(|info| info.name.clone())
+    }
+
+    pub fn short_name(self) -> String {
+        self.with_info(|info| info.short_name.clone())
+    }
+
+    pub fn description(self) -> String {
+        self.with_info(|info| info.description.clone())
+    }
+
+    /// Returns the amount each point of this attribute contributes to the
+    /// given stat
+    pub fn bonus_factor(self, stat: DerivedStat) -> f32 {
+        self.with_info(|info| info.bonuses.get(&stat).copied().unwrap_or(0.0))
+    }
+}
+
+impl fmt::Display for Attribute {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        self.with_info(|info| write!(fmt, "{}", info.id))
+    }
+}
+
+impl Serialize for Attribute {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.with_info(|info| serializer.serialize_str(&info.id))
+    }
+}
+
+impl<'de> Deserialize<'de> for Attribute {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Attribute, D::Error> {
+        let id = String::deserialize(deserializer)?;
+        match Attribute::from(&id) {
+            Some(attr) => Ok(attr),
+            None => Err(de::Error::custom(format!("Unknown attribute '{id}'"))),
+        }
     }
 }
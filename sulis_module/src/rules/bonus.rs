@@ -48,6 +48,10 @@ pub enum BonusKind {
     Will(i32),
     Concealment(i32),
     ConcealmentIgnore(i32),
+    Stealth(i32),
+    Detection(i32),
+    Mechanics(i32),
+    Reactions(i32),
     CritChance(i32),
     HitThreshold(i32),
     GrazeThreshold(i32),
@@ -207,6 +211,10 @@ fn apply_modifiers(bonus: &mut Bonus, neg: f32, pos: f32) {
         Will(val) => get_mod!(Will(val): i32, neg, pos),
         Concealment(val) => get_mod!(Concealment(val): i32, neg, pos),
         ConcealmentIgnore(val) => get_mod!(ConcealmentIgnore(val): i32, neg, pos),
+        Stealth(val) => get_mod!(Stealth(val): i32, neg, pos),
+        Detection(val) => get_mod!(Detection(val): i32, neg, pos),
+        Mechanics(val) => get_mod!(Mechanics(val): i32, neg, pos),
+        Reactions(val) => get_mod!(Reactions(val): i32, neg, pos),
         CritChance(val) => get_mod!(CritChance(val): i32, neg, pos),
         HitThreshold(val) => get_mod!(HitThreshold(val): i32, neg, pos),
         GrazeThreshold(val) => get_mod!(GrazeThreshold(val): i32, neg, pos),
@@ -364,6 +372,10 @@ pub fn merge_if_dup(first: &Bonus, sec: &Bonus) -> Option<Bonus> {
         Will(val) => merge_dup!(Will(val): sec, when),
         Concealment(val) => merge_dup!(Concealment(val): sec, when),
         ConcealmentIgnore(val) => merge_dup!(ConcealmentIgnore(val): sec, when),
+        Stealth(val) => merge_dup!(Stealth(val): sec, when),
+        Detection(val) => merge_dup!(Detection(val): sec, when),
+        Mechanics(val) => merge_dup!(Mechanics(val): sec, when),
+        Reactions(val) => merge_dup!(Reactions(val): sec, when),
         CritChance(val) => merge_dup!(CritChance(val): sec, when),
         HitThreshold(val) => merge_dup!(HitThreshold(val): sec, when),
         GrazeThreshold(val) => merge_dup!(GrazeThreshold(val): sec, when),
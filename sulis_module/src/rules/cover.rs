@@ -0,0 +1,120 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2019 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use serde::Deserialize;
+
+/// The degree to which a target is obstructed from a ranged attacker by
+/// walls, props, and higher terrain
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cover {
+    None,
+    Half,
+    Full,
+}
+
+impl Cover {
+    pub fn name(self) -> &'static str {
+        match self {
+            Cover::None => "No Cover",
+            Cover::Half => "Half Cover",
+            Cover::Full => "Full Cover",
+        }
+    }
+}
+
+/// The accuracy and defense modifiers applying to a single ranged attack
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RangedModifiers {
+    pub cover: Cover,
+
+    /// The difference in elevation between the attacker and target.  This
+    /// is positive when the attacker is higher
+    pub elevation: i32,
+
+    pub accuracy: i32,
+    pub cover_defense: i32,
+    pub elevation_defense: i32,
+}
+
+impl RangedModifiers {
+    /// The total defense bonus from both cover and elevation
+    pub fn defense(&self) -> i32 {
+        self.cover_defense + self.elevation_defense
+    }
+}
+
+/// Parameters for the cover and elevation modifiers applied to ranged
+/// attacks.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct CoverRules {
+    /// The fraction of the target's squares that must be obstructed from
+    /// the attacker for the target to have full cover.  Any smaller non-zero
+    /// fraction is half cover
+    pub full_cover_fraction: f32,
+
+    pub half_cover_defense: i32,
+    pub full_cover_defense: i32,
+
+    /// Accuracy bonus for each level the attacker is above the target
+    pub elevation_accuracy_bonus: i32,
+
+    /// Defense bonus for each level the target is above the attacker
+    pub elevation_defense_bonus: i32,
+
+    /// The maximum number of elevation levels counted for the above bonuses
+    pub max_elevation_levels: i32,
+}
+
+impl CoverRules {
+    /// Returns the cover for a target with the specified fraction of its
+    /// squares obstructed
+    pub fn cover(&self, obstructed_fraction: f32) -> Cover {
+        if obstructed_fraction <= 0.0 {
+            Cover::None
+        } else if obstructed_fraction < self.full_cover_fraction {
+            Cover::Half
+        } else {
+            Cover::Full
+        }
+    }
+
+    /// Computes the modifiers for a ranged attack against a target with the
+    /// specified cover and difference in elevation from the attacker
+    pub fn modifiers(&self, cover: Cover, elevation: i32) -> RangedModifiers {
+        let levels = elevation.clamp(-self.max_elevation_levels, self.max_elevation_levels);
+
+        let cover_defense = match cover {
+            Cover::None => 0,
+            Cover::Half => self.half_cover_defense,
+            Cover::Full => self.full_cover_defense,
+        };
+
+        let (accuracy, elevation_defense) = if levels > 0 {
+            (levels * self.elevation_accuracy_bonus, 0)
+        } else {
+            (0, -levels * self.elevation_defense_bonus)
+        };
+
+        RangedModifiers {
+            cover,
+            elevation,
+            accuracy,
+            cover_defense,
+            elevation_defense,
+        }
+    }
+}
@@ -14,12 +14,16 @@
 //  You should have received a copy of the GNU General Public License
 //  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
 
+use std::cell::RefCell;
 use std::fmt::{self, Display};
-use std::slice::Iter;
+use std::io::Error;
 
-use serde::{Serialize, Deserialize};
+use serde::de::{self, Deserializer};
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
 
-use sulis_core::util::gen_rand;
+use sulis_core::ui::Color;
+use sulis_core::util::{gen_rand, invalid_data_error};
 
 #[derive(Clone)]
 pub struct DamageList {
@@ -173,78 +177,162 @@ impl DamageList {
     }
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+/// The definition of a single damage kind, as specified in the `damage_kinds`
+/// list of the module rules.
+#[derive(Deserialize, Debug, Clone)]
 #[serde(deny_unknown_fields)]
-pub enum DamageKind {
-    Slashing,
-    Piercing,
-    Crushing,
-    Acid,
-    Cold,
-    Shock,
-    Fire,
-    Raw,
+pub struct DamageKindInfo {
+    pub id: String,
+    pub name: String,
+    pub color: Color,
+
+    #[serde(default)]
+    pub icon: Option<String>,
+
+    /// If true, armor does not reduce damage of this kind
+    #[serde(default)]
+    pub ignores_armor: bool,
+
+    /// If true, resistances do not reduce damage of this kind
+    #[serde(default)]
+    pub ignores_resistance: bool,
 }
 
-use crate::rules::DamageKind::*;
+#[derive(Default)]
+struct DamageKindRegistry {
+    kinds: Vec<DamageKindInfo>,
+    default_kind: usize,
+}
+
+thread_local! {
+    static DAMAGE_KINDS: RefCell<DamageKindRegistry> = RefCell::new(DamageKindRegistry::default());
+}
 
-// This array MUST be in the same order as the ordering on the DamageKind enum
-// This is top to bottom declaration order for derived.
-const DAMAGE_KINDS: [DamageKind; 8] = [Slashing, Piercing, Crushing, Acid, Cold, Shock, Fire, Raw];
+/// A kind of damage, such as Slashing or Fire.  Damage kinds are defined by
+/// the module rules, and each `DamageKind` is an index into that list.  The
+/// ordering of damage kinds is the order they are declared in the rules.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DamageKind(usize);
 
 impl DamageKind {
-    pub fn iter() -> Iter<'static, DamageKind> {
-        DAMAGE_KINDS.iter()
+    /// Sets up the list of damage kinds from the rules.  This must be called
+    /// prior to parsing any resources referencing damage kinds.
+    pub fn init(kinds: &[DamageKindInfo], default_kind: &str) -> Result<(), Error> {
+        if kinds.is_empty() {
+            return invalid_data_error("Must specify at least one damage kind");
+        }
+
+        for (index, kind) in kinds.iter().enumerate() {
+            if kinds[..index].iter().any(|other| other.id == kind.id) {
+                return invalid_data_error(&format!("Duplicate damage kind '{}'", kind.id));
+            }
+        }
+
+        let default_kind = match kinds.iter().position(|kind| kind.id == default_kind) {
+            None => {
+                return invalid_data_error(&format!(
+                    "Default damage kind '{default_kind}' is not a defined damage kind"
+                ))
+            }
+            Some(index) => index,
+        };
+
+        DAMAGE_KINDS.with(|registry| {
+            let mut registry = registry.borrow_mut();
+            registry.kinds = kinds.to_vec();
+            registry.default_kind = default_kind;
+        });
+
+        Ok(())
+    }
+
+    /// Returns the total number of defined damage kinds
+    pub fn count() -> usize {
+        DAMAGE_KINDS.with(|registry| registry.borrow().kinds.len())
+    }
+
+    pub fn iter() -> impl Iterator<Item = DamageKind> {
+        (0..DamageKind::count()).map(DamageKind)
+    }
+
+    /// The damage kind used when none is specified, typically Raw
+    pub fn default_kind() -> DamageKind {
+        DAMAGE_KINDS.with(|registry| DamageKind(registry.borrow().default_kind))
+    }
+
+    pub fn find(s: &str) -> Option<DamageKind> {
+        DAMAGE_KINDS.with(|registry| {
+            registry
+                .borrow()
+                .kinds
+                .iter()
+                .position(|kind| kind.id == s)
+                .map(DamageKind)
+        })
     }
 
     pub fn index(self) -> usize {
-        use crate::rules::DamageKind::*;
-        match self {
-            Slashing => 0,
-            Piercing => 1,
-            Crushing => 2,
-            Acid => 3,
-            Cold => 4,
-            Shock => 5,
-            Fire => 6,
-            Raw => 7,
-        }
+        self.0
     }
 
     pub fn unwrap_from_str(s: &str) -> DamageKind {
-        match s {
-            "Slashing" => DamageKind::Slashing,
-            "Piercing" => DamageKind::Piercing,
-            "Crushing" => DamageKind::Crushing,
-            "Acid" => DamageKind::Acid,
-            "Cold" => DamageKind::Cold,
-            "Shock" => DamageKind::Shock,
-            "Fire" => DamageKind::Fire,
-            "Raw" => DamageKind::Raw,
-            _ => {
+        match DamageKind::find(s) {
+            Some(kind) => kind,
+            None => {
                 warn!("Unable to parse '{}' as damage kind", s);
-                DamageKind::Raw
+                DamageKind::default_kind()
             }
         }
     }
 
-    pub fn to_str(self) -> &'static str {
-        match self {
-            Slashing => "Slashing",
-            Piercing => "Piercing",
-            Crushing => "Crushing",
-            Acid => "Acid",
-            Cold => "Cold",
-            Shock => "Shock",
-            Fire => "Fire",
-            Raw => "Raw",
-        }
+    fn with_info<T, F: FnOnce(&DamageKindInfo) -> T>(self, f: F) -> T {
+        DAMAGE_KINDS.with(|registry| f(&registry.borrow().kinds[self.0]))
+    }
+
+    pub fn id(self) -> String {
+        self.with_info(|info| info.id.clone())
+    }
+
+    pub fn name(self) -> String {
+        self.with_info(|info| info.name.clone())
+    }
+
+    pub fn color(self) -> Color {
+        self.with_info(|info| info.color)
+    }
+
+    pub fn icon(self) -> Option<String> {
+        self.with_info(|info| info.icon.clone())
+    }
+
+    pub fn ignores_armor(self) -> bool {
+        self.with_info(|info| info.ignores_armor)
+    }
+
+    pub fn ignores_resistance(self) -> bool {
+        self.with_info(|info| info.ignores_resistance)
     }
 }
 
 impl Display for DamageKind {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-        write!(fmt, "{self:?}")
+        self.with_info(|info| write!(fmt, "{}", info.id))
+    }
+}
+
+impl Serialize for DamageKind {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.with_info(|info| serializer.serialize_str(&info.id))
+    }
+}
+
+impl<'de> Deserialize<'de> for DamageKind {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<DamageKind, D::Error> {
+        let id = String::deserialize(deserializer)?;
+        match DamageKind::find(&id) {
+            Some(kind) => Ok(kind),
+            None => Err(de::Error::custom(format!("Unknown damage kind '{id}'"))),
+        }
     }
 }
 
@@ -0,0 +1,177 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::cell::RefCell;
+use std::fmt::{self, Display};
+use std::io::{Error, ErrorKind};
+use std::str::FromStr;
+
+use serde::de::{self, Deserializer};
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+
+use sulis_core::util::invalid_data_error;
+
+/// The definition of a weapon or armor kind, as specified in the
+/// `weapon_kinds` or `armor_kinds` lists of the module rules.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct EquipmentKindInfo {
+    pub id: String,
+    pub name: String,
+}
+
+thread_local! {
+    static WEAPON_KINDS: RefCell<Vec<EquipmentKindInfo>> = const { RefCell::new(Vec::new()) };
+    static ARMOR_KINDS: RefCell<Vec<EquipmentKindInfo>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Converts an id such as `SmallSword` to the `small_sword` form used by scripts
+fn snake_case(id: &str) -> String {
+    let mut out = String::new();
+    for (index, c) in id.chars().enumerate() {
+        if c.is_uppercase() {
+            if index > 0 {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+macro_rules! equipment_kind {
+    ($kind:ident, $registry:ident, $desc:literal) => {
+        impl $kind {
+            /// Sets up the list of kinds from the rules.  This must be called
+            /// prior to parsing any resources referencing these kinds.
+            pub fn init(kinds: &[EquipmentKindInfo]) -> Result<(), Error> {
+                if kinds.is_empty() {
+                    return invalid_data_error(concat!("Must specify at least one ", $desc));
+                }
+
+                for (index, kind) in kinds.iter().enumerate() {
+                    if kinds[..index].iter().any(|other| other.id == kind.id) {
+                        return invalid_data_error(&format!(
+                            concat!("Duplicate ", $desc, " '{}'"),
+                            kind.id
+                        ));
+                    }
+                }
+
+                $registry.with(|registry| *registry.borrow_mut() = kinds.to_vec());
+                Ok(())
+            }
+
+            pub fn count() -> usize {
+                $registry.with(|registry| registry.borrow().len())
+            }
+
+            pub fn iter() -> impl Iterator<Item = $kind> {
+                (0..$kind::count()).map($kind)
+            }
+
+            /// Finds the kind with the specified id
+            pub fn find(id: &str) -> Option<$kind> {
+                $registry.with(|registry| {
+                    registry
+                        .borrow()
+                        .iter()
+                        .position(|kind| kind.id == id)
+                        .map($kind)
+                })
+            }
+
+            pub fn index(self) -> usize {
+                self.0
+            }
+
+            fn with_info<T, F: FnOnce(&EquipmentKindInfo) -> T>(self, f: F) -> T {
+                $registry.with(|registry| f(&registry.borrow()[self.0]))
+            }
+
+            pub fn id(self) -> String {
+                self.with_info(|info| info.id.clone())
+            }
+
+            pub fn name(self) -> String {
+                self.with_info(|info| info.name.clone())
+            }
+        }
+
+        impl Display for $kind {
+            fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+                self.with_info(|info| write!(fmt, "{}", info.id))
+            }
+        }
+
+        /// Parses either the id or its snake case form, i.e. `small_sword`
+        /// for `SmallSword`.
+        impl FromStr for $kind {
+            type Err = Error;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let found = $registry.with(|registry| {
+                    registry
+                        .borrow()
+                        .iter()
+                        .position(|kind| kind.id == s || snake_case(&kind.id) == s)
+                });
+
+                match found {
+                    Some(index) => Ok($kind(index)),
+                    None => Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        format!(concat!("Unable to parse ", $desc, " from '{}'"), s),
+                    )),
+                }
+            }
+        }
+
+        impl Serialize for $kind {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                self.with_info(|info| serializer.serialize_str(&info.id))
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $kind {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<$kind, D::Error> {
+                let id = String::deserialize(deserializer)?;
+                match $kind::find(&id) {
+                    Some(kind) => Ok(kind),
+                    None => Err(de::Error::custom(format!(
+                        concat!("Unknown ", $desc, " '{}'"),
+                        id
+                    ))),
+                }
+            }
+        }
+    };
+}
+
+/// A kind of weapon, such as an Axe or Bow, as defined by the module rules.
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq, PartialOrd, Ord)]
+pub struct WeaponKind(usize);
+
+equipment_kind!(WeaponKind, WEAPON_KINDS, "weapon kind");
+
+/// A kind of armor, such as Light or Heavy, as defined by the module rules.
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq, PartialOrd, Ord)]
+pub struct ArmorKind(usize);
+
+equipment_kind!(ArmorKind, ARMOR_KINDS, "armor kind");
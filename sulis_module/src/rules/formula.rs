@@ -0,0 +1,515 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::fmt;
+use std::io::{Error, ErrorKind};
+use std::iter::Peekable;
+use std::str::{Chars, FromStr};
+
+use serde::de::{self, Deserializer, Visitor};
+use serde::Deserialize;
+
+/// A simple arithmetic expression, such as `base + (dex - 10) * 2 + level`.
+/// Formulas support numbers, named variables, the `+`, `-`, `*`, and `/`
+/// operators, parentheses, and the functions `min`, `max`, `floor`, `ceil`,
+/// `round`, and `trunc`.  Variables are looked up by the caller when the
+/// formula is evaluated.
+#[derive(Clone)]
+pub struct Formula {
+    source: String,
+    expr: Expr,
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Number(f32),
+    Variable(String),
+    Negate(Box<Expr>),
+    Binary(Op, Box<Expr>, Box<Expr>),
+    Call(Function, Vec<Expr>),
+}
+
+#[derive(Debug, Copy, Clone)]
+enum Op {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+#[derive(Debug, Copy, Clone)]
+enum Function {
+    Min,
+    Max,
+    Floor,
+    Ceil,
+    Round,
+    Trunc,
+}
+
+impl Function {
+    fn from_name(name: &str) -> Option<Function> {
+        use self::Function::*;
+        let func = match name {
+            "min" => Min,
+            "max" => Max,
+            "floor" => Floor,
+            "ceil" => Ceil,
+            "round" => Round,
+            "trunc" => Trunc,
+            _ => return None,
+        };
+        Some(func)
+    }
+
+    fn num_args(self) -> usize {
+        use self::Function::*;
+        match self {
+            Min | Max => 2,
+            Floor | Ceil | Round | Trunc => 1,
+        }
+    }
+
+    fn apply(self, args: &[f32]) -> f32 {
+        use self::Function::*;
+        match self {
+            Min => args[0].min(args[1]),
+            Max => args[0].max(args[1]),
+            Floor => args[0].floor(),
+            Ceil => args[0].ceil(),
+            Round => args[0].round(),
+            Trunc => args[0].trunc(),
+        }
+    }
+}
+
+impl Formula {
+    /// Evaluates this formula, calling `vars` to get the value of each
+    /// variable.  Variables that `vars` does not know evaluate to zero; use
+    /// `unknown_variable` to check for these when the formula is loaded.
+    pub fn eval<F: Fn(&str) -> Option<f32>>(&self, vars: &F) -> f32 {
+        self.expr.eval(vars)
+    }
+
+    /// Returns the first variable referenced by this formula for which
+    /// `is_known` returns false, if any.
+    pub fn unknown_variable<F: Fn(&str) -> bool>(&self, is_known: F) -> Option<&str> {
+        self.expr.unknown_variable(&is_known)
+    }
+}
+
+impl Expr {
+    fn eval<F: Fn(&str) -> Option<f32>>(&self, vars: &F) -> f32 {
+        match self {
+            Expr::Number(value) => *value,
+            Expr::Variable(name) => vars(name).unwrap_or(0.0),
+            Expr::Negate(expr) => -expr.eval(vars),
+            Expr::Binary(op, left, right) => {
+                let left = left.eval(vars);
+                let right = right.eval(vars);
+                match op {
+                    Op::Add => left + right,
+                    Op::Subtract => left - right,
+                    Op::Multiply => left * right,
+                    Op::Divide => left / right,
+                }
+            }
+            Expr::Call(func, args) => {
+                let args: Vec<f32> = args.iter().map(|arg| arg.eval(vars)).collect();
+                func.apply(&args)
+            }
+        }
+    }
+
+    fn unknown_variable<F: Fn(&str) -> bool>(&self, is_known: &F) -> Option<&str> {
+        match self {
+            Expr::Number(_) => None,
+            Expr::Variable(name) => {
+                if is_known(name) {
+                    None
+                } else {
+                    Some(name)
+                }
+            }
+            Expr::Negate(expr) => expr.unknown_variable(is_known),
+            Expr::Binary(_, left, right) => left
+                .unknown_variable(is_known)
+                .or_else(|| right.unknown_variable(is_known)),
+            Expr::Call(_, args) => args.iter().find_map(|arg| arg.unknown_variable(is_known)),
+        }
+    }
+}
+
+/// A recursive descent parser over the formula grammar:
+///
+/// ```text
+/// expr    = term (('+' | '-') term)*
+/// term    = unary (('*' | '/') unary)*
+/// unary   = '-' unary | primary
+/// primary = number | name | name '(' expr (',' expr)* ')' | '(' expr ')'
+/// ```
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+
+impl<'a> Parser<'a> {
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.chars.peek() {
+            if !c.is_whitespace() {
+                break;
+            }
+            self.chars.next();
+        }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.chars.peek().copied()
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.chars.next();
+                Ok(())
+            }
+            Some(c) => Err(format!("Expected '{expected}' but found '{c}'")),
+            None => Err(format!("Expected '{expected}' but found end of formula")),
+        }
+    }
+
+    fn expr(&mut self) -> Result<Expr, String> {
+        let mut expr = self.term()?;
+        loop {
+            let op = match self.peek() {
+                Some('+') => Op::Add,
+                Some('-') => Op::Subtract,
+                _ => return Ok(expr),
+            };
+            self.chars.next();
+            expr = Expr::Binary(op, Box::new(expr), Box::new(self.term()?));
+        }
+    }
+
+    fn term(&mut self) -> Result<Expr, String> {
+        let mut expr = self.unary()?;
+        loop {
+            let op = match self.peek() {
+                Some('*') => Op::Multiply,
+                Some('/') => Op::Divide,
+                _ => return Ok(expr),
+            };
+            self.chars.next();
+            expr = Expr::Binary(op, Box::new(expr), Box::new(self.unary()?));
+        }
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        if let Some('-') = self.peek() {
+            self.chars.next();
+            return Ok(Expr::Negate(Box::new(self.unary()?)));
+        }
+
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Expr, String> {
+        match self.peek() {
+            Some('(') => {
+                self.chars.next();
+                let expr = self.expr()?;
+                self.expect(')')?;
+                Ok(expr)
+            }
+            Some(c) if c.is_ascii_digit() || c == '.' => self.number(),
+            Some(c) if c.is_alphabetic() || c == '_' => self.name(),
+            Some(c) => Err(format!("Unexpected '{c}'")),
+            None => Err("Unexpected end of formula".to_string()),
+        }
+    }
+
+    fn number(&mut self) -> Result<Expr, String> {
+        let mut text = String::new();
+        while let Some(&c) = self.chars.peek() {
+            if !c.is_ascii_digit() && c != '.' {
+                break;
+            }
+            text.push(c);
+            self.chars.next();
+        }
+
+        match text.parse::<f32>() {
+            Ok(value) => Ok(Expr::Number(value)),
+            Err(_) => Err(format!("Invalid number '{text}'")),
+        }
+    }
+
+    fn name(&mut self) -> Result<Expr, String> {
+        let mut name = String::new();
+        while let Some(&c) = self.chars.peek() {
+            if !c.is_alphanumeric() && c != '_' {
+                break;
+            }
+            name.push(c);
+            self.chars.next();
+        }
+
+        if self.peek() != Some('(') {
+            return Ok(Expr::Variable(name));
+        }
+
+        let func = match Function::from_name(&name) {
+            None => return Err(format!("Unknown function '{name}'")),
+            Some(func) => func,
+        };
+
+        self.chars.next();
+        let mut args = vec![self.expr()?];
+        while let Some(',') = self.peek() {
+            self.chars.next();
+            args.push(self.expr()?);
+        }
+        self.expect(')')?;
+
+        if args.len() != func.num_args() {
+            return Err(format!(
+                "Function '{}' takes {} argument(s) but {} were given",
+                name,
+                func.num_args(),
+                args.len()
+            ));
+        }
+
+        Ok(Expr::Call(func, args))
+    }
+}
+
+impl FromStr for Formula {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            chars: s.chars().peekable(),
+        };
+
+        let result = parser.expr().and_then(|expr| match parser.peek() {
+            None => Ok(expr),
+            Some(c) => Err(format!("Unexpected '{c}'")),
+        });
+
+        match result {
+            Ok(expr) => Ok(Formula {
+                source: s.to_string(),
+                expr,
+            }),
+            Err(e) => Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("Unable to parse formula '{s}': {e}"),
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Formula {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+impl fmt::Debug for Formula {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Formula({})", self.source)
+    }
+}
+
+struct FormulaVisitor;
+
+impl<'de> Visitor<'de> for FormulaVisitor {
+    type Value = Formula;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a formula or number")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Formula, E> {
+        Formula::from_str(value).map_err(de::Error::custom)
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Formula, E> {
+        self.visit_str(&value.to_string())
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Formula, E> {
+        self.visit_str(&value.to_string())
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<Formula, E> {
+        self.visit_str(&value.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Formula {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Formula, D::Error> {
+        deserializer.deserialize_any(FormulaVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use std::str::FromStr;
+
+    use super::Formula;
+    use crate::rules::{Attribute, AttributeList, DerivedStat, Rules};
+    use sulis_core::resource::read_single_resource_path;
+
+    fn eval(text: &str) -> f32 {
+        let formula = Formula::from_str(text).unwrap();
+        formula.eval(&|var: &str| match var {
+            "x" => Some(3.0),
+            "y" => Some(-2.5),
+            _ => None,
+        })
+    }
+
+    fn load_rules() -> Rules {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../data/rules.yml");
+        let rules: Rules = read_single_resource_path(&path).unwrap();
+        rules.init_kinds().unwrap();
+        rules
+    }
+
+    fn attributes(values: &[(&str, u8)], base: i32) -> AttributeList {
+        let mut attrs = AttributeList::new(base as u8);
+        for &(short_name, value) in values {
+            attrs.set(Attribute::find_short_name(short_name).unwrap(), value);
+        }
+        attrs
+    }
+
+    #[test]
+    fn parse_and_eval() {
+        assert_eq!(eval("1 + 2 * 3"), 7.0);
+        assert_eq!(eval("(1 + 2) * 3"), 9.0);
+        assert_eq!(eval("10 - 4 - 3"), 3.0);
+        assert_eq!(eval("x * 2 + -y"), 8.5);
+        assert_eq!(eval("trunc(y) + floor(y) + ceil(0.5) + round(2.5)"), -1.0);
+        assert_eq!(eval("min(x, 1) + max(x, 1)"), 4.0);
+        assert_eq!(eval("unknown + 1"), 1.0);
+
+        assert!(Formula::from_str("1 +").is_err());
+        assert!(Formula::from_str("(1 + 2").is_err());
+        assert!(Formula::from_str("1 2").is_err());
+        assert!(Formula::from_str("min(1)").is_err());
+        assert!(Formula::from_str("sqrt(4)").is_err());
+    }
+
+    #[test]
+    fn default_rules() {
+        use crate::rules::DerivedStat::*;
+        let rules = load_rules();
+        let base = rules.base_attribute;
+        let attrs = attributes(
+            &[
+                ("str", 14),
+                ("dex", 12),
+                ("end", 13),
+                ("per", 11),
+                ("int", 9),
+                ("wis", 8),
+            ],
+            base,
+        );
+        let level = 3;
+
+        let expected_ints = [
+            (Initiative, 1),
+            (MeleeAccuracy, 39),
+            (RangedAccuracy, 35),
+            (SpellAccuracy, 26),
+            (Defense, 34),
+            (Fortitude, 36),
+            (Reflex, 34),
+            (Will, 26),
+            (Stealth, 4),
+            (Detection, 2),
+            (Mechanics, 1),
+            (HpPerLevel, 3),
+        ];
+        let expected_floats = [
+            (MeleeGrazeMultiplier, 0.08),
+            (MeleeHitMultiplier, 0.12),
+            (MeleeCritMultiplier, 0.24),
+            (RangedGrazeMultiplier, 0.04),
+            (RangedHitMultiplier, 0.06),
+            (RangedCritMultiplier, 0.12),
+        ];
+
+        for stat in rules.derived_stats.keys() {
+            let count = expected_ints.iter().filter(|(s, _)| s == stat).count()
+                + expected_floats.iter().filter(|(s, _)| s == stat).count();
+            assert_eq!(count, 1, "No expected value for {stat:?}");
+        }
+
+        for (stat, expected) in expected_ints {
+            let value = rules.derived_stat(stat, &attrs, level) as i32;
+            assert_eq!(value, expected, "{stat:?}");
+        }
+
+        for (stat, expected) in expected_floats {
+            let value = rules.derived_stat(stat, &attrs, level);
+            assert!((value - expected).abs() < 1e-5, "{stat:?}: {value}");
+        }
+
+        // at the base attribute, only the base values from the rules remain
+        let attrs = attributes(&[], base);
+        for (stat, _) in expected_ints {
+            let expected = match stat {
+                MeleeAccuracy | RangedAccuracy | SpellAccuracy => rules.base_accuracy as i32,
+                Defense | Fortitude | Reflex | Will => rules.base_defense as i32,
+                _ => 0,
+            };
+            assert_eq!(rules.derived_stat(stat, &attrs, level) as i32, expected);
+        }
+    }
+
+    #[test]
+    fn formula_variables() {
+        let mut rules = load_rules();
+        let formula = "base + bonus + (dex - base_attribute) * level";
+        rules
+            .derived_stats
+            .insert(DerivedStat::Reflex, Formula::from_str(formula).unwrap());
+        rules.init_kinds().unwrap();
+
+        let base = rules.base_attribute;
+        let attrs = attributes(&[("dex", base as u8 + 2)], base);
+        let expected = rules.base_defense as f32 + 4.0 + 2.0 * 5.0;
+        assert_eq!(rules.derived_stat(DerivedStat::Reflex, &attrs, 5), expected);
+    }
+
+    #[test]
+    fn unknown_variables_rejected() {
+        let mut rules = load_rules();
+        rules.derived_stats.insert(
+            DerivedStat::Will,
+            Formula::from_str("base + (cha - base_attribute) * 2").unwrap(),
+        );
+
+        let err = rules.init_kinds().unwrap_err();
+        assert!(err.to_string().contains("'cha'"), "{err}");
+    }
+}
@@ -0,0 +1,32 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use serde::Deserialize;
+
+/// Controls out of turn reactions, such as attacks of opportunity.  Each
+/// entity may react a limited number of times per round.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ReactionRules {
+    pub per_round: u32,
+
+    /// Whether moving out of a threatened square automatically provokes a
+    /// standard weapon attack from the threatening entity
+    pub attacks_of_opportunity: bool,
+
+    /// Ability groups whose activation while threatened provokes reactions
+    pub provoking_groups: Vec<String>,
+}
@@ -19,8 +19,8 @@ use std::rc::Rc;
 
 use crate::rules::bonus::{AttackBonuses, AttackBuilder, Bonus, BonusKind, BonusList};
 use crate::rules::{
-    AccuracyKind, Armor, ArmorKind, Attack, AttributeList, Damage, HitKind, Resistance, Slot,
-    WeaponKind, WeaponStyle,
+    AccuracyKind, Armor, ArmorKind, Attack, AttackRoll, AttributeList, Damage, HitKind, Resistance,
+    Slot, WeaponKind, WeaponStyle,
 };
 use crate::{Actor, Module};
 use sulis_core::image::Image;
@@ -63,6 +63,10 @@ pub struct StatList {
     pub will: i32,
     pub concealment: i32,
     pub concealment_ignore: i32,
+    pub stealth: i32,
+    pub detection: i32,
+    pub mechanics: i32,
+    pub reactions: i32,
     pub crit_chance: i32,
     pub hit_threshold: i32,
     pub graze_threshold: i32,
@@ -118,6 +122,10 @@ impl StatList {
             will: 0,
             concealment: 0,
             concealment_ignore: 0,
+            stealth: 0,
+            detection: 0,
+            mechanics: 0,
+            reactions: 0,
             crit_chance: 0,
             hit_threshold: 0,
             graze_threshold: 0,
@@ -187,6 +195,19 @@ impl StatList {
         defense: i32,
         bonuses: &AttackBonuses,
     ) -> HitKind {
+        self.attack_roll_detailed(accuracy_kind, crit_immunity, defense, bonuses)
+            .kind
+    }
+
+    /// Makes an attack roll as `attack_roll`, but returns the details of the
+    /// roll along with the result
+    pub fn attack_roll_detailed(
+        &self,
+        accuracy_kind: AccuracyKind,
+        crit_immunity: bool,
+        defense: i32,
+        bonuses: &AttackBonuses,
+    ) -> AttackRoll {
         let accuracy = match accuracy_kind {
             AccuracyKind::Melee => self.melee_accuracy + bonuses.melee_accuracy,
             AccuracyKind::Ranged => self.ranged_accuracy + bonuses.ranged_accuracy,
@@ -198,27 +219,43 @@ impl StatList {
             roll, accuracy, defense
         );
 
+        let graze_threshold = self.graze_threshold + bonuses.graze_threshold;
+        let hit_threshold = self.hit_threshold + bonuses.hit_threshold;
+        let mut attack_roll = AttackRoll {
+            kind: HitKind::Miss,
+            roll,
+            accuracy,
+            defense,
+            crit_roll: None,
+            graze_threshold,
+            hit_threshold,
+        };
+
         if roll + accuracy < defense {
-            return HitKind::Miss;
+            return attack_roll;
         }
 
-        let result = roll + accuracy - defense;
-
-        if !crit_immunity && (100 - roll) < self.crit_chance + bonuses.crit_chance {
-            let roll2 = gen_rand(1, 101);
-            let result2 = roll2 + accuracy - defense;
-            if result2 > self.graze_threshold + bonuses.graze_threshold {
-                HitKind::Crit
-            } else {
+        let result = attack_roll.result();
+
+        attack_roll.kind =
+            if !crit_immunity && (100 - roll) < self.crit_chance + bonuses.crit_chance {
+                let roll2 = gen_rand(1, 101);
+                attack_roll.crit_roll = Some(roll2);
+                let result2 = roll2 + accuracy - defense;
+                if result2 > graze_threshold {
+                    HitKind::Crit
+                } else {
+                    HitKind::Hit
+                }
+            } else if result > hit_threshold {
                 HitKind::Hit
-            }
-        } else if result > self.hit_threshold + bonuses.hit_threshold {
-            HitKind::Hit
-        } else if result > self.graze_threshold + bonuses.graze_threshold {
-            HitKind::Graze
-        } else {
-            HitKind::Miss
-        }
+            } else if result > graze_threshold {
+                HitKind::Graze
+            } else {
+                HitKind::Miss
+            };
+
+        attack_roll
     }
 
     pub fn has_shield(&self) -> bool {
@@ -363,6 +400,10 @@ impl StatList {
             Will(amount) => self.will += amount * times_i32,
             Concealment(amount) => self.concealment += amount * times_i32,
             ConcealmentIgnore(amount) => self.concealment_ignore += amount * times_i32,
+            Stealth(amount) => self.stealth += amount * times_i32,
+            Detection(amount) => self.detection += amount * times_i32,
+            Mechanics(amount) => self.mechanics += amount * times_i32,
+            Reactions(amount) => self.reactions += amount * times_i32,
             CritChance(amount) => self.crit_chance += amount * times_i32,
             HitThreshold(amount) => self.hit_threshold -= amount * times_i32,
             GrazeThreshold(amount) => self.graze_threshold -= amount * times_i32,
@@ -443,7 +484,7 @@ impl StatList {
         }
 
         let multiplier = if attacks.is_empty() {
-            attacks.push((&actor.race.base_attack, WeaponKind::Simple));
+            attacks.push((&actor.race.base_attack, rules.default_weapon_kind()));
             1.0
         } else if attacks.len() > 1 {
             rules.dual_wield_damage_multiplier
@@ -471,33 +512,32 @@ impl StatList {
         self.attack_range = attack_range.unwrap_or(0.0);
         self.armor.finalize();
 
-        let base_accuracy = rules.base_accuracy as i32;
-        let base_defense = rules.base_defense as i32;
-        let base_attr = rules.base_attribute;
-
-        use crate::rules::Attribute::*;
-        let attrs = &self.attributes;
-        let str_bonus = attrs.bonus(Strength, base_attr);
-        let dex_bonus = attrs.bonus(Dexterity, base_attr);
-        let end_bonus = attrs.bonus(Endurance, base_attr);
-        let per_bonus = attrs.bonus(Perception, base_attr);
-        let int_bonus = attrs.bonus(Intellect, base_attr);
-        let wis_bonus = attrs.bonus(Wisdom, base_attr);
-        self.initiative += dex_bonus / 2 + per_bonus / 2;
-        self.melee_accuracy += base_accuracy + per_bonus + str_bonus * 2;
-        self.ranged_accuracy += base_accuracy + per_bonus + dex_bonus * 2;
-        self.spell_accuracy += base_accuracy + wis_bonus + int_bonus * 2;
-        self.defense += base_defense + dex_bonus * 2;
-        self.fortitude += base_defense + end_bonus * 2;
-        self.reflex += base_defense + dex_bonus * 2;
-        self.will += base_defense + wis_bonus * 2;
-        self.max_hp += (actor.total_level as i32 * end_bonus) / 3;
-
-        let damage_stat_bonus = if is_melee { str_bonus } else { dex_bonus } as f32;
-
-        self.graze_multiplier += 0.02 * damage_stat_bonus;
-        self.hit_multiplier += 0.03 * damage_stat_bonus;
-        self.crit_multiplier += 0.06 * damage_stat_bonus;
+        use crate::rules::DerivedStat::*;
+        let attributes = self.attributes.clone();
+        let stat = |stat| rules.derived_stat(stat, &attributes, actor.total_level);
+
+        self.initiative += stat(Initiative) as i32;
+        self.melee_accuracy += stat(MeleeAccuracy) as i32;
+        self.ranged_accuracy += stat(RangedAccuracy) as i32;
+        self.spell_accuracy += stat(SpellAccuracy) as i32;
+        self.defense += stat(Defense) as i32;
+        self.fortitude += stat(Fortitude) as i32;
+        self.reflex += stat(Reflex) as i32;
+        self.will += stat(Will) as i32;
+        self.stealth += stat(Stealth) as i32;
+        self.detection += stat(Detection) as i32;
+        self.mechanics += stat(Mechanics) as i32;
+        self.max_hp += stat(HpPerLevel) as i32;
+
+        if is_melee {
+            self.graze_multiplier += stat(MeleeGrazeMultiplier);
+            self.hit_multiplier += stat(MeleeHitMultiplier);
+            self.crit_multiplier += stat(MeleeCritMultiplier);
+        } else {
+            self.graze_multiplier += stat(RangedGrazeMultiplier);
+            self.hit_multiplier += stat(RangedHitMultiplier);
+            self.crit_multiplier += stat(RangedCritMultiplier);
+        }
 
         if self.hit_multiplier < self.graze_multiplier {
             self.hit_multiplier = self.graze_multiplier;
@@ -525,6 +565,7 @@ impl StatList {
         self.movement_rate += actor.race.movement_rate;
         self.move_anim_rate += actor.race.move_anim_rate;
         self.attack_cost += rules.attack_ap as i32;
+        self.reactions += rules.reactions.per_round as i32;
 
         let size_bonus = actor.race.size.diagonal / 2.0;
         self.touch_range = self.bonus_reach + size_bonus;
@@ -0,0 +1,52 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use serde::Deserialize;
+
+/// Parameters for the perception vs. stealth contest used by hidden
+/// entities, and the awareness levels that AI entities build up as
+/// they notice them.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct StealthRules {
+    pub base_stealth: i32,
+    pub base_detection: i32,
+
+    /// Detection penalty applied for each square of distance between the
+    /// observer and the hidden entity
+    pub detection_dist_penalty: f32,
+
+    /// The full angle, in degrees, of the cone an entity can see in front
+    /// of it.  Entities within `peripheral_dist` are always seen
+    pub vision_cone_angle: f32,
+    pub peripheral_dist: f32,
+
+    /// Awareness points needed to become suspicious and alerted
+    pub suspicious_threshold: u32,
+    pub alerted_threshold: u32,
+
+    /// Awareness points lost each round by entities that are not alerted
+    pub awareness_decay: u32,
+
+    /// The radius, in squares, at which various actions can be heard
+    pub movement_noise: f32,
+    pub hidden_movement_noise: f32,
+    pub combat_noise: f32,
+
+    /// Party members within this distance of a hidden trap they can see get
+    /// a single detection check to spot it
+    pub trap_detection_dist: f32,
+}
@@ -0,0 +1,123 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2019 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::io::Error;
+
+use serde::Deserialize;
+
+use crate::condition::validate_triggers;
+use crate::{Module, OnTrigger};
+use sulis_core::ui::Color;
+use sulis_core::util::invalid_data_error;
+
+/// A timeline of steps played out by the actors in the current area, such
+/// as moving, speaking, and animating.  Player input is blocked until the
+/// sequence completes.
+#[derive(Debug)]
+pub struct Sequence {
+    pub id: String,
+    pub steps: Vec<Step>,
+    pub on_end: Vec<OnTrigger>,
+}
+
+impl Sequence {
+    pub fn new(builder: SequenceBuilder, module: &Module) -> Result<Sequence, Error> {
+        if builder.steps.is_empty() {
+            return invalid_data_error(&format!(
+                "Sequence '{}' must have at least one step",
+                builder.id
+            ));
+        }
+
+        for step in builder.steps.iter() {
+            match step {
+                Step::Color { millis, .. } | Step::Subpos { millis, .. } if *millis == 0 => {
+                    return invalid_data_error(&format!(
+                        "Animation steps in sequence '{}' must have a non-zero duration",
+                        builder.id
+                    ));
+                }
+                Step::Fire(triggers) => validate_triggers(triggers, module)?,
+                _ => (),
+            }
+        }
+
+        validate_triggers(&builder.on_end, module)?;
+
+        Ok(Sequence {
+            id: builder.id,
+            steps: builder.steps,
+            on_end: builder.on_end,
+        })
+    }
+}
+
+/// A single step in a sequence.  Entities are referred to as `player`, by a
+/// party member's actor ID, or by an entity's unique ID.  Steps run one
+/// after another without waiting unless a wait step is used.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub enum Step {
+    /// Moves the entity along a path to the specified point
+    Move { entity: String, x: i32, y: i32 },
+
+    /// Shows the text above the entity
+    Speak { entity: String, text: String },
+
+    /// Tints the entity with the color for the duration
+    Color {
+        entity: String,
+        color: Color,
+        millis: u32,
+    },
+
+    /// Shifts the entity's drawn position towards the offset, in tiles, over
+    /// the duration.  The entity snaps back to its position at the end
+    Subpos {
+        entity: String,
+        x: f32,
+        y: f32,
+        millis: u32,
+    },
+
+    /// Centers the view on the specified point
+    ScrollView { x: i32, y: i32 },
+
+    /// Centers the view on the entity
+    Focus(String),
+
+    /// Waits for the specified number of millis
+    Wait(u32),
+
+    /// Waits for the entity to finish its current moves and animations
+    WaitFor(String),
+
+    /// Waits for all moves and animations in the area to finish
+    WaitAll,
+
+    /// Fires the triggers, with the player as both parent and target
+    Fire(Vec<OnTrigger>),
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SequenceBuilder {
+    pub id: String,
+    pub steps: Vec<Step>,
+
+    #[serde(default)]
+    pub on_end: Vec<OnTrigger>,
+}
@@ -0,0 +1,135 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::collections::{HashMap, HashSet};
+use std::io::Error;
+use std::rc::Rc;
+
+use serde::Deserialize;
+
+use crate::{BonusList, Damage, DamageKind};
+use sulis_core::image::Image;
+use sulis_core::resource::ResourceSet;
+use sulis_core::ui::Color;
+use sulis_core::util::{invalid_data_error, unable_to_create_error};
+
+/// A kind of elemental surface, such as fire, oil, or water, that may cover
+/// squares of an area.  Surfaces interact with damage dealt on them and with
+/// other surfaces placed on top of them, as specified by `on_damage` and
+/// `on_surface`.
+#[derive(Debug)]
+pub struct SurfaceType {
+    pub id: String,
+    pub name: String,
+    pub image: Rc<dyn Image>,
+    pub color: Color,
+
+    /// The number of rounds this surface lasts, or None if it is permanent
+    pub duration: Option<u32>,
+
+    /// Damage dealt each round to entities standing on this surface
+    pub damage: Option<Damage>,
+
+    /// Bonuses applied to entities standing on this surface
+    pub bonuses: BonusList,
+
+    /// The distance, in squares, that an interaction on one square of this
+    /// surface spreads through connected squares of the same surface
+    pub spread: u32,
+
+    /// The surface this surface becomes when damaged by a given damage kind.
+    /// A result of None removes the surface.
+    pub on_damage: HashMap<DamageKind, Option<String>>,
+
+    /// The surface this surface becomes when another surface, specified by
+    /// ID, is placed on it.  A result of None removes the surface.  Surfaces
+    /// not listed simply replace this surface.
+    pub on_surface: HashMap<String, Option<String>>,
+}
+
+impl SurfaceType {
+    pub fn new(builder: SurfaceTypeBuilder, ids: &HashSet<String>) -> Result<SurfaceType, Error> {
+        let image = match ResourceSet::image(&builder.image) {
+            None => {
+                warn!("Unable to locate image '{}'", builder.image);
+                return unable_to_create_error("surface_type", &builder.id);
+            }
+            Some(image) => image,
+        };
+
+        let results = builder
+            .on_damage
+            .values()
+            .chain(builder.on_surface.values());
+        for result in results.flatten() {
+            if !ids.contains(result) {
+                return invalid_data_error(&format!(
+                    "Unknown surface type '{}' in interactions for '{}'",
+                    result, builder.id
+                ));
+            }
+        }
+
+        for id in builder.on_surface.keys() {
+            if !ids.contains(id) {
+                return invalid_data_error(&format!(
+                    "Unknown surface type '{}' in on_surface for '{}'",
+                    id, builder.id
+                ));
+            }
+        }
+
+        Ok(SurfaceType {
+            id: builder.id,
+            name: builder.name,
+            image,
+            color: builder.color,
+            duration: builder.duration,
+            damage: builder.damage,
+            bonuses: builder.bonuses,
+            spread: builder.spread,
+            on_damage: builder.on_damage,
+            on_surface: builder.on_surface,
+        })
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SurfaceTypeBuilder {
+    pub id: String,
+    pub name: String,
+    pub image: String,
+    pub color: Color,
+
+    #[serde(default)]
+    pub duration: Option<u32>,
+
+    #[serde(default)]
+    pub damage: Option<Damage>,
+
+    #[serde(default)]
+    pub bonuses: BonusList,
+
+    #[serde(default)]
+    pub spread: u32,
+
+    #[serde(default)]
+    pub on_damage: HashMap<DamageKind, Option<String>>,
+
+    #[serde(default)]
+    pub on_surface: HashMap<String, Option<String>>,
+}
@@ -28,7 +28,7 @@ use sulis_core::image::{Image, LayeredImage};
 use sulis_core::io::GraphicsRenderer;
 use sulis_core::util::{invalid_data_error, ExtInt, Offset, Scale};
 use sulis_module::{Ability, Actor, ActorBuilder, Faction, ImageLayer, Module};
-use sulis_module::{BonusList, ItemKind, ItemState, QuickSlot, Slot, StatList};
+use sulis_module::{BonusList, ItemKind, ItemState, QuickSlot, Slot, StatList, SurfaceType};
 
 pub struct ActorState {
     pub actor: Rc<Actor>,
@@ -36,6 +36,7 @@ pub struct ActorState {
     pub listeners: ChangeListenerList<ActorState>,
     inventory: Inventory,
     effects: Vec<(usize, BonusList)>,
+    surface_type: Option<Rc<SurfaceType>>,
     image: LayeredImage,
     pub(crate) ability_states: HashMap<String, AbilityState>,
     texture_cache_invalid: bool,
@@ -54,7 +55,7 @@ impl ActorState {
             Some(builder) => Rc::new(Module::load_actor(builder)?),
         };
 
-        let attrs = actor.attributes;
+        let attrs = actor.attributes.clone();
 
         let image = LayeredImage::new(
             actor
@@ -110,6 +111,7 @@ impl ActorState {
             listeners: ChangeListenerList::default(),
             image,
             effects: Vec::new(),
+            surface_type: None,
             ability_states,
             texture_cache_invalid: false,
             p_stats: save.p_stats,
@@ -128,7 +130,7 @@ impl ActorState {
                 .get_list(actor.sex, actor.hair_color, actor.skin_color),
             actor.hue,
         );
-        let attrs = actor.attributes;
+        let attrs = actor.attributes.clone();
 
         let mut ability_states = HashMap::new();
         for ability in actor.abilities.iter() {
@@ -147,6 +149,7 @@ impl ActorState {
             listeners: ChangeListenerList::default(),
             image,
             effects: Vec::new(),
+            surface_type: None,
             ability_states,
             texture_cache_invalid: false,
             p_stats: PStats::new(&actor),
@@ -228,6 +231,16 @@ impl ActorState {
         self.p_stats.is_threatened()
     }
 
+    pub fn reactions_remaining(&self) -> u32 {
+        self.p_stats.reactions_remaining(&self.stats)
+    }
+
+    pub(crate) fn use_reaction(&mut self) -> bool {
+        let result = self.p_stats.use_reaction(&self.stats);
+        self.listeners.notify(self);
+        result
+    }
+
     pub fn add_threatening(&mut self, index: usize) {
         self.p_stats.add_threatening(index);
     }
@@ -805,6 +818,21 @@ impl ActorState {
         self.compute_stats();
     }
 
+    /// The surface type this actor is currently standing on, if any
+    pub fn surface_type(&self) -> Option<&Rc<SurfaceType>> {
+        self.surface_type.as_ref()
+    }
+
+    pub(crate) fn set_surface_type(&mut self, surface_type: Option<Rc<SurfaceType>>) {
+        let cur_id = self.surface_type.as_ref().map(|s| &s.id);
+        if cur_id == surface_type.as_ref().map(|s| &s.id) {
+            return;
+        }
+
+        self.surface_type = surface_type;
+        self.compute_stats();
+    }
+
     pub fn init_day(&mut self) {
         self.p_stats.init_day(&self.stats);
         self.listeners.notify(self);
@@ -836,7 +864,7 @@ impl ActorState {
 
     pub fn compute_stats(&mut self) {
         debug!("Compute stats for '{}'", self.actor.name);
-        self.stats = StatList::new(self.actor.attributes);
+        self.stats = StatList::new(self.actor.attributes.clone());
 
         let mut layers_override = self.inventory().get_image_layers();
         for (layer, image) in self.anim_image_layers.iter() {
@@ -904,6 +932,10 @@ impl ActorState {
             self.stats.add(bonuses);
         }
 
+        if let Some(surface_type) = &self.surface_type {
+            self.stats.add(&surface_type.bonuses);
+        }
+
         let mut equipped_armor = HashMap::new();
         for slot in Slot::iter() {
             if let Some(item_state) = self.inventory.equipped(*slot) {
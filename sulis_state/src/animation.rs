@@ -35,12 +35,15 @@ pub mod move_animation;
 
 pub mod particle_generator;
 
+pub mod prop_attack_animation;
+
 pub mod ranged_attack_animation;
 
 use self::melee_attack_animation::MeleeAttackAnimModel;
 use self::move_animation::MoveAnimModel;
 use self::particle_generator::Param;
 use self::particle_generator::{GeneratorModel, GeneratorState};
+use self::prop_attack_animation::PropAttackAnimModel;
 use self::ranged_attack_animation::RangedAttackAnimModel;
 use crate::{ChangeListener, Effect, EntityState, ScriptCallback};
 use sulis_core::{
@@ -336,6 +339,9 @@ pub(in crate::animation) enum AnimKind {
     /// An attack with a ranged weapon
     RangedAttack { model: RangedAttackAnimModel },
 
+    /// An attack against a destructible prop
+    PropAttack { model: PropAttackAnimModel },
+
     /// Movement of a single entity within an area
     Move { model: MoveAnimModel },
 
@@ -432,6 +438,18 @@ impl Anim {
         )
     }
 
+    pub(in crate::animation) fn new_prop_attack(
+        attacker: &Rc<RefCell<EntityState>>,
+        duration_millis: u32,
+        model: PropAttackAnimModel,
+    ) -> Anim {
+        Anim::new(
+            attacker,
+            ExtInt::Int(duration_millis),
+            AnimKind::PropAttack { model },
+        )
+    }
+
     pub(in crate::animation) fn new_move(
         mover: &Rc<RefCell<EntityState>>,
         duration_millis: u32,
@@ -550,6 +568,7 @@ impl Anim {
         match self.kind {
             MeleeAttack { ref model } => model.has_attacked,
             RangedAttack { ref model } => model.has_attacked,
+            PropAttack { ref model } => model.has_attacked,
             Move { ref model } => model.last_frame_index == model.path.len() as i32 - 1,
             _ => true,
         }
@@ -583,6 +602,7 @@ impl Anim {
             RangedAttack { ref mut model } => {
                 ranged_attack_animation::update(&self.owner, model, frac)
             }
+            PropAttack { ref mut model } => prop_attack_animation::update(&self.owner, model, frac),
             Move { ref mut model } => {
                 move_animation::update(&self.owner, &self.marked_for_removal, model, millis)
             }
@@ -614,6 +634,7 @@ impl Anim {
             EntityScale { .. } => entity_scale_animation::cleanup(&self.owner),
             MeleeAttack { .. } => melee_attack_animation::cleanup(&self.owner),
             RangedAttack { .. } => ranged_attack_animation::cleanup(&self.owner),
+            PropAttack { .. } => prop_attack_animation::cleanup(&self.owner),
             Move { ref mut model } => move_animation::cleanup(&self.owner, model),
             EntityDeath { .. } => {
                 entity_color_animation::cleanup(&self.owner);
@@ -692,6 +713,7 @@ impl Anim {
             EntityScale { .. } | EntityImageLayer { .. } => !self.duration_millis.is_infinite(),
             MeleeAttack { .. } => true,
             RangedAttack { .. } => true,
+            PropAttack { .. } => true,
             Move { .. } => true,
             Wait => true,
             NonBlockingWait => false,
@@ -0,0 +1,73 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2019 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::{animation::Anim, prop_damage_handler, EntityState, GameState};
+
+pub(in crate::animation) fn update(
+    attacker: &Rc<RefCell<EntityState>>,
+    model: &mut PropAttackAnimModel,
+    frac: f32,
+) {
+    if !model.has_attacked && frac > 0.5 {
+        model.has_attacked = true;
+        prop_damage_handler::weapon_attack_prop(attacker, model.prop_index);
+    }
+
+    let mut attacker = attacker.borrow_mut();
+    if frac > 0.5 {
+        attacker.sub_pos = ((1.0 - frac) * model.vector.0, (1.0 - frac) * model.vector.1);
+    } else {
+        attacker.sub_pos = (frac * model.vector.0, frac * model.vector.1);
+    }
+}
+
+pub(in crate::animation) fn cleanup(owner: &Rc<RefCell<EntityState>>) {
+    owner.borrow_mut().sub_pos = (0.0, 0.0);
+}
+
+pub(in crate::animation) struct PropAttackAnimModel {
+    prop_index: usize,
+    vector: (f32, f32),
+    pub(in crate::animation) has_attacked: bool,
+}
+
+pub fn new(attacker: &Rc<RefCell<EntityState>>, prop_index: usize, duration_millis: u32) -> Anim {
+    let vector = {
+        let area_state = GameState::area_state();
+        let area_state = area_state.borrow();
+        let prop = area_state.props().get(prop_index);
+        let attacker = attacker.borrow();
+
+        let x = prop.location.x + prop.prop.size.width / 2
+            - attacker.location.x
+            - attacker.size.width / 2;
+        let y = prop.location.y + prop.prop.size.height / 2
+            - attacker.location.y
+            - attacker.size.height / 2;
+        (x as f32, y as f32)
+    };
+
+    let model = PropAttackAnimModel {
+        prop_index,
+        vector,
+        has_attacked: false,
+    };
+
+    Anim::new_prop_attack(attacker, duration_millis, model)
+}
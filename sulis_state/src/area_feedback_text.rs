@@ -25,7 +25,7 @@ use sulis_core::ui::{animation_state, Color, LineRenderer};
 use sulis_core::util::{self, Offset, Point, Rect, Scale};
 use sulis_module::{DamageKind, HitFlags, HitKind};
 
-use crate::{AreaState, EntityState};
+use crate::{AreaState, EntityState, PropState};
 
 pub struct Params {
     pub font: Rc<Font>,
@@ -36,7 +36,8 @@ pub struct Params {
     pub miss_color: Color,
     pub hit_color: Color,
     pub heal_color: Color,
-    pub damage_colors: [Color; 8],
+    pub damage_colors: Vec<Color>,
+    pub damage_icons: Vec<Rc<dyn Image>>,
 
     pub concealment_icon: Rc<dyn Image>,
     pub backstab_icon: Rc<dyn Image>,
@@ -58,9 +59,8 @@ impl Default for Params {
             miss_color: LIGHT_GRAY,
             hit_color: RED,
             heal_color: BLUE,
-            damage_colors: [
-                LIGHT_GRAY, LIGHT_GRAY, LIGHT_GRAY, GREEN, CYAN, BLUE, YELLOW, PURPLE,
-            ],
+            damage_colors: Vec::new(),
+            damage_icons: Vec::new(),
             concealment_icon: ResourceSet::empty_image(),
             backstab_icon: ResourceSet::empty_image(),
             flanking_icon: ResourceSet::empty_image(),
@@ -94,6 +94,7 @@ pub enum IconKind {
     Crit,
     Hit,
     Graze,
+    Damage(DamageKind),
 }
 
 pub struct AreaFeedbackText {
@@ -121,43 +122,73 @@ impl AreaFeedbackText {
         damage: &[(DamageKind, u32)],
     ) -> AreaFeedbackText {
         let mut text = AreaFeedbackText::with_target(target, area);
+        text.add_damage_entries(hit_kind, hit_flags, damage);
+        text
+    }
+
+    /// Creates feedback text for damage dealt to a destructible prop
+    pub fn with_prop_damage(
+        prop: &PropState,
+        area: &AreaState,
+        hit_kind: HitKind,
+        damage: &[(DamageKind, u32)],
+    ) -> AreaFeedbackText {
+        let mut text = AreaFeedbackText::with_prop(prop, area);
+        text.add_damage_entries(hit_kind, HitFlags::default(), damage);
+        text
+    }
+
+    pub fn with_prop(prop: &PropState, area: &AreaState) -> AreaFeedbackText {
+        let width = prop.prop.size.width;
+        AreaFeedbackText::at_location(prop.location.to_point(), width, area)
+    }
 
+    fn add_damage_entries(
+        &mut self,
+        hit_kind: HitKind,
+        hit_flags: HitFlags,
+        damage: &[(DamageKind, u32)],
+    ) {
         if hit_flags.sneak_attack {
-            text.add_icon_entry(IconKind::Backstab, ColorKind::Info);
+            self.add_icon_entry(IconKind::Backstab, ColorKind::Info);
         } else if hit_flags.flanking {
-            text.add_icon_entry(IconKind::Flanking, ColorKind::Info);
+            self.add_icon_entry(IconKind::Flanking, ColorKind::Info);
         }
 
         if hit_flags.concealment {
-            text.add_icon_entry(IconKind::Concealment, ColorKind::Info);
+            self.add_icon_entry(IconKind::Concealment, ColorKind::Info);
         }
 
         let mut first = true;
         for (kind, amount) in damage {
             if !first {
-                text.add_entry(" + ".to_string(), ColorKind::Info);
+                self.add_entry(" + ".to_string(), ColorKind::Info);
             }
 
             let color = ColorKind::Damage { kind: *kind };
-            text.add_entry(format!("{amount}"), color);
+            self.add_entry(format!("{amount}"), color);
+            if kind.icon().is_some() {
+                self.add_icon_entry(IconKind::Damage(*kind), color);
+            }
 
             first = false;
         }
 
         match hit_kind {
-            HitKind::Graze => text.add_icon_entry(IconKind::Graze, ColorKind::Info),
-            HitKind::Hit => text.add_icon_entry(IconKind::Hit, ColorKind::Info),
-            HitKind::Crit => text.add_icon_entry(IconKind::Crit, ColorKind::Info),
-            HitKind::Miss => text.add_entry("Miss".to_string(), ColorKind::Miss),
+            HitKind::Graze => self.add_icon_entry(IconKind::Graze, ColorKind::Info),
+            HitKind::Hit => self.add_icon_entry(IconKind::Hit, ColorKind::Info),
+            HitKind::Crit => self.add_icon_entry(IconKind::Crit, ColorKind::Info),
+            HitKind::Miss => self.add_entry("Miss".to_string(), ColorKind::Miss),
             HitKind::Auto => (),
         }
-
-        text
     }
 
     pub fn with_target(target: &EntityState, area: &AreaState) -> AreaFeedbackText {
+        AreaFeedbackText::at_location(target.location.to_point(), target.size.width, area)
+    }
+
+    fn at_location(mut area_pos: Point, width: i32, area: &AreaState) -> AreaFeedbackText {
         let move_rate = 3.0;
-        let mut area_pos = target.location.to_point();
         loop {
             let mut valid = true;
 
@@ -176,7 +207,7 @@ impl AreaFeedbackText {
             }
         }
 
-        let width = target.size.width as f32;
+        let width = width as f32;
         let pos_x = area_pos.x as f32 + width / 2.0;
         let pos_y = area_pos.y as f32 - 1.5;
 
@@ -275,10 +306,10 @@ impl AreaFeedbackText {
                 ColorKind::Miss => params.miss_color,
                 ColorKind::Hit => params.hit_color,
                 ColorKind::Heal => params.heal_color,
-                ColorKind::Damage { kind } => {
-                    let index = kind.index();
-                    params.damage_colors[index]
-                }
+                ColorKind::Damage { kind } => match params.damage_colors.get(kind.index()) {
+                    None => params.info_color,
+                    Some(color) => *color,
+                },
             };
             color.a *= self.alpha;
 
@@ -294,6 +325,10 @@ impl AreaFeedbackText {
                     IconKind::Crit => &params.crit_icon,
                     IconKind::Hit => &params.hit_icon,
                     IconKind::Graze => &params.graze_icon,
+                    IconKind::Damage(kind) => match params.damage_icons.get(kind.index()) {
+                        None => continue,
+                        Some(icon) => icon,
+                    },
                 };
 
                 let rect = Rect {
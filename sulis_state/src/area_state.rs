@@ -17,6 +17,9 @@
 mod prop_handler;
 use prop_handler::PropHandler;
 
+mod surface_layer;
+pub use surface_layer::{SurfaceLayer, SurfaceTile};
+
 use std::cell::RefCell;
 use std::collections::HashSet;
 use std::io::Error;
@@ -30,7 +33,10 @@ use sulis_core::io::Audio;
 use sulis_core::config::Config;
 use sulis_core::util::{self, gen_rand, invalid_data_error, Point, Size};
 use sulis_module::area::{Transition, TriggerKind, Trigger};
-use sulis_module::{Actor, Area, LootList, Module, ObjectSize, Time};
+use sulis_module::{
+    Actor, Area, Damage, DamageKind, LootList, Module, ObjectSize, RangedModifiers, SurfaceType,
+    Time,
+};
 
 pub struct TriggerState {
     pub(crate) fired: bool,
@@ -70,12 +76,15 @@ pub struct AreaState {
     trigger_grid: Vec<Option<usize>>,
 
     props: PropHandler,
+    surface_layer: SurfaceLayer,
 
     pc_vis_redraw: PCVisRedraw,
     pc_vis: Vec<bool>,
 
     feedback_text: Vec<AreaFeedbackText>,
     scroll_to_callback: Option<Rc<RefCell<EntityState>>>,
+    sprung_traps: Vec<(Rc<RefCell<EntityState>>, usize)>,
+    trap_checks: Vec<String>,
 
     targeter: Option<Rc<RefCell<AreaTargeter>>>,
     range_indicators: RangeIndicatorHandler,
@@ -98,6 +107,45 @@ fn gen_area(area: Rc<Area>, seed: Option<u128>) -> Result<(GeneratedArea, u128),
     Ok((area, seed))
 }
 
+// only voluntary movement, during the mover's own turn, provokes reactions
+fn queue_left_threat_reactions(
+    entity: &Rc<RefCell<EntityState>>,
+    old_threateners: &[usize],
+    from: Point,
+    mgr: &mut TurnManager,
+) {
+    match mgr.current() {
+        Some(current) if Rc::ptr_eq(&current, entity) => (),
+        _ => return,
+    }
+
+    let index = entity.borrow().index();
+    for reactor in old_threateners {
+        if entity.borrow().actor.p_stats().is_threatened_by(*reactor) {
+            continue;
+        }
+
+        mgr.queue_left_threat_reaction(*reactor, index, from);
+    }
+}
+
+// any movement into reach, voluntary or not, triggers readied actions
+fn queue_entered_threat_reactions(
+    entity: &Rc<RefCell<EntityState>>,
+    old_threateners: &[usize],
+    mgr: &mut TurnManager,
+) {
+    let new_threateners = entity.borrow().actor.p_stats().threatened_by().to_vec();
+    let index = entity.borrow().index();
+    for reactor in new_threateners {
+        if old_threateners.contains(&reactor) || mgr.readied_action(reactor).is_none() {
+            continue;
+        }
+
+        mgr.queue_reaction(ReactionKind::TargetEnteredThreat, reactor, index);
+    }
+}
+
 impl AreaState {
     pub fn new(area: Rc<Area>, seed: Option<u128>) -> Result<AreaState, Error> {
         let (gened, area_gen_seed) = gen_area(Rc::clone(&area), seed)?;
@@ -111,12 +159,14 @@ impl AreaState {
         let pc_explored = vec![false; dim];
 
         let props = PropHandler::new(dim, &area);
+        let surface_layer = SurfaceLayer::new(gened.area.width, gened.area.height);
 
         info!("Initializing area state for '{}'", gened.area.name);
         Ok(AreaState {
             area: gened,
             area_gen_seed,
             props,
+            surface_layer,
             entities: Vec::new(),
             surfaces: Vec::new(),
             triggers: Vec::new(),
@@ -129,6 +179,8 @@ impl AreaState {
             pc_vis_redraw: PCVisRedraw::Not,
             feedback_text: Vec::new(),
             scroll_to_callback: None,
+            sprung_traps: Vec::new(),
+            trap_checks: Vec::new(),
             targeter: None,
             range_indicators: RangeIndicatorHandler::default(),
             merchants: Vec::new(),
@@ -160,6 +212,7 @@ impl AreaState {
         }
 
         area_state.props.load(save.props)?;
+        area_state.surface_layer.load(save.surfaces)?;
 
         for (index, trigger_save) in save.triggers.into_iter().enumerate() {
             if index >= area_state.area.area.triggers.len() {
@@ -192,6 +245,82 @@ impl AreaState {
         &mut self.props
     }
 
+    pub fn surface_layer(&self) -> &SurfaceLayer {
+        &self.surface_layer
+    }
+
+    /// Places the surface type on the passable squares among `points`
+    pub fn add_surface_type(
+        &mut self,
+        surface_type: &Rc<SurfaceType>,
+        points: &[Point],
+        round: u32,
+    ) {
+        let points = self.surface_points(points);
+        self.surface_layer.add(surface_type, &points, round);
+    }
+
+    /// Applies damage of the specified kind to any surfaces on `points`
+    pub fn damage_surface_types(&mut self, kind: DamageKind, points: &[Point], round: u32) {
+        let points = self.surface_points(points);
+        self.surface_layer.apply_damage(kind, &points, round);
+    }
+
+    pub fn remove_surface_types(&mut self, points: &[Point]) {
+        let points = self.surface_points(points);
+        self.surface_layer.remove(&points);
+    }
+
+    fn surface_points(&self, points: &[Point]) -> Vec<Point> {
+        points
+            .iter()
+            .filter(|p| self.area.area.coords_valid(p.x, p.y))
+            .filter(|p| self.area.layer_set.is_passable(p.x, p.y))
+            .copied()
+            .collect()
+    }
+
+    /// Updates the surface type each entity in this area is standing on,
+    /// and returns the damage to apply to each entity if a new round has
+    /// started
+    #[must_use]
+    pub(crate) fn update_surface_types(
+        &mut self,
+        mgr: &TurnManager,
+    ) -> Vec<(Rc<RefCell<EntityState>>, Damage)> {
+        let new_round = self.surface_layer.update(mgr.current_round());
+        let changed = self.surface_layer.take_changed();
+
+        let mut damage = Vec::new();
+        if !new_round && !changed {
+            return damage;
+        }
+
+        for index in self.entities.iter() {
+            let entity = mgr.entity(*index);
+            let surface_type = {
+                let entity = entity.borrow();
+                let x = entity.location.x + entity.size.width / 2;
+                let y = entity.location.y + entity.size.height / 2;
+                self.surface_layer
+                    .get(x, y)
+                    .map(|tile| Rc::clone(&tile.surface_type))
+            };
+
+            if new_round && !entity.borrow().actor.is_dead() {
+                if let Some(amount) = surface_type.as_ref().and_then(|s| s.damage) {
+                    damage.push((Rc::clone(&entity), amount));
+                }
+            }
+
+            if changed {
+                entity.borrow_mut().actor.set_surface_type(surface_type);
+            }
+        }
+
+        damage
+    }
+
     fn pc_vis_partial_redraw(&mut self, x: i32, y: i32) {
         if let PCVisRedraw::Not = self.pc_vis_redraw {
             self.pc_vis_redraw = PCVisRedraw::Partial {
@@ -657,10 +786,51 @@ impl AreaState {
         self.update_view_visibility();
     }
 
+    /// Removes hit points from the destructible prop at `index`.  Returns
+    /// true if the prop was destroyed as a result.
+    pub(crate) fn remove_prop_hp(&mut self, index: usize, amount: u32) -> bool {
+        if !self.props.remove_hp(index, amount) {
+            return false;
+        }
+
+        self.pc_vis_partial_redraw(0, 0);
+        for member in GameState::party().iter() {
+            self.compute_pc_visibility(member, 0, 0);
+        }
+        self.update_view_visibility();
+
+        true
+    }
+
     pub fn has_visibility(&self, parent: &EntityState, target: &EntityState) -> bool {
         has_visibility(&self.area, self.props.entire_vis_grid(), parent, target)
     }
 
+    /// Computes the cover of `target` from ranged attacks by `parent`, and
+    /// the resulting accuracy and defense modifiers including elevation
+    pub fn ranged_modifiers(&self, parent: &EntityState, target: &EntityState) -> RangedModifiers {
+        let rules = Module::rules();
+
+        let fraction = cover_fraction(
+            &self.area,
+            self.props.entire_vis_grid(),
+            self.props.entire_pass_grid(),
+            parent,
+            target,
+        );
+
+        let elevation = |e: &EntityState| {
+            let x = e.location.x + e.size.width / 2;
+            let y = e.location.y + e.size.height / 2;
+            self.area.layer_set.elevation(x, y) as i32
+        };
+
+        let cover = rules.cover.cover(fraction);
+        rules
+            .cover
+            .modifiers(cover, elevation(parent) - elevation(target))
+    }
+
     pub fn compute_pc_visibility(
         &mut self,
         entity: &Rc<RefCell<EntityState>>,
@@ -679,6 +849,9 @@ impl AreaState {
             delta_y,
         );
 
+        let checks = trap_handler::check_detection(self, &entity.borrow(), &props_vis);
+        self.trap_checks.extend(checks);
+
         // set explored to true for any partially visible props
         for prop_index in props_vis {
             let prop = self.props.get(prop_index);
@@ -746,6 +919,38 @@ impl AreaState {
         );
     }
 
+    fn check_traps(&mut self, entity: &Rc<RefCell<EntityState>>) {
+        let mut indices: Vec<usize> = Vec::new();
+        for p in entity.borrow().location_points() {
+            if !self.area.area.coords_valid(p.x, p.y) {
+                continue;
+            }
+
+            let grid_index = (p.x + p.y * self.area.width) as usize;
+            for index in self.props.grid()[grid_index].iter() {
+                if !indices.contains(index) && self.props.get(*index).is_trap_armed() {
+                    indices.push(*index);
+                }
+            }
+        }
+
+        for index in indices {
+            self.sprung_traps.push((Rc::clone(entity), index));
+        }
+    }
+
+    /// Takes the list of traps that have been stepped on by party members
+    /// since the last update, along with the party member that triggered each
+    pub(crate) fn take_sprung_traps(&mut self) -> Vec<(Rc<RefCell<EntityState>>, usize)> {
+        std::mem::take(&mut self.sprung_traps)
+    }
+
+    /// Takes the combat log text of the trap detection checks made by party
+    /// members since the last update
+    pub(crate) fn take_trap_checks(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.trap_checks)
+    }
+
     /// whether the pc has current visibility to the specified coordinations
     /// No bounds checking is done on the `x` and `y` arguments
     pub fn is_pc_visible(&self, x: i32, y: i32) -> bool {
@@ -1038,7 +1243,11 @@ impl AreaState {
         let old_surfaces = self.clear_entity_points(&entity.borrow(), old_x, old_y);
         let new_surfaces = self.add_entity_points(&entity.borrow());
 
+        let old_threateners = entity.borrow().actor.p_stats().threatened_by().to_vec();
         self.compute_threatened(entity, mgr, false);
+        let from = Point::new(old_x, old_y);
+        queue_left_threat_reactions(entity, &old_threateners, from, mgr);
+        queue_entered_threat_reactions(entity, &old_threateners, mgr);
         // remove from surfaces in old but not in new
         for surface in old_surfaces.difference(&new_surfaces) {
             mgr.remove_from_surface(entity_index, *surface);
@@ -1061,14 +1270,25 @@ impl AreaState {
             self.update_view_visibility();
 
             self.check_trigger_grid(entity);
+            self.check_traps(entity);
         }
 
         mgr.fire_on_moved_next_update(entity_index);
+
+        let rules = Module::rules();
+        let noise = if entity.borrow().actor.stats.hidden {
+            rules.stealth.hidden_movement_noise
+        } else {
+            rules.stealth.movement_noise
+        };
+        mgr.make_noise(entity, noise, self);
         mgr.check_ai_activation(entity, self);
     }
 
     #[must_use]
     fn add_entity_points(&mut self, entity: &EntityState) -> HashSet<usize> {
+        self.surface_layer.mark_changed();
+
         let mut surfaces = HashSet::new();
         for p in entity.location_points() {
             self.add_entity_to_grid(p.x, p.y, entity.index());
@@ -76,10 +76,9 @@ impl PropHandler {
         };
 
         let index = self.add(&prop_data, location, false)?;
-        self.props[index]
-            .as_mut()
-            .unwrap()
-            .load_interactive(data.interactive)?;
+        let prop = self.props[index].as_mut().unwrap();
+        prop.load_interactive(data.interactive)?;
+        prop.load_lock(data.locked, data.trap_sprung, data.pick_attempts);
 
         self.update_vis_pass_grid(index);
         Ok(())
@@ -102,7 +101,7 @@ impl PropHandler {
         for prop_index in &self.prop_grid[index] {
             use prop_state::Interactive::*;
             match self.props[*prop_index].as_ref().unwrap().interactive {
-                Not | Door { .. } | Hover { .. } => (),
+                Not | Door { .. } | Hover { .. } | Destructible { .. } | Trap { .. } => (),
                 Container { .. } => return Some(*prop_index),
             }
         }
@@ -299,8 +298,18 @@ impl PropHandler {
             return None;
         }
 
-        let index = (x + y * self.area.width) as usize;
-        self.prop_grid[index].first().copied()
+        // undetected traps are never returned.  destroyed props are only
+        // returned if nothing else is present, so that i.e. loot dropped by a
+        // broken crate can still be picked up
+        let grid = &self.prop_grid[(x + y * self.area.width) as usize];
+        let mut visible = grid.iter().filter(|index| !self.get(**index).is_hidden());
+        grid.iter()
+            .find(|index| {
+                let prop = self.get(**index);
+                !prop.is_hidden() && !prop.is_destroyed()
+            })
+            .or_else(|| visible.next())
+            .copied()
     }
 
     pub fn get(&self, index: usize) -> &PropState {
@@ -364,6 +373,18 @@ impl PropHandler {
         true
     }
 
+    // This method must be called by the owning AreaState in order
+    // to compute visibility correctly
+    pub(in crate::area_state) fn remove_hp(&mut self, index: usize, amount: u32) -> bool {
+        if !self.get_mut(index).remove_hp(amount) {
+            return false;
+        }
+
+        self.update_vis_pass_grid(index);
+
+        true
+    }
+
     fn find_index_to_add(&mut self) -> usize {
         for (index, item) in self.props.iter().enumerate() {
             if item.is_none() {
@@ -379,7 +400,7 @@ impl PropHandler {
         let prop = self.props[index].as_mut();
         let state = prop.unwrap();
 
-        if !state.is_door() {
+        if !state.is_door() && !state.is_destructible() {
             return;
         }
 
@@ -389,7 +410,14 @@ impl PropHandler {
         let end_x = start_x + state.prop.size.width;
         let end_y = start_y + state.prop.size.height;
 
-        if state.is_active() {
+        // destructible props block vision and movement until destroyed
+        let open = if state.is_destructible() {
+            state.is_destroyed()
+        } else {
+            state.is_active()
+        };
+
+        if open {
             for y in start_y..end_y {
                 for x in start_x..end_x {
                     let idx = (x + y * width) as usize;
@@ -401,6 +429,11 @@ impl PropHandler {
             ref closed_invis,
             ref closed_impass,
             ..
+        }
+        | Interactive::Destructible {
+            ref closed_invis,
+            ref closed_impass,
+            ..
         } = state.prop.interactive
         {
             for p in closed_invis {
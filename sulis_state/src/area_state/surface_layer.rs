@@ -0,0 +1,263 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::collections::VecDeque;
+use std::io::Error;
+use std::rc::Rc;
+
+use crate::save_state::SurfaceTileSaveState;
+use sulis_core::util::{invalid_data_error, Point};
+use sulis_module::{DamageKind, Module, SurfaceType};
+
+/// A single square covered by a surface type
+pub struct SurfaceTile {
+    pub surface_type: Rc<SurfaceType>,
+
+    /// The round at which this tile is removed, if any
+    pub expires: Option<u32>,
+}
+
+/// The grid of elemental surface types covering the squares of an area.
+/// Surfaces placed on a square, or damage dealt to it, interact with the
+/// existing surface as specified by its `SurfaceType`.
+pub struct SurfaceLayer {
+    width: i32,
+    height: i32,
+    tiles: Vec<Option<SurfaceTile>>,
+    last_round: Option<u32>,
+    changed: bool,
+}
+
+impl SurfaceLayer {
+    pub fn new(width: i32, height: i32) -> SurfaceLayer {
+        let mut tiles = Vec::new();
+        tiles.resize_with((width * height) as usize, || None);
+
+        SurfaceLayer {
+            width,
+            height,
+            tiles,
+            last_round: None,
+            changed: false,
+        }
+    }
+
+    pub fn load(&mut self, tiles: Vec<SurfaceTileSaveState>) -> Result<(), Error> {
+        for data in tiles {
+            let surface_type = match Module::surface_type(&data.surface_type) {
+                None => {
+                    return invalid_data_error(&format!(
+                        "No surface type with ID '{}'",
+                        data.surface_type
+                    ))
+                }
+                Some(surface_type) => surface_type,
+            };
+
+            let p = data.location;
+            if !self.coords_valid(p.x, p.y) {
+                return invalid_data_error(&format!("Invalid surface location {},{}", p.x, p.y));
+            }
+
+            let index = self.index(p);
+            self.tiles[index] = Some(SurfaceTile {
+                surface_type,
+                expires: data.expires,
+            });
+        }
+
+        self.changed = true;
+        Ok(())
+    }
+
+    pub fn get(&self, x: i32, y: i32) -> Option<&SurfaceTile> {
+        if !self.coords_valid(x, y) {
+            return None;
+        }
+
+        self.tiles[self.index(Point::new(x, y))].as_ref()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &SurfaceTile)> {
+        let width = self.width;
+        self.tiles
+            .iter()
+            .enumerate()
+            .filter_map(move |(index, tile)| {
+                let index = index as i32;
+                let p = Point::new(index % width, index / width);
+                tile.as_ref().map(|tile| (p, tile))
+            })
+    }
+
+    /// Places the surface type on each of the points, interacting with any
+    /// surface already present
+    pub(crate) fn add(&mut self, surface_type: &Rc<SurfaceType>, points: &[Point], round: u32) {
+        for p in points {
+            let existing = match self.get(p.x, p.y) {
+                None => {
+                    self.set(*p, Some(Rc::clone(surface_type)), round);
+                    continue;
+                }
+                Some(tile) => Rc::clone(&tile.surface_type),
+            };
+
+            match existing.on_surface.get(&surface_type.id) {
+                None => self.set(*p, Some(Rc::clone(surface_type)), round),
+                Some(result) => self.interact(*p, &existing, result, round),
+            }
+        }
+    }
+
+    /// Applies damage of the specified kind to any surfaces on the points
+    pub(crate) fn apply_damage(&mut self, kind: DamageKind, points: &[Point], round: u32) {
+        for p in points {
+            let existing = match self.get(p.x, p.y) {
+                None => continue,
+                Some(tile) => Rc::clone(&tile.surface_type),
+            };
+
+            if let Some(result) = existing.on_damage.get(&kind) {
+                self.interact(*p, &existing, result, round);
+            }
+        }
+    }
+
+    pub(crate) fn remove(&mut self, points: &[Point]) {
+        for p in points {
+            if self.get(p.x, p.y).is_some() {
+                self.set(*p, None, 0);
+            }
+        }
+    }
+
+    /// Removes any expired surfaces.  Returns true if this is the first
+    /// update in a new round.
+    pub(crate) fn update(&mut self, round: u32) -> bool {
+        if self.last_round == Some(round) {
+            return false;
+        }
+
+        for tile in self.tiles.iter_mut() {
+            let expired = match tile {
+                Some(SurfaceTile {
+                    expires: Some(expires),
+                    ..
+                }) => *expires <= round,
+                _ => false,
+            };
+
+            if expired {
+                *tile = None;
+                self.changed = true;
+            }
+        }
+
+        let new_round = self.last_round.is_some();
+        self.last_round = Some(round);
+        new_round
+    }
+
+    /// Flags that the surfaces under entities must be recomputed
+    pub(crate) fn mark_changed(&mut self) {
+        self.changed = true;
+    }
+
+    pub(crate) fn take_changed(&mut self) -> bool {
+        let changed = self.changed;
+        self.changed = false;
+        changed
+    }
+
+    fn interact(
+        &mut self,
+        start: Point,
+        existing: &Rc<SurfaceType>,
+        result: &Option<String>,
+        round: u32,
+    ) {
+        let result = match result {
+            None => None,
+            Some(id) => match Module::surface_type(id) {
+                None => {
+                    warn!("Invalid surface type '{}' in interaction", id);
+                    return;
+                }
+                Some(surface_type) => Some(surface_type),
+            },
+        };
+
+        let points = self.connected_points(start, &existing.id, existing.spread);
+
+        for p in points {
+            self.set(p, result.clone(), round);
+        }
+    }
+
+    /// Finds the squares of the surface `id` connected to `start` and no more
+    /// than `max_dist` steps away from it, including `start` itself
+    fn connected_points(&self, start: Point, id: &str, max_dist: u32) -> Vec<Point> {
+        let mut visited = vec![false; self.tiles.len()];
+        let mut points = Vec::new();
+        let mut queue = VecDeque::new();
+        visited[self.index(start)] = true;
+        queue.push_back((start, 0));
+
+        while let Some((p, dist)) = queue.pop_front() {
+            points.push(p);
+            if dist >= max_dist {
+                continue;
+            }
+
+            for (x, y) in [
+                (p.x - 1, p.y),
+                (p.x + 1, p.y),
+                (p.x, p.y - 1),
+                (p.x, p.y + 1),
+            ] {
+                let matches = match self.get(x, y) {
+                    None => false,
+                    Some(tile) => tile.surface_type.id == id,
+                };
+
+                let next = Point::new(x, y);
+                if matches && !visited[self.index(next)] {
+                    visited[self.index(next)] = true;
+                    queue.push_back((next, dist + 1));
+                }
+            }
+        }
+
+        points
+    }
+
+    fn set(&mut self, p: Point, surface_type: Option<Rc<SurfaceType>>, round: u32) {
+        let index = self.index(p);
+        self.tiles[index] = surface_type.map(|surface_type| SurfaceTile {
+            expires: surface_type.duration.map(|duration| round + duration),
+            surface_type,
+        });
+        self.changed = true;
+    }
+
+    fn coords_valid(&self, x: i32, y: i32) -> bool {
+        x >= 0 && y >= 0 && x < self.width && y < self.height
+    }
+
+    fn index(&self, p: Point) -> usize {
+        (p.x + p.y * self.width) as usize
+    }
+}
@@ -0,0 +1,184 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use serde::{Deserialize, Serialize};
+
+use crate::{center, dist, EntityState};
+use sulis_core::util::{gen_rand, Point};
+use sulis_module::Module;
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum AwarenessLevel {
+    #[default]
+    Unaware,
+    Suspicious,
+    Alerted,
+}
+
+impl AwarenessLevel {
+    pub fn to_str(self) -> &'static str {
+        match self {
+            AwarenessLevel::Unaware => "unaware",
+            AwarenessLevel::Suspicious => "suspicious",
+            AwarenessLevel::Alerted => "alerted",
+        }
+    }
+}
+
+/// How aware an AI entity is of hostiles it cannot (yet) see.  Points
+/// are built up by detection rolls against hidden entities and by noise,
+/// and slowly decay while the entity is not alerted.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Awareness {
+    level: AwarenessLevel,
+    points: u32,
+    last_known_pos: Option<Point>,
+    facing: Option<(f32, f32)>,
+}
+
+impl Awareness {
+    pub fn level(&self) -> AwarenessLevel {
+        self.level
+    }
+
+    pub fn last_known_pos(&self) -> Option<Point> {
+        self.last_known_pos
+    }
+
+    pub fn is_alerted(&self) -> bool {
+        self.level == AwarenessLevel::Alerted
+    }
+
+    pub(crate) fn set_facing(&mut self, x: i32, y: i32) {
+        if x == 0 && y == 0 {
+            return;
+        }
+
+        let len = ((x * x + y * y) as f32).sqrt();
+        self.facing = Some((x as f32 / len, y as f32 / len));
+    }
+
+    /// Returns true if the `target` lies within the vision cone of the
+    /// `parent` owning this awareness.  Entities that have never moved
+    /// have no facing and see in all directions.
+    pub(crate) fn in_vision_cone(&self, parent: &EntityState, target: &EntityState) -> bool {
+        let rules = Module::rules();
+        if dist(parent, target) <= rules.stealth.peripheral_dist {
+            return true;
+        }
+
+        let (fx, fy) = match self.facing {
+            None => return true,
+            Some(facing) => facing,
+        };
+
+        let (px, py) = center(parent);
+        let (tx, ty) = center(target);
+        let (dx, dy) = (tx - px, ty - py);
+        let len = dx.hypot(dy);
+        if len == 0.0 {
+            return true;
+        }
+
+        let cos_angle = ((fx * dx + fy * dy) / len).clamp(-1.0, 1.0);
+        let half_cone = rules.stealth.vision_cone_angle / 2.0;
+        cos_angle.acos().to_degrees() <= half_cone
+    }
+
+    /// Adds the specified awareness points, noting the position they came
+    /// from.  Returns true if the entity is now alerted.
+    pub(crate) fn add_points(&mut self, points: u32, pos: Point) -> bool {
+        if points == 0 {
+            return false;
+        }
+        if self.is_alerted() {
+            self.last_known_pos = Some(pos);
+            return true;
+        }
+
+        let rules = Module::rules();
+        self.points += points;
+        self.last_known_pos = Some(pos);
+        if self.points >= rules.stealth.alerted_threshold {
+            self.level = AwarenessLevel::Alerted;
+            true
+        } else {
+            if self.points >= rules.stealth.suspicious_threshold {
+                self.level = AwarenessLevel::Suspicious;
+            }
+            false
+        }
+    }
+
+    /// Makes the entity suspicious of the specified position, if it is
+    /// not already alerted.  Returns true if the awareness changed.
+    pub(crate) fn hear(&mut self, pos: Point) -> bool {
+        if self.is_alerted() || self.last_known_pos == Some(pos) {
+            return false;
+        }
+
+        let threshold = Module::rules().stealth.suspicious_threshold;
+        self.level = AwarenessLevel::Suspicious;
+        self.points = self.points.max(threshold);
+        self.last_known_pos = Some(pos);
+        true
+    }
+
+    pub(crate) fn set_alerted(&mut self) {
+        self.level = AwarenessLevel::Alerted;
+        self.points = self.points.max(Module::rules().stealth.alerted_threshold);
+    }
+
+    /// Called when combat ends; the entity stays wary but is no longer alerted.
+    pub(crate) fn calm(&mut self) {
+        if !self.is_alerted() {
+            return;
+        }
+
+        self.level = AwarenessLevel::Suspicious;
+        self.points = Module::rules().stealth.suspicious_threshold;
+    }
+
+    pub(crate) fn decay(&mut self) {
+        if self.is_alerted() || self.points == 0 {
+            return;
+        }
+
+        let rules = Module::rules();
+        self.points = self.points.saturating_sub(rules.stealth.awareness_decay);
+        if self.points < rules.stealth.suspicious_threshold {
+            self.level = AwarenessLevel::Unaware;
+            self.last_known_pos = None;
+        }
+    }
+}
+
+/// Rolls the `observer`'s detection against the `target`'s stealth,
+/// returning the number of awareness points the observer gains.
+pub(crate) fn detection_roll(observer: &EntityState, target: &EntityState) -> u32 {
+    let rules = Module::rules();
+    let penalty = (dist(observer, target) * rules.stealth.detection_dist_penalty) as i32;
+    let roll = gen_rand(1, 101);
+    let result = roll + observer.actor.stats.detection - target.actor.stats.stealth - penalty;
+
+    debug!(
+        "Detection roll of {} by '{}' against '{}': {}",
+        roll, observer.actor.actor.name, target.actor.actor.name, result
+    );
+
+    (result - 50).max(0) as u32
+}
@@ -0,0 +1,117 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2019 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use serde::{Deserialize, Serialize};
+
+use sulis_module::{condition, Module, OnTrigger};
+
+use crate::{EntityState, GameState, TriggerContext};
+
+/// The banters that have already been started, and the time the most
+/// recent one started at.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct BanterState {
+    #[serde(default)]
+    fired: Vec<String>,
+
+    #[serde(default)]
+    last_millis: usize,
+}
+
+impl BanterState {
+    pub fn has_fired(&self, id: &str) -> bool {
+        self.fired.iter().any(|fired| fired == id)
+    }
+
+    pub(crate) fn fire(&mut self, id: &str, millis: usize) {
+        if !self.has_fired(id) {
+            self.fired.push(id.to_string());
+        }
+        self.last_millis = millis;
+    }
+}
+
+/// Starts the first banter, in order of ID, that is ready to go.  Banters
+/// are only started out of combat and while no modal window is open.
+pub(crate) fn check(state: &BanterState) {
+    if GameState::is_combat_active() || GameState::is_modal_locked() {
+        return;
+    }
+
+    let millis = GameState::turn_manager().borrow().total_elapsed_millis();
+    let area_id = GameState::area_state().borrow().area.area.id.to_string();
+    let rules = Module::rules();
+    let party = GameState::party();
+
+    let mut banters = Module::all_banters();
+    banters.sort_by(|a, b| a.id.cmp(&b.id));
+
+    for banter in banters {
+        if !banter.repeatable && state.has_fired(&banter.id) {
+            continue;
+        }
+
+        if !banter.is_valid_area(&area_id) {
+            continue;
+        }
+
+        if millis < state.last_millis + rules.compute_millis(banter.delay) {
+            continue;
+        }
+
+        let speakers: Option<Vec<_>> = banter
+            .speakers
+            .iter()
+            .map(|id| find_party_member(&party, id))
+            .collect();
+        let target = match speakers {
+            None => continue,
+            Some(speakers) => Rc::clone(&speakers[0]),
+        };
+
+        let pc = GameState::player();
+        let ctx = TriggerContext {
+            pc: &pc,
+            target: &target,
+        };
+        if !condition::triggers_match(&banter.conditions, &ctx) {
+            continue;
+        }
+
+        info!("Starting banter '{}'", banter.id);
+        GameState::fire_banter(&banter.id, millis);
+        let convo = OnTrigger::StartConversation(banter.conversation.id.to_string());
+        GameState::add_ui_callback(vec![convo], &pc, &target);
+        return;
+    }
+}
+
+fn find_party_member(
+    party: &[Rc<RefCell<EntityState>>],
+    id: &str,
+) -> Option<Rc<RefCell<EntityState>>> {
+    party
+        .iter()
+        .find(|member| {
+            let member = member.borrow();
+            member.actor.actor.id == id && !member.actor.is_dead()
+        })
+        .cloned()
+}
@@ -0,0 +1,86 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2019 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use sulis_core::util::{gen_rand, ExtInt};
+use sulis_module::conversation::{CheckKind, ResponseCheck};
+use sulis_module::Module;
+
+use crate::area_feedback_text::ColorKind;
+use crate::{AreaFeedbackText, CombatLogKind, EntityState, GameState};
+
+/// Returns the value of `entity` that is tested by a check of the
+/// specified kind, before any random roll is added
+pub fn check_value(entity: &EntityState, kind: &CheckKind) -> i32 {
+    match kind {
+        CheckKind::Attribute(attr) => entity.actor.stats.attributes.get(*attr) as i32,
+        CheckKind::ClassLevel(id) => match Module::class(id) {
+            None => 0,
+            Some(class) => entity.actor.actor.levels(&class) as i32,
+        },
+        CheckKind::ClassStat(id) => match entity.actor.current_class_stat(id) {
+            ExtInt::Int(value) => value as i32,
+            ExtInt::Infinity => i32::MAX,
+        },
+    }
+}
+
+/// Performs the `check` for `entity`, logging the result and showing
+/// feedback text.  Returns true if the check was passed
+pub fn roll_check(entity: &Rc<RefCell<EntityState>>, check: &ResponseCheck) -> bool {
+    let value = check_value(&entity.borrow(), &check.kind);
+    let roll = match check.roll {
+        None | Some(0) => 0,
+        Some(max) => gen_rand(1, max as i32 + 1),
+    };
+    let success = value.saturating_add(roll) >= check.difficulty;
+
+    let result = if success { "passes" } else { "fails" };
+    let text = if check.roll.is_some() {
+        format!(
+            "{} {} {} check: {} + {} vs {}",
+            entity.borrow().actor.actor.name,
+            result,
+            check.label(),
+            roll,
+            value,
+            check.difficulty,
+        )
+    } else {
+        format!(
+            "{} {} {} check: {} vs {}",
+            entity.borrow().actor.actor.name,
+            result,
+            check.label(),
+            value,
+            check.difficulty,
+        )
+    };
+    GameState::add_combat_log_entry(CombatLogKind::Check, text);
+
+    let area_state = GameState::area_state();
+    let mut feedback = AreaFeedbackText::with_target(&entity.borrow(), &area_state.borrow());
+    if success {
+        feedback.add_entry("Success".to_string(), ColorKind::Info);
+    } else {
+        feedback.add_entry("Failure".to_string(), ColorKind::Miss);
+    }
+    area_state.borrow_mut().add_feedback_text(feedback);
+
+    success
+}
@@ -0,0 +1,208 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::collections::VecDeque;
+use std::fs;
+use std::io::{Error, Write};
+use std::path::PathBuf;
+
+use chrono::prelude::*;
+
+use crate::{ChangeListenerList, EntityState};
+use sulis_core::config;
+use sulis_module::{AttackRoll, DamageRoll, HitFlags};
+
+const MAX_ENTRIES: usize = 1000;
+
+/// The categories of combat log entries, used for filtering
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CombatLogKind {
+    Attack,
+    Damage,
+    Effect,
+    Check,
+}
+
+impl CombatLogKind {
+    pub fn iter() -> impl Iterator<Item = &'static CombatLogKind> {
+        [
+            CombatLogKind::Attack,
+            CombatLogKind::Damage,
+            CombatLogKind::Effect,
+            CombatLogKind::Check,
+        ]
+        .iter()
+    }
+
+    pub fn to_str(self) -> &'static str {
+        match self {
+            CombatLogKind::Attack => "attack",
+            CombatLogKind::Damage => "damage",
+            CombatLogKind::Effect => "effect",
+            CombatLogKind::Check => "check",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CombatLogEntry {
+    pub kind: CombatLogKind,
+    pub round: u32,
+    pub text: String,
+}
+
+/// A record of the most recent attack rolls, damage, and effects, for
+/// display to the player
+#[derive(Default)]
+pub struct CombatLog {
+    entries: VecDeque<CombatLogEntry>,
+    removed: usize,
+    pub listeners: ChangeListenerList<CombatLog>,
+}
+
+impl CombatLog {
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.removed = 0;
+        self.listeners = ChangeListenerList::default();
+    }
+
+    pub fn add(&mut self, kind: CombatLogKind, round: u32, text: String) {
+        if self.entries.len() >= MAX_ENTRIES {
+            self.entries.pop_front();
+            self.removed += 1;
+        }
+
+        self.entries.push_back(CombatLogEntry { kind, round, text });
+        self.listeners.notify(self);
+    }
+
+    /// Iterates over all entries, oldest first
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &CombatLogEntry> {
+        self.entries.iter()
+    }
+
+    /// Iterates over the entries from the specified index onwards, oldest
+    /// first, along with the index of each entry.  Entry indices keep
+    /// counting up as old entries are dropped from the log.
+    pub fn iter_from(&self, index: usize) -> impl Iterator<Item = (usize, &CombatLogEntry)> {
+        let skip = index.saturating_sub(self.removed);
+        let removed = self.removed;
+        self.entries
+            .iter()
+            .enumerate()
+            .skip(skip)
+            .map(move |(i, entry)| (removed + i, entry))
+    }
+
+    /// The index of the oldest entry still in the log
+    pub fn first_index(&self) -> usize {
+        self.removed
+    }
+
+    /// Writes all entries of the specified `kinds` to a new text file in the
+    /// user directory, returning the path of the file.
+    pub fn export(&self, kinds: &[CombatLogKind]) -> Result<PathBuf, Error> {
+        let mut path = config::USER_DIR.clone();
+        path.push("combat_log");
+        if !path.is_dir() {
+            fs::create_dir_all(path.clone())?;
+        }
+
+        let utc = Utc::now();
+        path.push(format!("combat_log_{}.txt", utc.format("%Y%m%d-%H%M%S")));
+
+        let mut file = fs::File::create(&path)?;
+        for entry in self.entries.iter() {
+            if !kinds.contains(&entry.kind) {
+                continue;
+            }
+
+            writeln!(file, "[Round {}] {}", entry.round, entry.text)?;
+        }
+
+        info!("Exported combat log to {:?}", path);
+        Ok(path)
+    }
+}
+
+pub(crate) fn attack_text(
+    parent: &EntityState,
+    target: &EntityState,
+    roll: &AttackRoll,
+    flags: HitFlags,
+) -> String {
+    let mut text = format!(
+        "{} attacks {}: {} + {} accuracy vs {} defense = {} (graze > {}, hit > {})",
+        parent.actor.actor.name,
+        target.actor.actor.name,
+        roll.roll,
+        roll.accuracy,
+        roll.defense,
+        roll.result(),
+        roll.graze_threshold,
+        roll.hit_threshold,
+    );
+
+    if let Some(crit_roll) = roll.crit_roll {
+        text.push_str(&format!(", crit roll {crit_roll}"));
+    }
+
+    let mut flag_names = Vec::new();
+    if flags.flanking {
+        flag_names.push("flanking");
+    }
+    if flags.sneak_attack {
+        flag_names.push("sneak attack");
+    }
+    if !flag_names.is_empty() {
+        text.push_str(&format!(" ({})", flag_names.join(", ")));
+    }
+
+    text.push_str(&format!(": {:?}", roll.kind));
+    text
+}
+
+pub(crate) fn concealment_text(
+    parent: &EntityState,
+    target: &EntityState,
+    concealment: i32,
+) -> String {
+    format!(
+        "{} attacks {}: Miss (concealment {}%)",
+        parent.actor.actor.name, target.actor.actor.name, concealment
+    )
+}
+
+pub(crate) fn damage_text(target_name: &str, rolls: &[DamageRoll]) -> String {
+    let total: u32 = rolls.iter().map(|roll| roll.amount).sum();
+    let parts: Vec<_> = rolls
+        .iter()
+        .map(|roll| {
+            format!(
+                "{} {} x{:.2}, {}% resisted, {:.1} armor = {}",
+                roll.kind, roll.rolled, roll.multiplier, roll.resistance, roll.armor, roll.amount
+            )
+        })
+        .collect();
+
+    format!(
+        "{} takes {} damage ({})",
+        target_name,
+        total,
+        parts.join("; ")
+    )
+}
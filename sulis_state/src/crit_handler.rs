@@ -0,0 +1,133 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2019 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use sulis_core::util::ExtInt;
+use sulis_module::crit_table::CritOutcome;
+use sulis_module::{
+    Attack, CritTableKind, DamageList, HitFlags, HitKind, Module, Slot, ROUND_TIME_MILLIS,
+};
+
+use crate::area_feedback_text::ColorKind;
+use crate::{AreaFeedbackText, CombatLogKind, Effect, EntityState, GameState, ScriptCallback};
+
+/// Rolls on the crit table of the specified kind matching `attack`, if one
+/// exists, and applies the outcomes of the rolled entry to `target`.  For
+/// fumbles, `target` is the attacker.
+pub(crate) fn roll_table(
+    kind: CritTableKind,
+    attack: &Attack,
+    parent: &Rc<RefCell<EntityState>>,
+    target: &Rc<RefCell<EntityState>>,
+) {
+    let table = match Module::crit_table_for(kind, attack.weapon_kind, attack.damage_kind()) {
+        None => return,
+        Some(table) => table,
+    };
+
+    let entry = table.roll();
+    debug!("Rolled '{}' on crit table '{}'", entry.name, table.id);
+
+    let text = match kind {
+        CritTableKind::Crit => format!(
+            "{} suffers a critical: {}",
+            target.borrow().actor.actor.name,
+            entry.name
+        ),
+        CritTableKind::Fumble => {
+            format!(
+                "{} fumbles: {}",
+                target.borrow().actor.actor.name,
+                entry.name
+            )
+        }
+    };
+    GameState::add_combat_log_entry(CombatLogKind::Attack, text);
+
+    let area_state = GameState::area_state();
+    {
+        let mut area_state = area_state.borrow_mut();
+        let mut feedback = AreaFeedbackText::with_target(&target.borrow(), &area_state);
+        feedback.add_entry(entry.name.to_string(), ColorKind::Info);
+        area_state.add_feedback_text(feedback);
+    }
+
+    for outcome in entry.outcomes.iter() {
+        match outcome {
+            CritOutcome::Damage(damage) => {
+                let rules = Module::rules();
+                let amount = {
+                    let stats = &target.borrow().actor.stats;
+                    let damage = DamageList::from(*damage);
+                    rules.roll_damage(&damage, &stats.armor, &stats.resistance, 1.0)
+                };
+
+                if !amount.is_empty() {
+                    EntityState::remove_hp(target, parent, HitKind::Auto, amount.clone());
+                }
+
+                let feedback = AreaFeedbackText::with_damage(
+                    &target.borrow(),
+                    &area_state.borrow(),
+                    HitKind::Auto,
+                    HitFlags::default(),
+                    &amount,
+                );
+                area_state.borrow_mut().add_feedback_text(feedback);
+            }
+            CritOutcome::Effect { duration, bonuses } => {
+                let duration = ExtInt::Int(*duration * ROUND_TIME_MILLIS);
+                let effect = Effect::new(&entry.name, "crit", duration, bonuses.clone(), None);
+
+                let mgr = GameState::turn_manager();
+                mgr.borrow_mut()
+                    .add_effect(effect, target, Vec::new(), Vec::new());
+            }
+            CritOutcome::DropWeapon => drop_weapon(target),
+            CritOutcome::Trigger(on_trigger) => {
+                GameState::add_ui_callback(on_trigger.clone(), parent, target);
+            }
+        }
+    }
+}
+
+fn drop_weapon(entity: &Rc<RefCell<EntityState>>) {
+    let item = match entity.borrow_mut().actor.unequip(Slot::HeldMain) {
+        None => return,
+        Some(item) => item,
+    };
+
+    info!(
+        "'{}' dropped '{}'",
+        entity.borrow().actor.actor.name,
+        item.item.id
+    );
+
+    let p = entity.borrow().location.to_point();
+    {
+        let area_state = GameState::area_state();
+        let mut area_state = area_state.borrow_mut();
+        if let Some(index) = area_state.props_mut().check_or_create_container(p.x, p.y) {
+            area_state.props_mut().get_mut(index).add_item(item);
+        }
+    }
+
+    let mgr = GameState::turn_manager();
+    let cbs = entity.borrow().callbacks(&mgr.borrow());
+    cbs.iter().for_each(|cb| cb.on_held_changed());
+}
@@ -17,13 +17,65 @@
 use std::cell::RefCell;
 use std::rc::Rc;
 
+use crate::{
+    center, combat_log, crit_handler, is_threat, ActorState, CombatLogKind, EntityState, GameState,
+    ReactionKind,
+};
 use sulis_core::io::Audio;
-use crate::{center, is_threat, ActorState, EntityState, GameState};
-use sulis_module::{AccuracyKind, Attack, AttackKind, DamageKind, HitFlags, HitKind, Module,
-    OnTrigger};
+use sulis_module::{
+    AccuracyKind, Attack, AttackKind, CritTableKind, DamageKind, HitFlags, HitKind, Module,
+    OnTrigger,
+};
 
 fn is_sneak_attack(parent: &EntityState, target: &EntityState) -> bool {
-    parent.actor.stats.hidden && !target.actor.stats.sneak_attack_immunity
+    if target.actor.stats.sneak_attack_immunity {
+        return false;
+    }
+
+    // hostile AI that has not yet noticed anyone is caught off guard
+    let unaware = !target.is_party_member()
+        && target.is_hostile(parent)
+        && !target.is_ai_active()
+        && !target.awareness().is_alerted();
+
+    parent.actor.stats.hidden || unaware
+}
+
+fn queue_ally_attacked_reactions(
+    parent: &Rc<RefCell<EntityState>>,
+    target: &Rc<RefCell<EntityState>>,
+) {
+    let mgr = GameState::turn_manager();
+    let mut mgr = mgr.borrow_mut();
+    let parent_index = parent.borrow().index();
+    let threateners = parent.borrow().actor.p_stats().threatened_by().to_vec();
+    for reactor_index in threateners {
+        if reactor_index == target.borrow().index() {
+            continue;
+        }
+
+        let reactor = match mgr.entity_checked(reactor_index) {
+            None => continue,
+            Some(reactor) => reactor,
+        };
+
+        if !reactor.borrow().is_friendly(&target.borrow()) {
+            continue;
+        }
+
+        mgr.queue_reaction(ReactionKind::AllyAttacked, reactor_index, parent_index);
+    }
+}
+
+fn make_combat_noise(parent: &Rc<RefCell<EntityState>>) {
+    let area = match GameState::get_area_state(&parent.borrow().location.area_id) {
+        None => return,
+        Some(area) => area,
+    };
+    let radius = Module::rules().stealth.combat_noise;
+
+    let mgr = GameState::turn_manager();
+    mgr.borrow_mut().make_noise(parent, radius, &area.borrow());
 }
 
 fn is_flanking(parent: &EntityState, target: &EntityState) -> bool {
@@ -120,6 +172,8 @@ pub fn weapon_attack(
         GameState::add_ui_callback(vec![OnTrigger::ScreenShake], parent, target);
     }
 
+    make_combat_noise(parent);
+    queue_ally_attacked_reactions(parent, target);
     ActorState::check_death(parent, target);
     result
 }
@@ -145,6 +199,8 @@ pub fn attack(
     let (hit_kind, hit_flags, damage) =
         attack_internal(parent, target, attack, is_flanking, is_sneak_attack);
 
+    make_combat_noise(parent);
+    queue_ally_attacked_reactions(parent, target);
     ActorState::check_death(parent, target);
 
     (hit_kind, hit_flags, damage)
@@ -166,6 +222,8 @@ fn attack_internal(
 
     if !rules.concealment_roll(concealment) {
         debug!("Concealment miss");
+        let text = combat_log::concealment_text(&parent.borrow(), &target.borrow(), concealment);
+        GameState::add_combat_log_entry(CombatLogKind::Attack, text);
         return (
             HitKind::Miss,
             HitFlags {
@@ -176,7 +234,7 @@ fn attack_internal(
         );
     }
 
-    let (accuracy_kind, defense) = {
+    let (accuracy_kind, mut defense) = {
         let target_stats = &target.borrow().actor.stats;
         match attack.kind {
             AttackKind::Fortitude { accuracy } => (accuracy, target_stats.fortitude),
@@ -201,35 +259,70 @@ fn attack_internal(
         attack.bonuses.spell_accuracy += rules.hidden_accuracy_bonus;
     }
 
+    if attack.is_ranged() {
+        let area_state = GameState::area_state();
+        let modifiers = area_state
+            .borrow()
+            .ranged_modifiers(&parent.borrow(), &target.borrow());
+        debug!("Ranged modifiers: {:?}", modifiers);
+        attack.bonuses.ranged_accuracy += modifiers.accuracy;
+        defense += modifiers.defense();
+    }
+
     let hit_flags = HitFlags {
         flanking,
         sneak_attack,
         concealment: false,
     };
 
-    let (hit_kind, damage_multiplier) = {
+    let (hit_kind, damage_multiplier, fumble) = {
         let parent_stats = &parent.borrow().actor.stats;
-        let hit_kind =
-            parent_stats.attack_roll(accuracy_kind, crit_immunity, defense, &attack.bonuses);
+        let roll = parent_stats.attack_roll_detailed(
+            accuracy_kind,
+            crit_immunity,
+            defense,
+            &attack.bonuses,
+        );
+        let text = combat_log::attack_text(&parent.borrow(), &target.borrow(), &roll, hit_flags);
+        GameState::add_combat_log_entry(CombatLogKind::Attack, text);
+
+        let hit_kind = roll.kind;
         let damage_multiplier = match hit_kind {
-            HitKind::Miss => {
-                debug!("Miss");
-                return (HitKind::Miss, hit_flags, Vec::new());
-            }
+            HitKind::Miss => 0.0,
             HitKind::Graze => parent_stats.graze_multiplier + attack.bonuses.graze_multiplier,
             HitKind::Hit => parent_stats.hit_multiplier + attack.bonuses.hit_multiplier,
             HitKind::Crit => parent_stats.crit_multiplier + attack.bonuses.crit_multiplier,
             HitKind::Auto => panic!(),
         };
-        (hit_kind, damage_multiplier)
+        let fumble = hit_kind == HitKind::Miss && roll.roll <= rules.fumble_chance as i32;
+        (hit_kind, damage_multiplier, fumble)
     };
 
-    let damage = {
+    if hit_kind == HitKind::Miss {
+        debug!("Miss");
+        if fumble {
+            crit_handler::roll_table(CritTableKind::Fumble, attack, parent, parent);
+        }
+        return (HitKind::Miss, hit_flags, Vec::new());
+    }
+
+    let rolls = {
         let target = &target.borrow().actor.stats;
         let damage = &attack.damage;
-        rules.roll_damage(damage, &target.armor, &target.resistance, damage_multiplier)
+        rules.roll_damage_detailed(damage, &target.armor, &target.resistance, damage_multiplier)
     };
 
+    if !rolls.is_empty() {
+        let text = combat_log::damage_text(&target.borrow().actor.actor.name, &rolls);
+        GameState::add_combat_log_entry(CombatLogKind::Damage, text);
+    }
+
+    let damage: Vec<_> = rolls
+        .into_iter()
+        .filter(|roll| roll.amount > 0)
+        .map(|roll| (roll.kind, roll.amount))
+        .collect();
+
     debug!("{:?}. {:?} damage", hit_kind, damage);
 
     if !damage.is_empty() {
@@ -241,5 +334,9 @@ fn attack_internal(
         EntityState::remove_hp(target, parent, hit_kind, damage.clone());
     }
 
+    if hit_kind == HitKind::Crit && !crit_immunity && target.borrow().actor.hp() > 0 {
+        crit_handler::roll_table(CritTableKind::Crit, attack, parent, target);
+    }
+
     (hit_kind, hit_flags, damage)
 }
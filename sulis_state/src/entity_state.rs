@@ -27,8 +27,8 @@ use crate::save_state::EntitySaveState;
 use crate::script::{self, CallbackData, ScriptEntitySet};
 use crate::{
     entity_attack_handler::weapon_attack, entity_texture_cache::Slot, is_within_attack_dist,
-    ActorState, AreaState, ChangeListenerList, EntityTextureCache, EntityTextureSlot, GameState,
-    Location, ScriptCallback, TurnManager,
+    quest_handler, ActorState, AreaState, Awareness, ChangeListenerList, EntityTextureCache,
+    EntityTextureSlot, GameState, Location, ScriptCallback, TurnManager,
 };
 use sulis_core::io::GraphicsRenderer;
 use sulis_core::ui::{color, Color};
@@ -59,6 +59,7 @@ pub struct EntityState {
     texture_cache_slot: Option<EntityTextureSlot>,
 
     custom_flags: HashMap<String, String>,
+    awareness: Awareness,
 
     index: usize,      // index in vec of the owning manager
     unique_id: String, // assigned when setting the index and persisted on save
@@ -123,6 +124,7 @@ impl EntityState {
             marked_for_removal: false,
             texture_cache_slot: None,
             custom_flags: save.custom_flags,
+            awareness: save.awareness,
             collapsed_groups: save.collapsed_groups,
         })
     }
@@ -171,6 +173,7 @@ impl EntityState {
             ai_state,
             texture_cache_slot: None,
             custom_flags: HashMap::new(),
+            awareness: Awareness::default(),
             collapsed_groups: Vec::new(),
         }
     }
@@ -213,6 +216,15 @@ impl EntityState {
                     ai::FuncKind::OnRoundElapsed => {
                         cbs.add_func(script::FuncKind::OnRoundElapsed, func)
                     },
+                    ai::FuncKind::OnTargetLeftThreat => {
+                        cbs.add_func(script::FuncKind::OnTargetLeftThreat, func)
+                    }
+                    ai::FuncKind::OnTargetCastWhileThreatened => {
+                        cbs.add_func(script::FuncKind::OnTargetCastWhileThreatened, func)
+                    }
+                    ai::FuncKind::OnAllyAttacked => {
+                        cbs.add_func(script::FuncKind::OnAllyAttacked, func)
+                    }
                     ai::FuncKind::AiAction => (), // this is handled specially when running the AI
                 }
             }
@@ -311,6 +323,14 @@ impl EntityState {
         }
     }
 
+    pub fn awareness(&self) -> &Awareness {
+        &self.awareness
+    }
+
+    pub(crate) fn awareness_mut(&mut self) -> &mut Awareness {
+        &mut self.awareness
+    }
+
     pub fn show_portrait(&self) -> bool {
         match self.ai_state {
             AIState::Player { show_portrait, .. } => show_portrait,
@@ -479,10 +499,28 @@ impl EntityState {
         entity.borrow().explore_self_location();
     }
 
+    /// Attacks the destructible prop at `index` in the current area.  Props
+    /// are always attacked using a melee style animation, regardless of weapon.
+    pub fn attack_prop(entity: &Rc<RefCell<EntityState>>, index: usize, remove_ap: bool) {
+        let time = Config::animation_base_time_millis();
+        let anim = animation::prop_attack_animation::new(entity, index, time * 5);
+        GameState::add_animation(anim);
+
+        if remove_ap {
+            let attack_ap = entity.borrow().actor.stats.attack_cost;
+            entity.borrow_mut().actor.remove_ap(attack_ap as u32);
+        }
+
+        entity.borrow().explore_self_location();
+    }
+
     pub fn add_xp(&mut self, xp: u32) {
         self.actor.add_xp(xp);
     }
 
+    /// Removes the specified damage from the entity's hit points and applies
+    /// it to any surfaces underneath the entity.  The area state must not be
+    /// borrowed, nor the turn manager mutably borrowed, when calling this.
     pub fn remove_hp(
         entity: &Rc<RefCell<EntityState>>,
         attacker: &Rc<RefCell<EntityState>>,
@@ -490,8 +528,11 @@ impl EntityState {
         damage: Vec<(DamageKind, u32)>,
     ) {
         let hp_amount = damage.iter().map(|(_, amount)| amount).sum();
+        let was_alive = !entity.borrow().actor.is_dead();
         entity.borrow_mut().actor.remove_hp(hp_amount);
 
+        EntityState::damage_surface_types(entity, &damage);
+
         let targets = ScriptEntitySet::from_pair(entity, attacker);
 
         let mgr = GameState::turn_manager();
@@ -508,11 +549,33 @@ impl EntityState {
             );
             let anim = Anim::new_entity_death(entity);
             GameState::add_animation(anim);
+
+            if was_alive {
+                let id = entity.borrow().actor.actor.id.to_string();
+                quest_handler::on_kill(&id);
+            }
         } else {
             GameState::create_damage_animation(entity);
         }
     }
 
+    /// Applies each kind of the specified damage to any surfaces underneath
+    /// the entity, allowing i.e. fire damage to ignite oil
+    fn damage_surface_types(entity: &Rc<RefCell<EntityState>>, damage: &[(DamageKind, u32)]) {
+        let area_state = GameState::area_state();
+        let mut area_state = area_state.borrow_mut();
+        let entity = entity.borrow();
+        if !entity.location.is_in(&area_state) {
+            return;
+        }
+
+        let round = GameState::turn_manager().borrow().current_round();
+        let points: Vec<_> = entity.location_points().collect();
+        for (kind, _) in damage.iter() {
+            area_state.damage_surface_types(*kind, &points, round);
+        }
+    }
+
     pub fn move_to(&mut self, x: i32, y: i32, squares: u32) -> bool {
         trace!("Move to {},{}", x, y);
         if !self.location.coords_valid(x, y) {
@@ -538,6 +601,8 @@ impl EntityState {
             self.actor.remove_ap(ap_cost);
         }
 
+        self.awareness
+            .set_facing(x - self.location.x, y - self.location.y);
         self.location.move_to(x, y);
         self.listeners.notify(self);
         true
@@ -25,19 +25,22 @@ use sulis_core::util::{invalid_data_error, ExtInt, Offset, Point, Scale};
 use sulis_module::on_trigger::QuestEntryState;
 use sulis_module::{
     area::{Destination, PathFinder, Trigger, TriggerKind},
-    Actor, ItemState, Module, OnTrigger, Time, MOVE_TO_THRESHOLD,
+    Actor, Damage, DamageList, HitFlags, HitKind, ItemState, Module, OnTrigger, Time,
+    MOVE_TO_THRESHOLD,
 };
 
 use crate::animation::{particle_generator::Param, Anim, AnimSaveState, AnimState};
 use crate::script::{script_cache, script_callback, Script, ScriptCallback, ScriptEntity};
 use crate::{
-    path_finder, transition_handler, AreaState, ChangeListener, ChangeListenerList, Effect,
-    EntityState, Formation, ItemList, Location, PartyStash, QuestStateSet, SaveState, TurnManager,
-    UICallback, WorldMapState, AI,
+    banter_handler, path_finder, quest_handler, reaction_handler, transition_handler, trap_handler,
+    AreaFeedbackText, AreaState, BanterState, ChangeListener, ChangeListenerList, CombatLog,
+    CombatLogKind, Effect, EntityState, Formation, ItemList, Location, PartyStash, QuestStateSet,
+    SaveState, TurnManager, UICallback, WorldMapState, AI,
 };
 
 thread_local! {
     static TURN_MANAGER: Rc<RefCell<TurnManager>> = Rc::new(RefCell::new(TurnManager::default()));
+    static COMBAT_LOG: Rc<RefCell<CombatLog>> = Rc::new(RefCell::new(CombatLog::default()));
     static STATE: RefCell<Option<GameState>> = const { RefCell::new(None) };
     static AI: RefCell<AI> = const { RefCell::new(AI::new()) };
     static CLEAR_ANIMS: Cell<bool> = const { Cell::new(false) };
@@ -45,6 +48,7 @@ thread_local! {
     static ANIMATIONS: RefCell<AnimState> = const { RefCell::new(AnimState::new()) };
     static ANIMS_TO_ADD: RefCell<Vec<Anim>> = const { RefCell::new(Vec::new()) };
     static COMBAT_INACTIVE_TIME: Cell<u32> = const { Cell::new(0) };
+    static ROUND_CHECKED: Cell<u32> = const { Cell::new(0) };
 }
 
 pub struct GameState {
@@ -52,6 +56,7 @@ pub struct GameState {
     area_state: Rc<RefCell<AreaState>>,
     world_map: WorldMapState,
     quests: QuestStateSet,
+    banters: BanterState,
     selected: Vec<Rc<RefCell<EntityState>>>,
     user_zoom: f32,
     party: Vec<Rc<RefCell<EntityState>>>,
@@ -158,6 +163,27 @@ impl GameState {
                 mgr.borrow_mut().ai_groups.insert(index, value);
             }
 
+            for (key, value) in save_state.manager.readied {
+                let index = match key.parse::<usize>() {
+                    Ok(val) => val,
+                    Err(e) => {
+                        let err = Error::new(ErrorKind::InvalidInput, e);
+                        return Err(err);
+                    }
+                };
+
+                match entities.get(&index) {
+                    None => {
+                        return invalid_data_error(&format!("Invalid readied action index {index}"))
+                    }
+                    // the index has changed with the load
+                    Some(entity) => {
+                        let index = entity.borrow().index();
+                        mgr.borrow_mut().readied.insert(index, value);
+                    }
+                }
+            }
+
             for effect_save in save_state.manager.effects {
                 let old_index = effect_save.index;
                 let new_index = mgr.borrow().get_next_effect_index();
@@ -258,6 +284,7 @@ impl GameState {
                 ui_callbacks: Vec::new(),
                 world_map,
                 quests,
+                banters: save_state.banters,
             })
         };
 
@@ -266,6 +293,9 @@ impl GameState {
             *state.borrow_mut() = Some(game_state);
         });
 
+        // discard entries for effects re-applied while loading
+        COMBAT_LOG.with(|log| log.borrow_mut().clear());
+
         let pc = GameState::player();
         let area_state = GameState::area_state();
         let mut area_state = area_state.borrow_mut();
@@ -285,6 +315,7 @@ impl GameState {
         MODAL_LOCKED.with(|c| c.set(false));
         ANIMS_TO_ADD.with(|anims| anims.borrow_mut().clear());
         AI.with(|ai| *ai.borrow_mut() = AI::new());
+        COMBAT_LOG.with(|log| log.borrow_mut().clear());
 
         TURN_MANAGER.with(|mgr| {
             let rules = Module::rules();
@@ -428,6 +459,7 @@ impl GameState {
             ui_callbacks: Vec::new(),
             world_map: WorldMapState::new(),
             quests: QuestStateSet::default(),
+            banters: BanterState::default(),
         })
     }
 
@@ -458,6 +490,24 @@ impl GameState {
         })
     }
 
+    pub fn banter_state() -> BanterState {
+        STATE.with(|state| {
+            let state = state.borrow();
+            let state = state.as_ref().unwrap();
+
+            state.banters.clone()
+        })
+    }
+
+    pub(crate) fn fire_banter(id: &str, millis: usize) {
+        STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            let state = state.as_mut().unwrap();
+
+            state.banters.fire(id, millis);
+        })
+    }
+
     pub fn quest_state() -> QuestStateSet {
         STATE.with(|state| {
             let state = state.borrow();
@@ -508,6 +558,30 @@ impl GameState {
         })
     }
 
+    pub fn set_quest_entry_progress(quest: &str, entry: &str, progress: u32) {
+        STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            let state = state.as_mut().unwrap();
+            state.quests.set_entry_progress(quest, entry, progress);
+        })
+    }
+
+    pub fn add_journal_note(note: String) {
+        STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            let state = state.as_mut().unwrap();
+            state.quests.add_note(note);
+        })
+    }
+
+    pub fn remove_journal_note(index: usize) {
+        STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            let state = state.as_mut().unwrap();
+            state.quests.remove_note(index);
+        })
+    }
+
     pub fn set_user_zoom(zoom: f32) {
         STATE.with(|state| {
             let mut state = state.borrow_mut();
@@ -525,10 +599,32 @@ impl GameState {
         TURN_MANAGER.with(Rc::clone)
     }
 
+    pub fn combat_log() -> Rc<RefCell<CombatLog>> {
+        COMBAT_LOG.with(Rc::clone)
+    }
+
+    /// Adds an entry to the combat log, stamped with the current round.  This
+    /// must not be called while the turn manager is borrowed.
+    pub fn add_combat_log_entry(kind: CombatLogKind, text: String) {
+        let round = GameState::turn_manager().borrow().current_round();
+        GameState::combat_log().borrow_mut().add(kind, round, text);
+    }
+
     pub fn set_selected_party_member(entity: Rc<RefCell<EntityState>>) {
         GameState::select_party_members(vec![entity]);
     }
 
+    /// Selects the specified party member.  In group initiative mode, this
+    /// also gives the party member the active turn if it has not yet acted
+    /// in the current phase.
+    pub fn activate_party_member(entity: Rc<RefCell<EntityState>>) {
+        let mgr = GameState::turn_manager();
+        let activated = mgr.borrow_mut().activate_in_phase(&entity);
+        if !activated {
+            GameState::set_selected_party_member(entity);
+        }
+    }
+
     pub fn clear_selected_party_member() {
         GameState::select_party_members(Vec::new());
     }
@@ -954,11 +1050,28 @@ impl GameState {
         let cbs = mgr.borrow_mut().update_entity_move_callbacks();
         script_callback::fire_on_moved(cbs);
 
-        {
+        let reactions = mgr.borrow_mut().drain_reactions();
+        reaction_handler::fire_reactions(reactions);
+
+        let searches = mgr.borrow_mut().drain_searches();
+        GameState::search_last_known_positions(searches);
+
+        let (surface_damage, sprung_traps, trap_checks) = {
             let area_state = GameState::area_state();
             let mut area_state = area_state.borrow_mut();
             area_state.update();
+            let surface_damage = area_state.update_surface_types(&mgr.borrow());
+            (
+                surface_damage,
+                area_state.take_sprung_traps(),
+                area_state.take_trap_checks(),
+            )
+        };
+        for text in trap_checks {
+            GameState::add_combat_log_entry(CombatLogKind::Check, text);
         }
+        GameState::apply_surface_damage(surface_damage);
+        trap_handler::spring_traps(sprung_traps);
 
         if GameState::check_clear_anims() {
             ANIMATIONS.with(|a| a.borrow_mut().clear_all_blocking_anims());
@@ -1009,6 +1122,13 @@ impl GameState {
             script_cache::set_report_enabled(true);
         }
 
+        // checks that only need to happen once per round
+        let round = mgr.borrow().current_round();
+        if ROUND_CHECKED.with(|r| r.replace(round)) != round {
+            banter_handler::check(&GameState::banter_state());
+            quest_handler::check_objectives();
+        }
+
         ui_cb
     }
 
@@ -1113,6 +1233,54 @@ impl GameState {
         }
     }
 
+    /// Damages each entity standing in a harmful surface.  The area state must
+    /// not be borrowed when calling this, as `EntityState::remove_hp` also
+    /// applies the damage to the surfaces under the entity.
+    fn apply_surface_damage(surface_damage: Vec<(Rc<RefCell<EntityState>>, Damage)>) {
+        let rules = Module::rules();
+        for (entity, damage) in surface_damage {
+            let damage = {
+                let stats = &entity.borrow().actor.stats;
+                let damage = DamageList::from(damage);
+                rules.roll_damage(&damage, &stats.armor, &stats.resistance, 1.0)
+            };
+
+            if !damage.is_empty() {
+                EntityState::remove_hp(&entity, &entity, HitKind::Auto, damage.clone());
+
+                let area_state = GameState::area_state();
+                let feedback = AreaFeedbackText::with_damage(
+                    &entity.borrow(),
+                    &area_state.borrow(),
+                    HitKind::Auto,
+                    HitFlags::default(),
+                    &damage,
+                );
+                area_state.borrow_mut().add_feedback_text(feedback);
+            }
+        }
+    }
+
+    fn search_last_known_positions(searches: Vec<(Rc<RefCell<EntityState>>, Point)>) {
+        let area_id = GameState::area_state().borrow().area.area.id.to_string();
+        for (entity, pos) in searches {
+            if !entity.borrow().location.is_in_area_id(&area_id) {
+                continue;
+            }
+            if GameState::has_blocking_animations(&entity) {
+                continue;
+            }
+
+            let dest = GameState::get_point_dest(&entity.borrow(), pos.x as f32, pos.y as f32);
+            debug!(
+                "'{}' searching towards {:?}",
+                entity.borrow().actor.actor.name,
+                pos
+            );
+            GameState::move_towards_dest(&entity, &[], dest, None);
+        }
+    }
+
     pub fn get_point_dest(entity: &EntityState, x: f32, y: f32) -> Destination {
         let dist = MOVE_TO_THRESHOLD;
         let w = entity.size.width as f32;
@@ -34,10 +34,23 @@ pub use self::area_feedback_text::AreaFeedbackText;
 pub mod area_state;
 pub use self::area_state::AreaState;
 
+pub mod awareness;
+pub use self::awareness::{Awareness, AwarenessLevel};
+
+pub mod banter_handler;
+pub use self::banter_handler::BanterState;
+
 mod change_listener;
 pub use self::change_listener::ChangeListener;
 pub use self::change_listener::ChangeListenerList;
 
+pub mod check_handler;
+
+pub mod combat_log;
+pub use self::combat_log::{CombatLog, CombatLogEntry, CombatLogKind};
+
+mod crit_handler;
+
 mod distance_finder;
 pub use self::distance_finder::{
     can_attack, center, center_i32, dist, is_threat, is_within, is_within_attack_dist,
@@ -75,8 +88,11 @@ pub use self::item_list::ItemList;
 mod location;
 pub use self::location::Location;
 
+pub mod lock_handler;
+
 mod los_calculator;
 pub use self::los_calculator::calculate_los;
+pub use self::los_calculator::cover_fraction;
 pub use self::los_calculator::has_visibility;
 
 mod merchant_state;
@@ -89,16 +105,23 @@ mod party_bump_handler;
 mod party_stash;
 pub use self::party_stash::PartyStash;
 
+mod prop_damage_handler;
+
 mod prop_state;
 pub use self::prop_state::PropState;
 
 mod p_stats;
 pub use self::p_stats::PStats;
 
+pub mod quest_handler;
+
 pub mod quest_state;
 pub use self::quest_state::QuestState;
 pub use self::quest_state::QuestStateSet;
 
+pub mod reaction_handler;
+pub use self::reaction_handler::{Reaction, ReactionKind, ReadiedAction};
+
 mod range_indicator;
 pub use self::range_indicator::{RangeIndicator, RangeIndicatorHandler, RangeIndicatorImageSet};
 
@@ -114,6 +137,11 @@ pub use self::script::{Script, ScriptCallback, ScriptState};
 
 mod transition_handler;
 
+pub mod trap_handler;
+
+mod trigger_context;
+pub use self::trigger_context::TriggerContext;
+
 mod turn_manager;
 pub(crate) use self::turn_manager::TurnManager;
 
@@ -0,0 +1,151 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2019 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use sulis_core::util::gen_rand;
+
+use crate::area_feedback_text::ColorKind;
+use crate::{trap_handler, AreaFeedbackText, AreaState, CombatLogKind, EntityState, GameState};
+
+/// Attempts to unlock the prop at `index` in the area of `entity`.  The
+/// prop is unlocked if the party carries the key, or if `entity` passes a
+/// mechanics check against the lock difficulty.  Each party member may only
+/// attempt the check once per lock.  A failed check springs the lock's trap,
+/// if any.  Returns true if the prop is now unlocked.
+pub fn try_unlock(entity: &Rc<RefCell<EntityState>>, index: usize) -> bool {
+    let area_state = match GameState::get_area_state(&entity.borrow().location.area_id) {
+        None => return false,
+        Some(area_state) => area_state,
+    };
+
+    let prop = {
+        let area_state = area_state.borrow();
+        if !area_state.props().index_valid(index) {
+            return false;
+        }
+
+        let prop = area_state.props().get(index);
+        if !prop.is_locked() {
+            return true;
+        }
+        Rc::clone(&prop.prop)
+    };
+
+    let lock = match prop.lock {
+        None => return true,
+        Some(ref lock) => lock,
+    };
+
+    if let Some(ref key) = lock.key {
+        let stash = GameState::party_stash();
+        if stash.borrow().has_item(&key.id) {
+            if lock.consume_key {
+                let index = stash
+                    .borrow()
+                    .items()
+                    .iter()
+                    .position(|(_, item)| item.item.id == key.id);
+                if let Some(index) = index {
+                    let _ = stash.borrow_mut().remove_item(index);
+                }
+            }
+
+            info!("Unlocked '{}' with key '{}'", prop.id, key.id);
+            unlock(&area_state, index, "Unlocked");
+            return true;
+        }
+    }
+
+    let difficulty = match lock.difficulty {
+        None => {
+            add_feedback(&area_state, index, "Locked", ColorKind::Miss);
+            return false;
+        }
+        Some(difficulty) => difficulty,
+    };
+
+    if let Some(ref ability) = lock.ability {
+        if !entity.borrow().actor.actor.has_ability(ability) {
+            add_feedback(&area_state, index, "Locked", ColorKind::Miss);
+            return false;
+        }
+    }
+
+    let first_attempt = area_state
+        .borrow_mut()
+        .props_mut()
+        .get_mut(index)
+        .add_pick_attempt(entity.borrow().unique_id());
+    if !first_attempt {
+        add_feedback(&area_state, index, "Already Attempted", ColorKind::Miss);
+        return false;
+    }
+
+    let mechanics = entity.borrow().actor.stats.mechanics;
+    let roll = gen_rand(1, 21);
+    let success = roll + mechanics >= difficulty;
+
+    let text = format!(
+        "{} {} lock on {}: {} + {} vs {}",
+        entity.borrow().actor.actor.name,
+        if success { "picks" } else { "fails to pick" },
+        prop.name,
+        roll,
+        mechanics,
+        difficulty,
+    );
+    GameState::add_combat_log_entry(CombatLogKind::Check, text);
+
+    if success {
+        unlock(&area_state, index, "Lock Picked");
+        return true;
+    }
+
+    add_feedback(&area_state, index, "Failed", ColorKind::Miss);
+
+    if let Some(ref trap) = lock.trap {
+        let sprung = area_state.borrow().props().get(index).is_trap_sprung();
+        if !sprung || trap.fire_more_than_once {
+            area_state
+                .borrow_mut()
+                .props_mut()
+                .get_mut(index)
+                .set_trap_sprung();
+
+            trap_handler::fire_trap(entity, &area_state, trap);
+        }
+    }
+
+    false
+}
+
+fn unlock(area_state: &Rc<RefCell<AreaState>>, index: usize, text: &str) {
+    area_state
+        .borrow_mut()
+        .props_mut()
+        .get_mut(index)
+        .set_locked(false);
+    add_feedback(area_state, index, text, ColorKind::Info);
+}
+
+fn add_feedback(area_state: &Rc<RefCell<AreaState>>, index: usize, text: &str, color: ColorKind) {
+    let mut area_state = area_state.borrow_mut();
+    let mut feedback = AreaFeedbackText::with_prop(area_state.props().get(index), &area_state);
+    feedback.add_entry(text.to_string(), color);
+    area_state.add_feedback_text(feedback);
+}
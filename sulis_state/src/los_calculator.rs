@@ -94,6 +94,54 @@ pub fn has_visibility(
     false
 }
 
+/// Returns the fraction of the squares of `target` that are obstructed from
+/// the center of `entity` by walls, props, or terrain higher than both of
+/// them.  This is used to determine the cover of `target` from ranged attacks.
+pub fn cover_fraction(
+    area: &GeneratedArea,
+    prop_vis_grid: &[bool],
+    prop_pass_grid: &[bool],
+    entity: &EntityState,
+    target: &EntityState,
+) -> f32 {
+    let start_x = entity.location.x + entity.size.width / 2;
+    let start_y = entity.location.y + entity.size.height / 2;
+    let target_x = target.location.x + target.size.width / 2;
+    let target_y = target.location.y + target.size.height / 2;
+    let max_elev = cmp::max(
+        area.layer_set.elevation(start_x, start_y),
+        area.layer_set.elevation(target_x, target_y),
+    );
+
+    let is_clear = |x: i32, y: i32| {
+        let occupied = |e: &EntityState| e.location_points().any(|p| p.x == x && p.y == y);
+        if occupied(entity) || occupied(target) {
+            return true;
+        }
+
+        let index = (x + y * area.width) as usize;
+        prop_vis_grid[index]
+            && prop_pass_grid[index]
+            && area.layer_set.is_visible_index(index)
+            && area.layer_set.elevation_index(index) <= max_elev
+    };
+
+    let mut total = 0;
+    let mut obstructed = 0;
+    for p in target.location_points() {
+        total += 1;
+        if !cast_ray(start_x, start_y, p.x, p.y, &is_clear) {
+            obstructed += 1;
+        }
+    }
+
+    if total == 0 {
+        return 0.0;
+    }
+
+    obstructed as f32 / total as f32
+}
+
 fn check_vis(
     area: &GeneratedArea,
     prop_vis_grid: &[bool],
@@ -106,83 +154,37 @@ fn check_vis(
     let dist_squared =
         (start_x - end_x) * (start_x - end_x) + (start_y - end_y) * (start_y - end_y);
 
-    if dist_squared < area.area.vis_dist_up_one_squared {
-        cast_ray(
-            area,
-            prop_vis_grid,
-            start_x,
-            start_y,
-            end_x,
-            end_y,
-            src_elev + 1,
-        )
+    let elev = if dist_squared < area.area.vis_dist_up_one_squared {
+        src_elev + 1
     } else if dist_squared < area.area.vis_dist_squared {
-        cast_ray(
-            area,
-            prop_vis_grid,
-            start_x,
-            start_y,
-            end_x,
-            end_y,
-            src_elev,
-        )
+        src_elev
     } else {
-        false
-    }
+        return false;
+    };
+
+    cast_ray(start_x, start_y, end_x, end_y, &|x, y| {
+        check(area, prop_vis_grid, x, y, elev)
+    })
 }
 
-fn cast_ray(
-    area: &GeneratedArea,
-    prop_vis_grid: &[bool],
+fn cast_ray<F: Fn(i32, i32) -> bool>(
     start_x: i32,
     start_y: i32,
     end_x: i32,
     end_y: i32,
-    src_elev: u8,
+    check: &F,
 ) -> bool {
     if (end_y - start_y).abs() < (end_x - start_x).abs() {
         if start_x > end_x {
-            cast_low(
-                area,
-                prop_vis_grid,
-                end_x,
-                end_y,
-                start_x,
-                start_y,
-                src_elev,
-            )
+            cast_low(end_x, end_y, start_x, start_y, check)
         } else {
-            cast_low(
-                area,
-                prop_vis_grid,
-                start_x,
-                start_y,
-                end_x,
-                end_y,
-                src_elev,
-            )
+            cast_low(start_x, start_y, end_x, end_y, check)
         }
     } else {
         if start_y > end_y {
-            cast_high(
-                area,
-                prop_vis_grid,
-                end_x,
-                end_y,
-                start_x,
-                start_y,
-                src_elev,
-            )
+            cast_high(end_x, end_y, start_x, start_y, check)
         } else {
-            cast_high(
-                area,
-                prop_vis_grid,
-                start_x,
-                start_y,
-                end_x,
-                end_y,
-                src_elev,
-            )
+            cast_high(start_x, start_y, end_x, end_y, check)
         }
     }
 }
@@ -195,14 +197,12 @@ fn check(area: &GeneratedArea, prop_vis_grid: &[bool], x: i32, y: i32, src_elev:
         && area.layer_set.elevation_index(index) <= src_elev
 }
 
-fn cast_high(
-    area: &GeneratedArea,
-    prop_vis_grid: &[bool],
+fn cast_high<F: Fn(i32, i32) -> bool>(
     start_x: i32,
     start_y: i32,
     end_x: i32,
     end_y: i32,
-    src_elev: u8,
+    check: &F,
 ) -> bool {
     let mut delta_x = end_x - start_x;
     let delta_y = end_y - start_y;
@@ -221,7 +221,7 @@ fn cast_high(
     for y in start_y..end_y {
         if first {
             first = false;
-        } else if !check(area, prop_vis_grid, x, y, src_elev) {
+        } else if !check(x, y) {
             return false;
         }
 
@@ -235,14 +235,12 @@ fn cast_high(
     true
 }
 
-fn cast_low(
-    area: &GeneratedArea,
-    prop_vis_grid: &[bool],
+fn cast_low<F: Fn(i32, i32) -> bool>(
     start_x: i32,
     start_y: i32,
     end_x: i32,
     end_y: i32,
-    src_elev: u8,
+    check: &F,
 ) -> bool {
     let delta_x = end_x - start_x;
     let mut delta_y = end_y - start_y;
@@ -261,7 +259,7 @@ fn cast_low(
     for x in start_x..end_x {
         if first {
             first = false;
-        } else if !check(area, prop_vis_grid, x, y, src_elev) {
+        } else if !check(x, y) {
             return false;
         }
 
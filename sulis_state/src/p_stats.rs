@@ -42,6 +42,9 @@ pub struct PStats {
     #[serde(skip)]
     threatening: Vec<usize>,
 
+    #[serde(default)]
+    reactions_used: u32,
+
     pub(crate) current_group_uses_per_encounter: HashMap<String, ExtInt>,
     pub(crate) current_group_uses_per_day: HashMap<String, ExtInt>,
 
@@ -67,6 +70,7 @@ impl PStats {
             inventory_locked: false,
             threatened_by: Vec::new(),
             threatening: Vec::new(),
+            reactions_used: 0,
             current_group_uses_per_encounter: HashMap::new(),
             current_group_uses_per_day: HashMap::new(),
             current_class_stats: HashMap::new(),
@@ -155,6 +159,26 @@ impl PStats {
         !self.threatened_by.is_empty()
     }
 
+    pub fn threatened_by(&self) -> &[usize] {
+        &self.threatened_by
+    }
+
+    /// Returns the number of reactions the parent entity may still make
+    /// before its next turn
+    pub fn reactions_remaining(&self, stats: &StatList) -> u32 {
+        (stats.reactions.max(0) as u32).saturating_sub(self.reactions_used)
+    }
+
+    /// Uses up one reaction, returning false if none were remaining
+    pub fn use_reaction(&mut self, stats: &StatList) -> bool {
+        if self.reactions_remaining(stats) == 0 {
+            return false;
+        }
+
+        self.reactions_used += 1;
+        true
+    }
+
     pub fn add_threatening(&mut self, index: usize) {
         if !self.threatening.contains(&index) {
             self.threatening.push(index);
@@ -346,6 +370,7 @@ impl PStats {
         }
 
         self.ap = ap;
+        self.reactions_used = 0;
     }
 
     pub fn end_turn(&mut self) {
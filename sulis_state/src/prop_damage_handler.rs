@@ -0,0 +1,276 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2019 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use sulis_core::io::Audio;
+use sulis_core::util::Point;
+use sulis_module::prop::{Explosion, Interactive};
+use sulis_module::{DamageKind, DamageList, HitFlags, HitKind, Module};
+
+use crate::{
+    center, combat_log, dist, ActorState, AreaFeedbackText, AreaState, CombatLogKind, EntityState,
+    GameState,
+};
+
+type AttackResult = Vec<(HitKind, HitFlags, Vec<(DamageKind, u32)>)>;
+
+/// Performs a weapon attack by `parent` against the destructible prop at
+/// `index` in the parent's area.  Props have no defenses, so every attack hits.
+pub fn weapon_attack_prop(parent: &Rc<RefCell<EntityState>>, index: usize) -> AttackResult {
+    let area_state = match GameState::get_area_state(&parent.borrow().location.area_id) {
+        None => return Vec::new(),
+        Some(area_state) => area_state,
+    };
+
+    {
+        let area_state = area_state.borrow();
+        if !area_state.props().index_valid(index) {
+            return Vec::new();
+        }
+
+        let prop = area_state.props().get(index);
+        if !prop.is_destructible() || prop.is_destroyed() {
+            return Vec::new();
+        }
+
+        info!(
+            "'{}' attacks prop '{}'",
+            parent.borrow().actor.actor.name,
+            prop.prop.id
+        );
+    }
+
+    let attacks = parent.borrow().actor.stats.attacks.clone();
+    let mut result = Vec::new();
+    for attack in attacks {
+        let multiplier = parent.borrow().actor.stats.hit_multiplier + attack.bonuses.hit_multiplier;
+        let damage = damage_prop(parent, &area_state, index, &attack.damage, multiplier);
+
+        if let Some(sound_id) = attack.sounds.sound(HitKind::Hit) {
+            Audio::play_sfx(sound_id, 1.0);
+        }
+
+        result.push((HitKind::Hit, HitFlags::default(), damage));
+    }
+
+    result
+}
+
+/// Rolls the specified damage against the armor and resistances of the
+/// destructible prop at `index`, destroying it if its hit points are
+/// reduced to zero.  Returns the damage that was dealt.
+pub fn damage_prop(
+    attacker: &Rc<RefCell<EntityState>>,
+    area_state: &Rc<RefCell<AreaState>>,
+    index: usize,
+    damage: &DamageList,
+    multiplier: f32,
+) -> Vec<(DamageKind, u32)> {
+    let rolls = {
+        let area_state = area_state.borrow();
+        if !area_state.props().index_valid(index) {
+            return Vec::new();
+        }
+
+        let prop = area_state.props().get(index);
+        if prop.is_destroyed() {
+            return Vec::new();
+        }
+
+        let (armor, resistance) = match prop.prop.interactive {
+            Interactive::Destructible {
+                ref armor,
+                ref resistance,
+                ..
+            } => (armor, resistance),
+            _ => return Vec::new(),
+        };
+
+        let rules = Module::rules();
+        let rolls = rules.roll_damage_detailed(damage, armor, resistance, multiplier);
+        if !rolls.is_empty() {
+            let text = combat_log::damage_text(prop.name(), &rolls);
+            GameState::add_combat_log_entry(CombatLogKind::Damage, text);
+        }
+        rolls
+    };
+
+    let damage: Vec<_> = rolls
+        .into_iter()
+        .filter(|roll| roll.amount > 0)
+        .map(|roll| (roll.kind, roll.amount))
+        .collect();
+
+    let total = damage.iter().map(|(_, amount)| amount).sum();
+    let destroyed = {
+        let mut area_state = area_state.borrow_mut();
+        let destroyed = area_state.remove_prop_hp(index, total);
+
+        let prop = area_state.props().get(index);
+        let points: Vec<_> = prop.location_points().collect();
+        let feedback = AreaFeedbackText::with_prop_damage(prop, &area_state, HitKind::Hit, &damage);
+        area_state.add_feedback_text(feedback);
+
+        let round = GameState::turn_manager().borrow().current_round();
+        for (kind, _) in damage.iter() {
+            area_state.damage_surface_types(*kind, &points, round);
+        }
+
+        destroyed
+    };
+
+    if destroyed {
+        destroy_prop(attacker, area_state, index);
+    }
+
+    damage
+}
+
+fn destroy_prop(
+    attacker: &Rc<RefCell<EntityState>>,
+    area_state: &Rc<RefCell<AreaState>>,
+    index: usize,
+) {
+    let (prop, location, center) = {
+        let area_state = area_state.borrow();
+        let prop = area_state.props().get(index);
+        (
+            Rc::clone(&prop.prop),
+            prop.location.to_point(),
+            center(prop),
+        )
+    };
+
+    debug!("Prop '{}' at {:?} was destroyed", prop.id, location);
+
+    let (loot, explosion, on_destroyed) = match prop.interactive {
+        Interactive::Destructible {
+            ref loot,
+            ref explosion,
+            ref on_destroyed,
+            ..
+        } => (loot, explosion, on_destroyed),
+        _ => return,
+    };
+
+    if let Some(loot) = loot {
+        let items = loot.generate();
+        if !items.is_empty() {
+            let mut area_state = area_state.borrow_mut();
+            if let Some(index) = area_state
+                .props_mut()
+                .check_or_create_container(location.x, location.y)
+            {
+                area_state.props_mut().get_mut(index).add_items(items);
+            }
+        }
+    }
+
+    if !on_destroyed.is_empty() {
+        GameState::add_ui_callback(on_destroyed.clone(), attacker, attacker);
+    }
+
+    if let Some(explosion) = explosion {
+        explode(attacker, area_state, center, explosion);
+    }
+}
+
+fn explode(
+    attacker: &Rc<RefCell<EntityState>>,
+    area_state: &Rc<RefCell<AreaState>>,
+    center: (f32, f32),
+    explosion: &Explosion,
+) {
+    let radius = explosion.radius;
+    let damage = DamageList::from(explosion.damage);
+
+    let targets: Vec<_> = {
+        let mgr = GameState::turn_manager();
+        let mgr = mgr.borrow();
+        let area_state = area_state.borrow();
+        area_state
+            .entity_iter()
+            .map(|index| mgr.entity(*index))
+            .filter(|entity| {
+                let entity = entity.borrow();
+                !entity.actor.is_dead() && dist(&center, &*entity) <= radius
+            })
+            .collect()
+    };
+
+    let rules = Module::rules();
+    for target in targets {
+        let amount = {
+            let stats = &target.borrow().actor.stats;
+            rules.roll_damage(&damage, &stats.armor, &stats.resistance, 1.0)
+        };
+
+        if !amount.is_empty() {
+            EntityState::remove_hp(&target, attacker, HitKind::Auto, amount.clone());
+        }
+
+        let feedback = AreaFeedbackText::with_damage(
+            &target.borrow(),
+            &area_state.borrow(),
+            HitKind::Auto,
+            HitFlags::default(),
+            &amount,
+        );
+        area_state.borrow_mut().add_feedback_text(feedback);
+        ActorState::check_death(attacker, &target);
+    }
+
+    let (points, props) = {
+        let area_state = area_state.borrow();
+        let r = radius.ceil() as i32;
+        let (cx, cy) = (center.0 as i32, center.1 as i32);
+        let mut points = Vec::new();
+        for y in (cy - r)..=(cy + r) {
+            for x in (cx - r)..=(cx + r) {
+                let p = Point::new(x, y);
+                if dist(&center, &p) <= radius {
+                    points.push(p);
+                }
+            }
+        }
+
+        let props: Vec<usize> = (0..area_state.props().len())
+            .filter(|index| area_state.props().index_valid(*index))
+            .filter(|index| {
+                let prop = area_state.props().get(*index);
+                prop.is_destructible() && !prop.is_destroyed() && dist(&center, prop) <= radius
+            })
+            .collect();
+        (points, props)
+    };
+
+    let round = GameState::turn_manager().borrow().current_round();
+    let kind = explosion
+        .damage
+        .kind
+        .unwrap_or_else(DamageKind::default_kind);
+    area_state
+        .borrow_mut()
+        .damage_surface_types(kind, &points, round);
+
+    // damage other destructible props in the radius, which may in turn
+    // set off further explosions
+    for index in props {
+        damage_prop(attacker, area_state, index, &damage, 1.0);
+    }
+}
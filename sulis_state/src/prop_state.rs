@@ -45,6 +45,17 @@ pub enum Interactive {
     Hover {
         text: String,
     },
+    Destructible {
+        hp: i32,
+    },
+    Trap {
+        detected: bool,
+        disarmed: bool,
+
+        /// unique IDs of party members who have already made a detection
+        /// check against this trap
+        detect_attempts: Vec<String>,
+    },
 }
 
 pub struct PropState {
@@ -54,6 +65,12 @@ pub struct PropState {
     pub listeners: ChangeListenerList<PropState>,
     pub(crate) interactive: Interactive,
     enabled: bool,
+    locked: bool,
+    trap_sprung: bool,
+
+    /// unique IDs of party members who have already attempted to pick
+    /// this prop's lock
+    pick_attempts: Vec<String>,
 
     marked_for_removal: bool,
 
@@ -119,6 +136,22 @@ impl PropState {
                     fire_more_than_once: *fire_more_than_once,
                 }
             }
+            prop::Interactive::Destructible { hp, .. } => {
+                if !items.is_empty() {
+                    warn!("Attempted to add items to a destructible prop.  Use loot instead.");
+                }
+                Interactive::Destructible { hp: *hp as i32 }
+            }
+            prop::Interactive::Trap { .. } => {
+                if !items.is_empty() {
+                    warn!("Attempted to add items to a trap prop");
+                }
+                Interactive::Trap {
+                    detected: false,
+                    disarmed: false,
+                    detect_attempts: Vec::new(),
+                }
+            }
         };
 
         let millis_offset_range = prop_data.prop.random_millis_offset;
@@ -131,6 +164,9 @@ impl PropState {
         PropState {
             prop: Rc::clone(&prop_data.prop),
             enabled: prop_data.enabled,
+            locked: prop_data.prop.lock.is_some(),
+            trap_sprung: false,
+            pick_attempts: Vec::new(),
             location,
             interactive,
             animation_state: anim_state,
@@ -223,11 +259,56 @@ impl PropState {
 
                 self.interactive = Interactive::Hover { text };
             }
+            PropInteractiveSaveState::Destructible { hp } => {
+                // the base prop interactive must match, if not don't load this.
+                // this is for save compat.
+                match self.prop.interactive {
+                    prop::Interactive::Destructible { .. } => (),
+                    _ => return Ok(()),
+                }
+
+                self.interactive = Interactive::Destructible { hp };
+                if hp <= 0 {
+                    self.animation_state.add(animation_state::Kind::Active);
+                } else {
+                    self.animation_state.remove(animation_state::Kind::Active);
+                }
+            }
+            PropInteractiveSaveState::Trap { detected, disarmed } => {
+                // the base prop interactive must match, if not don't load this.
+                // this is for save compat.
+                match self.prop.interactive {
+                    prop::Interactive::Trap { .. } => (),
+                    _ => return Ok(()),
+                }
+
+                self.interactive = Interactive::Trap {
+                    detected,
+                    disarmed,
+                    detect_attempts: Vec::new(),
+                };
+            }
         }
 
         Ok(())
     }
 
+    pub(crate) fn load_lock(
+        &mut self,
+        locked: Option<bool>,
+        trap_sprung: bool,
+        pick_attempts: Vec<String>,
+    ) {
+        if let Some(locked) = locked {
+            self.locked = locked && self.prop.lock.is_some();
+        }
+        self.trap_sprung = trap_sprung;
+        self.pick_attempts = pick_attempts;
+        if trap_sprung && self.is_trap() {
+            self.animation_state.add(animation_state::Kind::Active);
+        }
+    }
+
     pub fn name(&self) -> &str {
         match self.interactive {
             Interactive::Hover { ref text } => text,
@@ -283,12 +364,169 @@ impl PropState {
         matches!(self.interactive, Interactive::Container { .. })
     }
 
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    pub(crate) fn set_locked(&mut self, locked: bool) {
+        self.locked = locked && self.prop.lock.is_some();
+        self.listeners.notify(self);
+    }
+
+    pub fn pick_attempts(&self) -> &[String] {
+        &self.pick_attempts
+    }
+
+    /// Records an attempt to pick this prop's lock by the party member with
+    /// the specified unique ID.  Returns false if that party member has
+    /// already attempted to pick this lock
+    pub(crate) fn add_pick_attempt(&mut self, id: &str) -> bool {
+        if self.pick_attempts.iter().any(|attempt| attempt == id) {
+            return false;
+        }
+
+        self.pick_attempts.push(id.to_string());
+        true
+    }
+
+    pub fn is_trap_sprung(&self) -> bool {
+        self.trap_sprung
+    }
+
+    pub(crate) fn set_trap_sprung(&mut self) {
+        self.trap_sprung = true;
+        if let Interactive::Trap {
+            ref mut detected, ..
+        } = self.interactive
+        {
+            *detected = true;
+            self.animation_state.add(animation_state::Kind::Active);
+        }
+    }
+
+    pub fn is_trap(&self) -> bool {
+        matches!(self.interactive, Interactive::Trap { .. })
+    }
+
+    /// Returns true if this is a trap that the party has not yet found.
+    /// Hidden traps are not drawn and cannot be interacted with
+    pub fn is_hidden(&self) -> bool {
+        matches!(self.interactive, Interactive::Trap { detected, .. } if !detected)
+    }
+
+    /// Returns true if this is a trap that will fire when a party member
+    /// moves onto it
+    pub fn is_trap_armed(&self) -> bool {
+        let fire_more_than_once = match self.prop.interactive {
+            prop::Interactive::Trap { ref effect, .. } => effect.fire_more_than_once,
+            _ => return false,
+        };
+
+        match self.interactive {
+            Interactive::Trap { disarmed, .. } => {
+                self.enabled && !disarmed && (!self.trap_sprung || fire_more_than_once)
+            }
+            _ => false,
+        }
+    }
+
+    pub(crate) fn set_trap_detected(&mut self) {
+        if let Interactive::Trap {
+            ref mut detected, ..
+        } = self.interactive
+        {
+            *detected = true;
+        }
+    }
+
+    pub(crate) fn set_trap_disarmed(&mut self) {
+        if let Interactive::Trap {
+            ref mut disarmed, ..
+        } = self.interactive
+        {
+            *disarmed = true;
+        }
+    }
+
+    /// Records a detection attempt against this trap by the party member with
+    /// the specified unique ID.  Returns false if that party member has
+    /// already attempted to detect this trap
+    pub(crate) fn add_detect_attempt(&mut self, id: &str) -> bool {
+        let attempts = match self.interactive {
+            Interactive::Trap {
+                ref mut detect_attempts,
+                ..
+            } => detect_attempts,
+            _ => return false,
+        };
+
+        if attempts.iter().any(|attempt| attempt == id) {
+            return false;
+        }
+
+        attempts.push(id.to_string());
+        true
+    }
+
+    pub fn is_destructible(&self) -> bool {
+        matches!(self.interactive, Interactive::Destructible { .. })
+    }
+
+    /// Returns true if this is a destructible prop that has been reduced
+    /// to zero hit points
+    pub fn is_destroyed(&self) -> bool {
+        matches!(self.interactive, Interactive::Destructible { hp } if hp <= 0)
+    }
+
+    /// Returns the current hit points for a destructible prop, or
+    /// `None` for any other kind of prop
+    pub fn hp(&self) -> Option<i32> {
+        match self.interactive {
+            Interactive::Destructible { hp } => Some(hp),
+            _ => None,
+        }
+    }
+
+    /// Removes the specified amount of hit points from this destructible prop.
+    /// Returns true if this caused the prop to be destroyed.  A destroyed
+    /// prop is drawn using its active animation state.
+    pub(crate) fn remove_hp(&mut self, amount: u32) -> bool {
+        let hp = match self.interactive {
+            Interactive::Destructible { ref mut hp } => hp,
+            _ => {
+                warn!("Attempted to damage non-destructible prop {}", self.prop.id);
+                return false;
+            }
+        };
+
+        if *hp <= 0 {
+            return false;
+        }
+
+        *hp -= amount as i32;
+        if *hp > 0 {
+            return false;
+        }
+
+        self.animation_state.add(animation_state::Kind::Active);
+        true
+    }
+
+    /// Toggles the active state of this prop.  Destructible props only
+    /// become active when destroyed, so this has no effect on them.
     pub fn toggle_active(&mut self) {
+        if self.is_destructible() {
+            return;
+        }
+
         self.animation_state.toggle(animation_state::Kind::Active);
         let is_active = self.is_active();
 
         match self.interactive {
-            Interactive::Not | Interactive::Hover { .. } => (),
+            Interactive::Not
+            | Interactive::Hover { .. }
+            | Interactive::Destructible { .. }
+            | Interactive::Trap { .. } => (),
             Interactive::Container {
                 ref mut items,
                 ref mut loot_to_generate,
@@ -0,0 +1,240 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2019 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use sulis_core::util::Point;
+use sulis_module::on_trigger::QuestEntryState;
+use sulis_module::quest::{Objective, QuestMarker, QuestReward};
+use sulis_module::{ItemState, Module, OnTrigger, Quest};
+
+use crate::{GameState, QuestStateSet};
+
+enum Event<'a> {
+    Kill(&'a str),
+    Talk(&'a str),
+    Poll,
+}
+
+/// Counts a kill of the actor towards any active kill objectives
+pub fn on_kill(actor_id: &str) {
+    update(Event::Kill(actor_id));
+}
+
+/// Completes any active objectives to talk to the actor
+pub fn on_talk(actor_id: &str) {
+    update(Event::Talk(actor_id));
+}
+
+/// Checks objectives that depend on the party's current state, such as
+/// items collected and locations reached
+pub(crate) fn check_objectives() {
+    update(Event::Poll);
+}
+
+/// The markers of all active entries in quests shown in the journal
+pub fn active_markers(quests: &QuestStateSet) -> Vec<QuestMarker> {
+    let mut markers = Vec::new();
+
+    for quest in Module::all_quests() {
+        match quests.state(&quest.id) {
+            QuestEntryState::Visible | QuestEntryState::Active => (),
+            _ => continue,
+        }
+
+        for (entry_id, entry) in quest.entries.iter() {
+            if quests.entry_state(&quest.id, entry_id) != QuestEntryState::Active {
+                continue;
+            }
+
+            if let Some(marker) = entry.map_marker() {
+                markers.push(marker);
+            }
+        }
+    }
+
+    markers
+}
+
+fn update(event: Event) {
+    for quest in Module::all_quests() {
+        if !quest.has_objectives() {
+            continue;
+        }
+
+        for (entry_id, entry) in quest.entries.iter() {
+            let quests = GameState::quest_state();
+            if quests.entry_state(&quest.id, entry_id) != QuestEntryState::Active {
+                continue;
+            }
+
+            // the fail objective's progress is stored alongside the entry's
+            if let Some(ref fail_on) = entry.fail_on {
+                let key = format!("{}#fail", entry_id);
+                if update_progress(&quests, &quest.id, &key, fail_on, &event) {
+                    fail_entry(&quest, entry_id, entry.optional);
+                    continue;
+                }
+            }
+
+            if let Some(ref objective) = entry.objective {
+                if update_progress(&quests, &quest.id, entry_id, objective, &event) {
+                    complete_entry(&quest, entry_id, entry.reward.as_ref());
+                }
+            }
+        }
+    }
+}
+
+/// Updates the stored progress for the objective, returning true if it
+/// has been met
+fn update_progress(
+    quests: &QuestStateSet,
+    quest_id: &str,
+    key: &str,
+    objective: &Objective,
+    event: &Event,
+) -> bool {
+    let cur = quests.entry_progress(quest_id, key);
+    let progress = match (objective, event) {
+        (Objective::Kill { actor, .. }, Event::Kill(id)) if actor == id => cur + 1,
+        (Objective::Talk { actor }, Event::Talk(id)) if actor == id => 1,
+        (Objective::Collect { item, .. }, Event::Poll) => party_item_count(item),
+        (Objective::Reach { area, point, dist }, Event::Poll) => {
+            if party_has_reached(area, *point, *dist) {
+                1
+            } else {
+                0
+            }
+        }
+        _ => return false,
+    };
+
+    if progress != cur {
+        GameState::set_quest_entry_progress(quest_id, key, progress);
+    }
+
+    progress >= objective.count()
+}
+
+fn party_item_count(id: &str) -> u32 {
+    let stash = GameState::party_stash();
+    let stash = stash.borrow();
+    stash
+        .items()
+        .iter()
+        .filter(|(_, item)| item.item.id == id)
+        .map(|(qty, _)| *qty)
+        .sum()
+}
+
+fn party_has_reached(area: &str, point: Option<Point>, dist: f32) -> bool {
+    GameState::party().iter().any(|member| {
+        let member = member.borrow();
+        if member.actor.is_dead() || !member.location.is_in_area_id(area) {
+            return false;
+        }
+
+        match point {
+            None => true,
+            Some(point) => member.location.to_point().dist(point) <= dist,
+        }
+    })
+}
+
+fn complete_entry(quest: &Quest, entry_id: &str, reward: Option<&QuestReward>) {
+    info!("Completed objective '{}' of quest '{}'", entry_id, quest.id);
+    GameState::set_quest_entry_state(
+        quest.id.to_string(),
+        entry_id.to_string(),
+        QuestEntryState::Complete,
+    );
+
+    let mut line = format!("{}: Objective complete", quest.name);
+    if let Some(reward) = reward {
+        line.push_str(&give_reward(reward));
+    }
+    say_line(line);
+
+    // quests with required entries that are not objectives are completed
+    // by scripts or conversations instead
+    let quests = GameState::quest_state();
+    let required_complete = quest
+        .entries
+        .iter()
+        .filter(|(_, entry)| !entry.optional)
+        .all(|(id, entry)| {
+            entry.objective.is_some()
+                && quests.entry_state(&quest.id, id) == QuestEntryState::Complete
+        });
+
+    if required_complete {
+        info!("Completed all objectives of quest '{}'", quest.id);
+        GameState::set_quest_state(quest.id.to_string(), QuestEntryState::Complete);
+    }
+}
+
+fn fail_entry(quest: &Quest, entry_id: &str, optional: bool) {
+    info!("Failed objective '{}' of quest '{}'", entry_id, quest.id);
+    GameState::set_quest_entry_state(
+        quest.id.to_string(),
+        entry_id.to_string(),
+        QuestEntryState::Failed,
+    );
+
+    if optional {
+        say_line(format!("{}: Optional objective failed", quest.name));
+    } else {
+        GameState::set_quest_state(quest.id.to_string(), QuestEntryState::Failed);
+        say_line(format!("{}: Quest failed", quest.name));
+    }
+}
+
+/// Gives the reward to the party, returning a description of it
+fn give_reward(reward: &QuestReward) -> String {
+    let mut gained = Vec::new();
+
+    if reward.xp > 0 {
+        for member in GameState::party().iter() {
+            member.borrow_mut().add_xp(reward.xp);
+        }
+        gained.push(format!("{} xp", reward.xp));
+    }
+
+    if reward.coins != 0 {
+        GameState::add_party_coins(reward.coins);
+        gained.push(format!("{} coins", reward.coins));
+    }
+
+    let stash = GameState::party_stash();
+    for id in reward.items.iter() {
+        let item = match Module::item(id) {
+            None => continue,
+            Some(item) => item,
+        };
+        gained.push(item.name.to_string());
+        stash.borrow_mut().add_item(1, ItemState::new(item, None));
+    }
+
+    if gained.is_empty() {
+        String::new()
+    } else {
+        format!(".  Gained {}", gained.join(", "))
+    }
+}
+
+fn say_line(line: String) {
+    let pc = GameState::player();
+    GameState::add_ui_callback(vec![OnTrigger::SayLine(line)], &pc, &pc);
+}
@@ -24,6 +24,7 @@ use sulis_module::{on_trigger::QuestEntryState, Module};
 pub struct QuestStateSet {
     quests: HashMap<String, QuestState>,
     current_quest: Vec<String>, // the order that quests have been updated, most recent last
+    notes: Vec<String>,
 
     pub listeners: ChangeListenerList<QuestStateSet>,
 }
@@ -33,6 +34,7 @@ impl Clone for QuestStateSet {
         QuestStateSet {
             quests: self.quests.clone(),
             current_quest: self.current_quest.clone(),
+            notes: self.notes.clone(),
             listeners: ChangeListenerList::default(),
         }
     }
@@ -50,6 +52,7 @@ impl Default for QuestStateSet {
         QuestStateSet {
             quests,
             current_quest: Vec::new(),
+            notes: Vec::new(),
             listeners: ChangeListenerList::default(),
         }
     }
@@ -65,6 +68,7 @@ impl QuestStateSet {
         QuestStateSet {
             quests,
             current_quest: data.current_quest,
+            notes: data.notes,
             listeners: ChangeListenerList::default(),
         }
     }
@@ -73,6 +77,25 @@ impl QuestStateSet {
         self.current_quest.clone()
     }
 
+    /// The player's own journal notes, in the order they were added
+    pub fn notes(&self) -> &[String] {
+        &self.notes
+    }
+
+    pub fn add_note(&mut self, note: String) {
+        self.notes.push(note);
+        self.listeners.notify(self);
+    }
+
+    pub fn remove_note(&mut self, index: usize) {
+        if index >= self.notes.len() {
+            return;
+        }
+
+        self.notes.remove(index);
+        self.listeners.notify(self);
+    }
+
     pub fn current_quest(&self) -> Option<&String> {
         self.current_quest.last()
     }
@@ -101,7 +124,7 @@ impl QuestStateSet {
         self.current_quest.retain(|id| id != quest);
 
         match self.quests.get(quest).unwrap().state {
-            QuestEntryState::Complete | QuestEntryState::Hidden => {
+            QuestEntryState::Complete | QuestEntryState::Failed | QuestEntryState::Hidden => {
                 // don't add the current quest as active since it isn't
                 // displayed in the window by default
             }
@@ -149,6 +172,24 @@ impl QuestStateSet {
         self.set_current_quest_and_notify(quest_id);
     }
 
+    /// Sets the objective progress for the entry, without changing its state
+    pub fn set_entry_progress(&mut self, quest_id: &str, entry: &str, progress: u32) {
+        let quest = self
+            .quests
+            .entry(quest_id.to_string())
+            .or_insert_with(|| QuestState::new(quest_id.to_string()));
+        quest.set_entry_progress(entry, progress);
+
+        self.listeners.notify(self);
+    }
+
+    pub fn entry_progress(&self, quest: &str, entry: &str) -> u32 {
+        match self.quests.get(quest) {
+            None => 0,
+            Some(quest) => quest.entry_progress(entry),
+        }
+    }
+
     pub fn quests_iter(self) -> impl Iterator<Item = (String, QuestState)> {
         self.quests.into_iter()
     }
@@ -159,6 +200,9 @@ pub struct QuestState {
     id: String,
     state: QuestEntryState,
     entries: Vec<(String, QuestEntryState)>,
+
+    #[serde(default)]
+    progress: Vec<(String, u32)>,
 }
 
 impl QuestState {
@@ -167,6 +211,7 @@ impl QuestState {
             id,
             state: QuestEntryState::Hidden,
             entries: Vec::new(),
+            progress: Vec::new(),
         }
     }
 
@@ -202,6 +247,27 @@ impl QuestState {
         self.entries.push((entry.to_string(), state));
     }
 
+    pub fn entry_progress(&self, entry: &str) -> u32 {
+        for (ref id, progress) in self.progress.iter() {
+            if id == entry {
+                return *progress;
+            }
+        }
+
+        0
+    }
+
+    pub fn set_entry_progress(&mut self, entry: &str, progress: u32) {
+        for (ref id, ref mut cur) in self.progress.iter_mut() {
+            if id == entry {
+                *cur = progress;
+                return;
+            }
+        }
+
+        self.progress.push((entry.to_string(), progress));
+    }
+
     pub fn state(&self) -> QuestEntryState {
         self.state
     }
@@ -0,0 +1,251 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::script::{Script, ScriptCallback, ScriptEntitySet};
+use crate::{
+    area_feedback_text::ColorKind, is_within_attack_dist, AreaFeedbackText, EntityState, GameState,
+    Locatable,
+};
+use sulis_core::util::Point;
+use sulis_module::Module;
+
+/// The kinds of events that may provoke an out of turn reaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReactionKind {
+    TargetLeftThreat,
+    TargetCastWhileThreatened,
+    AllyAttacked,
+    TargetEnteredThreat,
+}
+
+/// An action held by an entity during its turn, which fires the next time
+/// a hostile enters its reach before the start of its next turn.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub enum ReadiedAction {
+    /// A standard weapon attack against the entering hostile
+    Attack,
+
+    /// Calls `func` in the script of the specified ability, with the
+    /// entering hostile as the target
+    Ability { id: String, func: String },
+}
+
+/// A reaction that has been provoked but not yet resolved.  The `reactor`
+/// is the entity that may react, and the `target` is the entity that
+/// provoked it.  `from` is the square the target left, if it provoked the
+/// reaction by moving.
+#[derive(Debug, Clone, Copy)]
+pub struct Reaction {
+    pub kind: ReactionKind,
+    pub reactor: usize,
+    pub target: usize,
+    pub from: Option<Point>,
+}
+
+/// A target's footprint at a square it has moved away from
+struct Spot {
+    pos: Point,
+    size: (f32, f32),
+}
+
+impl Locatable for Spot {
+    fn size(&self) -> (f32, f32) {
+        self.size
+    }
+
+    fn pos(&self) -> (f32, f32) {
+        (self.pos.x as f32, self.pos.y as f32)
+    }
+}
+
+/// Checks that the target is still within the reactor's reach.  A target
+/// that has stepped out of reach is checked at the square it left, as long
+/// as it has not moved any further since.
+fn is_in_reach(reactor: &EntityState, target: &EntityState, from: Option<Point>) -> bool {
+    if reactor.location.area_id != target.location.area_id {
+        return false;
+    }
+
+    let from = match from {
+        None => return is_within_attack_dist(reactor, target),
+        Some(from) => from,
+    };
+
+    let (dx, dy) = (target.location.x - from.x, target.location.y - from.y);
+    if dx.abs() > 1 || dy.abs() > 1 {
+        return false;
+    }
+
+    let spot = Spot {
+        pos: from,
+        size: Locatable::size(target),
+    };
+    is_within_attack_dist(reactor, &spot)
+}
+
+fn can_react(reactor: &EntityState, target: &EntityState) -> bool {
+    if reactor.actor.is_dead() || reactor.actor.is_disabled() || target.actor.is_dead() {
+        return false;
+    }
+
+    if !reactor.is_hostile(target) {
+        return false;
+    }
+
+    reactor.actor.reactions_remaining() > 0
+}
+
+/// Fires script callbacks for each of the `reactions`, and then makes any
+/// built in reactions, such as attacks of opportunity
+pub fn fire_reactions(reactions: Vec<Reaction>) {
+    let mgr = GameState::turn_manager();
+    for reaction in reactions {
+        let (reactor, target) = {
+            let mgr = mgr.borrow();
+            match (
+                mgr.entity_checked(reaction.reactor),
+                mgr.entity_checked(reaction.target),
+            ) {
+                (Some(reactor), Some(target)) => (reactor, target),
+                _ => continue,
+            }
+        };
+
+        // readied actions were paid for on the reactor's turn, and so do
+        // not use up one of its reactions
+        if reaction.kind == ReactionKind::TargetEnteredThreat {
+            fire_readied_action(&reactor, &target);
+            continue;
+        }
+
+        if !can_react(&reactor.borrow(), &target.borrow()) {
+            continue;
+        }
+
+        debug!(
+            "'{}' may react to '{}': {:?}",
+            reactor.borrow().actor.actor.name,
+            target.borrow().actor.actor.name,
+            reaction.kind
+        );
+
+        let cbs = reactor.borrow().callbacks(&mgr.borrow());
+        for cb in cbs {
+            match reaction.kind {
+                ReactionKind::TargetLeftThreat => cb.on_target_left_threat(reaction.target),
+                ReactionKind::TargetCastWhileThreatened => {
+                    cb.on_target_cast_while_threatened(reaction.target)
+                }
+                ReactionKind::AllyAttacked => cb.on_ally_attacked(reaction.target),
+                ReactionKind::TargetEnteredThreat => unreachable!(),
+            }
+        }
+
+        if reaction.kind == ReactionKind::TargetLeftThreat
+            && Module::rules().reactions.attacks_of_opportunity
+        {
+            attack_of_opportunity(&reactor, &target, reaction.from);
+        }
+    }
+}
+
+fn attack_of_opportunity(
+    reactor: &Rc<RefCell<EntityState>>,
+    target: &Rc<RefCell<EntityState>>,
+    from: Option<Point>,
+) {
+    if !can_react(&reactor.borrow(), &target.borrow()) {
+        return;
+    }
+
+    if !is_in_reach(&reactor.borrow(), &target.borrow(), from) {
+        return;
+    }
+
+    if !reactor.borrow_mut().actor.use_reaction() {
+        return;
+    }
+
+    info!(
+        "'{}' makes an attack of opportunity against '{}'",
+        reactor.borrow().actor.actor.name,
+        target.borrow().actor.actor.name
+    );
+
+    let area = GameState::area_state();
+    let mut text = AreaFeedbackText::with_target(&reactor.borrow(), &area.borrow());
+    text.add_entry("Attack of Opportunity".to_string(), ColorKind::Info);
+    area.borrow_mut().add_feedback_text(text);
+
+    EntityState::attack(reactor, target, None, false);
+}
+
+fn fire_readied_action(reactor: &Rc<RefCell<EntityState>>, target: &Rc<RefCell<EntityState>>) {
+    {
+        let reactor = reactor.borrow();
+        let target = target.borrow();
+        if reactor.actor.is_dead() || reactor.actor.is_disabled() || target.actor.is_dead() {
+            return;
+        }
+
+        if !reactor.is_hostile(&target) {
+            return;
+        }
+    }
+
+    let index = reactor.borrow().index();
+    let action = match GameState::turn_manager()
+        .borrow_mut()
+        .take_readied_action(index)
+    {
+        None => return,
+        Some(action) => action,
+    };
+
+    info!(
+        "'{}' fires readied action {:?} against '{}'",
+        reactor.borrow().actor.actor.name,
+        action,
+        target.borrow().actor.actor.name
+    );
+
+    let area = GameState::area_state();
+    let mut text = AreaFeedbackText::with_target(&reactor.borrow(), &area.borrow());
+    text.add_entry("Readied Action".to_string(), ColorKind::Info);
+    area.borrow_mut().add_feedback_text(text);
+
+    match action {
+        ReadiedAction::Attack => EntityState::attack(reactor, target, None, false),
+        ReadiedAction::Ability { id, func } => {
+            let ability = match Module::ability(&id) {
+                None => {
+                    warn!("Invalid readied ability '{}'", id);
+                    return;
+                }
+                Some(ability) => ability,
+            };
+
+            let targets = ScriptEntitySet::from_pair(reactor, target);
+            Script::ability(reactor, &ability, targets, &func);
+        }
+    }
+}
@@ -28,11 +28,12 @@ use sulis_module::{
 };
 
 use crate::animation::AnimSaveState;
-use crate::area_state::TriggerState;
+use crate::area_state::{SurfaceTile, TriggerState};
 use crate::script::CallbackData;
 use crate::{
-    effect, prop_state::Interactive, turn_manager::EncounterRef, ActorState, Effect, EntityState,
-    Formation, GameState, Location, MerchantState, PStats, PropState, QuestState, WorldMapState,
+    effect, prop_state::Interactive, turn_manager::EncounterRef, ActorState, Awareness,
+    BanterState, Effect, EntityState, Formation, GameState, Location, MerchantState, PStats,
+    PropState, QuestState, ReadiedAction, WorldMapState,
 };
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -49,6 +50,9 @@ pub struct SaveState {
     pub(crate) current_area: String,
     pub(crate) world_map: WorldMapState,
     pub(crate) quests: QuestSaveState,
+
+    #[serde(default)]
+    pub(crate) banters: BanterState,
     pub(crate) areas: HashMap<String, AreaSaveState>,
     pub(crate) manager: ManagerSaveState,
     pub(crate) anims: Vec<AnimSaveState>,
@@ -90,6 +94,7 @@ impl SaveState {
 
         let quest_state = GameState::quest_state();
         let current_quest = quest_state.current_quest_stack();
+        let notes = quest_state.notes().to_vec();
         let mut quests = Vec::new();
         for (_, quest_state) in quest_state.quests_iter() {
             quests.push(quest_state);
@@ -98,6 +103,7 @@ impl SaveState {
         let quest_state = QuestSaveState {
             quests,
             current_quest,
+            notes,
         };
 
         let mgr = GameState::turn_manager();
@@ -116,6 +122,7 @@ impl SaveState {
             anims: GameState::save_anims(),
             world_map: GameState::world_map(),
             quests: quest_state,
+            banters: GameState::banter_state(),
             total_elapsed_millis,
         }
     }
@@ -130,6 +137,9 @@ impl SaveState {
 pub struct QuestSaveState {
     pub(crate) quests: Vec<QuestState>,
     pub(crate) current_quest: Vec<String>,
+
+    #[serde(default)]
+    pub(crate) notes: Vec<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -139,6 +149,9 @@ pub struct ManagerSaveState {
     pub(crate) effects: Vec<EffectSaveState>,
     pub(crate) cur_ai_group_index: usize,
     pub(crate) ai_groups: HashMap<String, EncounterRef>,
+
+    #[serde(default)]
+    pub(crate) readied: HashMap<String, ReadiedAction>,
 }
 
 impl ManagerSaveState {
@@ -166,11 +179,17 @@ impl ManagerSaveState {
             ai_groups.insert(key.to_string(), value.clone());
         }
 
+        let mut readied = HashMap::new();
+        for (key, value) in mgr.readied.iter() {
+            readied.insert(key.to_string(), value.clone());
+        }
+
         ManagerSaveState {
             entities,
             effects,
             cur_ai_group_index,
             ai_groups,
+            readied,
         }
     }
 }
@@ -236,6 +255,9 @@ pub struct AreaSaveState {
 
     #[serde(default)]
     pub(crate) seed: u128,
+
+    #[serde(default)]
+    pub(crate) surfaces: Vec<SurfaceTileSaveState>,
 }
 
 impl AreaSaveState {
@@ -280,6 +302,12 @@ impl AreaSaveState {
             merchants.push(MerchantSaveState::new(merchant));
         }
 
+        let surfaces = area_state
+            .surface_layer()
+            .iter()
+            .map(|(location, tile)| SurfaceTileSaveState::new(location, tile))
+            .collect();
+
         AreaSaveState {
             pc_explored,
             on_load_fired,
@@ -287,6 +315,7 @@ impl AreaSaveState {
             triggers,
             merchants,
             seed: area_state.area_gen_seed,
+            surfaces,
         }
     }
 }
@@ -299,6 +328,15 @@ pub struct PropSaveState {
     pub(crate) location: Point,
     pub(crate) active: bool,
     pub(crate) enabled: bool,
+
+    #[serde(default)]
+    pub(crate) locked: Option<bool>,
+
+    #[serde(default)]
+    pub(crate) trap_sprung: bool,
+
+    #[serde(default)]
+    pub(crate) pick_attempts: Vec<String>,
 }
 
 impl PropSaveState {
@@ -328,6 +366,10 @@ impl PropSaveState {
             }
             Interactive::Door { open, activate_fired, .. } => Door { open, activate_fired },
             Interactive::Hover { ref text } => Hover { text: text.clone() },
+            Interactive::Destructible { hp } => Destructible { hp },
+            Interactive::Trap {
+                detected, disarmed, ..
+            } => Trap { detected, disarmed },
         };
 
         PropSaveState {
@@ -336,6 +378,9 @@ impl PropSaveState {
             location,
             active: prop_state.is_active(),
             enabled: prop_state.is_enabled(),
+            locked: Some(prop_state.is_locked()),
+            trap_sprung: prop_state.is_trap_sprung(),
+            pick_attempts: prop_state.pick_attempts().to_vec(),
         }
     }
 }
@@ -358,6 +403,13 @@ pub enum PropInteractiveSaveState {
     Hover {
         text: String,
     },
+    Destructible {
+        hp: i32,
+    },
+    Trap {
+        detected: bool,
+        disarmed: bool,
+    },
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -376,6 +428,26 @@ impl TriggerSaveState {
     }
 }
 
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SurfaceTileSaveState {
+    pub(crate) location: Point,
+    pub(crate) surface_type: String,
+
+    #[serde(default)]
+    pub(crate) expires: Option<u32>,
+}
+
+impl SurfaceTileSaveState {
+    pub fn new(location: Point, tile: &SurfaceTile) -> SurfaceTileSaveState {
+        SurfaceTileSaveState {
+            location,
+            surface_type: tile.surface_type.id.to_string(),
+            expires: tile.expires,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(deny_unknown_fields)]
 pub struct MerchantSaveState {
@@ -434,6 +506,9 @@ pub struct EntitySaveState {
 
     #[serde(default)]
     pub(crate) collapsed_groups: Vec<String>,
+
+    #[serde(default)]
+    pub(crate) awareness: Awareness,
 }
 
 impl EntitySaveState {
@@ -472,7 +547,7 @@ impl EntitySaveState {
                 inline_race: None,
                 sex: Some(actor.sex),
                 portrait: actor.portrait.as_ref().map(|p| p.id()),
-                attributes: actor.attributes,
+                attributes: actor.attributes.clone(),
                 conversation: actor.conversation.as_ref().map(|c| c.id.to_string()),
                 faction: Some(actor.faction()),
                 images: actor.builder_images.clone(),
@@ -507,6 +582,7 @@ impl EntitySaveState {
             show_portrait: entity.show_portrait(),
             actor_base,
             collapsed_groups: entity.collapsed_groups(),
+            awareness: entity.awareness().clone(),
         }
     }
 }
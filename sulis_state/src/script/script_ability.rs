@@ -21,7 +21,9 @@ use std::rc::Rc;
 use rlua::{self, Context, UserData, UserDataMethods};
 
 use crate::script::{CallbackData, ScriptEntity};
-use crate::{area_feedback_text::ColorKind, AreaFeedbackText, EntityState, GameState};
+use crate::{
+    area_feedback_text::ColorKind, AreaFeedbackText, EntityState, GameState, ReactionKind,
+};
 use sulis_module::{
     ability::{self, AIData, Range},
     Ability, Module,
@@ -475,5 +477,21 @@ fn activate(
         .borrow_mut()
         .actor
         .activate_ability_state(&ability.id);
+
+    if provokes_reactions(&ability) {
+        let index = entity.borrow().index();
+        mgr.borrow_mut()
+            .queue_reactions_from_threateners(ReactionKind::TargetCastWhileThreatened, index);
+    }
     Ok(())
 }
+
+fn provokes_reactions(ability: &Ability) -> bool {
+    let active = match &ability.active {
+        None => return false,
+        Some(active) => active,
+    };
+
+    let group = active.group.name();
+    Module::rules().reactions.provoking_groups.contains(&group)
+}
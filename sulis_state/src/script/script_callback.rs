@@ -67,6 +67,9 @@ pub fn fire_cbs(cbs: Vec<TriggeredCallback>) {
             FuncKind::OnMovedInSurface => cb.on_moved_in_surface(target),
             FuncKind::OnEnteredSurface => cb.on_entered_surface(target),
             FuncKind::OnExitedSurface => cb.on_exited_surface(target),
+            FuncKind::OnTargetLeftThreat => cb.on_target_left_threat(target),
+            FuncKind::OnTargetCastWhileThreatened => cb.on_target_cast_while_threatened(target),
+            FuncKind::OnAllyAttacked => cb.on_ally_attacked(target),
             FuncKind::OnRemoved => cb.on_removed(),
             FuncKind::OnMoved => cb.on_moved(),
             FuncKind::OnRoundElapsed => cb.on_round_elapsed(),
@@ -197,6 +200,18 @@ pub enum FuncKind {
 
     /// Called whena an ability mode is deactivated
     OnDeactivated,
+
+    /// Called on an entity, out of turn, when a hostile it is threatening moves
+    /// out of its threatened area.  The hostile is passed as the target.
+    OnTargetLeftThreat,
+
+    /// Called on an entity, out of turn, when a hostile it is threatening
+    /// activates an ability from one of the reaction provoking groups.
+    OnTargetCastWhileThreatened,
+
+    /// Called on an entity, out of turn, when a hostile it is threatening
+    /// attacks one of its allies.  The attacker is passed as the target.
+    OnAllyAttacked,
 }
 
 /// A trait representing a callback that will fire a script when called.  In lua scripts,
@@ -253,6 +268,12 @@ pub trait ScriptCallback {
     fn on_entered_surface(&self, _target: usize) {}
 
     fn on_exited_surface(&self, _target: usize) {}
+
+    fn on_target_left_threat(&self, _target: usize) {}
+
+    fn on_target_cast_while_threatened(&self, _target: usize) {}
+
+    fn on_ally_attacked(&self, _target: usize) {}
 }
 
 /// A callback that can be passed to various functions to be executed later.
@@ -291,6 +312,9 @@ pub trait ScriptCallback {
 /// # `set_on_moved_in_surface_fn(func: String)`
 /// # `set_on_entered_surface_fn(func: String)`
 /// # `set_on_exited_surface_fn(func: String)`
+/// # `set_on_target_left_threat_fn(func: String)`
+/// # `set_on_target_cast_while_threatened_fn(func: String)`
+/// # `set_on_ally_attacked_fn(func: String)`
 /// Each of these methods causes a specified lua `func` to be called when the condition is met,
 /// as described in `FuncKind`.  Multiple of these methods may be added to one
 /// Callback.
@@ -547,6 +571,17 @@ impl CallbackData {
 
         self.exec_standard_script(targets, kind);
     }
+
+    fn exec_reaction_script(&self, kind: FuncKind, target: usize) {
+        if !self.funcs.contains_key(&kind) {
+            return;
+        }
+
+        let mut targets = ScriptEntitySet::with_parent(self.parent);
+        targets.indices.push(Some(target));
+
+        self.exec_standard_script(targets, kind);
+    }
 }
 
 impl ScriptCallback for CallbackData {
@@ -621,6 +656,18 @@ impl ScriptCallback for CallbackData {
         self.exec_standard_script(targets, FuncKind::OnExitedSurface);
     }
 
+    fn on_target_left_threat(&self, target: usize) {
+        self.exec_reaction_script(FuncKind::OnTargetLeftThreat, target);
+    }
+
+    fn on_target_cast_while_threatened(&self, target: usize) {
+        self.exec_reaction_script(FuncKind::OnTargetCastWhileThreatened, target);
+    }
+
+    fn on_ally_attacked(&self, target: usize) {
+        self.exec_reaction_script(FuncKind::OnAllyAttacked, target);
+    }
+
     fn after_defense(
         &self,
         targets: &ScriptEntitySet,
@@ -837,6 +884,21 @@ impl UserData for CallbackData {
             cb.add_func(FuncKind::OnExitedSurface, func);
             Ok(())
         });
+        methods.add_method_mut("set_on_target_left_threat_fn", |_, cb, func: String| {
+            cb.add_func(FuncKind::OnTargetLeftThreat, func);
+            Ok(())
+        });
+        methods.add_method_mut(
+            "set_on_target_cast_while_threatened_fn",
+            |_, cb, func: String| {
+                cb.add_func(FuncKind::OnTargetCastWhileThreatened, func);
+                Ok(())
+            },
+        );
+        methods.add_method_mut("set_on_ally_attacked_fn", |_, cb, func: String| {
+            cb.add_func(FuncKind::OnAllyAttacked, func);
+            Ok(())
+        });
     }
 }
 
@@ -884,12 +946,12 @@ pub struct ScriptHitKind {
 
 #[derive(Clone)]
 struct DamageEntry {
-    kind: &'static str,
+    kind: String,
     amount: u32,
 }
 impl UserData for DamageEntry {
     fn add_methods<'lua, M: UserDataMethods<'lua, Self>>(methods: &mut M) {
-        methods.add_method("kind", |_, entry, ()| Ok(entry.kind));
+        methods.add_method("kind", |_, entry, ()| Ok(entry.kind.clone()));
         methods.add_method("amount", |_, entry, ()| Ok(entry.amount));
     }
 }
@@ -901,7 +963,7 @@ impl ScriptHitKind {
         for (kind, amount) in damage {
             total_damage += amount;
             entries.push(DamageEntry {
-                kind: kind.to_str(),
+                kind: kind.id(),
                 amount,
             });
         }
@@ -175,6 +175,10 @@ fn check_for_bonus(effect: &ScriptAppliedEffect, kind: String) -> bool {
         "will" => Will(0),
         "concealment" => Concealment(0),
         "concealment_ignore" => ConcealmentIgnore(0),
+        "stealth" => Stealth(0),
+        "detection" => Detection(0),
+        "mechanics" => Mechanics(0),
+        "reactions" => Reactions(0),
         "crit_chance" => CritChance(0),
         "hit_threshold" => HitThreshold(0),
         "graze_threshold" => GrazeThreshold(0),
@@ -798,6 +802,10 @@ fn add_num_bonus(
         "will" => Will(amount_int),
         "concealment" => Concealment(amount_int),
         "concealment_ignore" => ConcealmentIgnore(amount_int),
+        "stealth" => Stealth(amount_int),
+        "detection" => Detection(amount_int),
+        "mechanics" => Mechanics(amount_int),
+        "reactions" => Reactions(amount_int),
         "crit_chance" => CritChance(amount_int),
         "hit_threshold" => HitThreshold(amount_int),
         "graze_threshold" => GrazeThreshold(amount_int),
@@ -23,7 +23,7 @@ use rlua::{self, Context, UserData, UserDataMethods};
 
 use crate::{ability_state::DisabledReason, dist, is_within_attack_dist, is_within_touch_dist};
 use crate::{ai, animation, entity_attack_handler, script::*, AreaFeedbackText};
-use crate::{area_feedback_text::ColorKind, EntityState, GameState, Location};
+use crate::{area_feedback_text::ColorKind, EntityState, GameState, Location, ReadiedAction};
 use sulis_core::config::Config;
 use sulis_core::resource::ResourceSet;
 use sulis_core::util::ExtInt;
@@ -142,6 +142,33 @@ use sulis_module::{
 /// Returns true if this entity is a member of the player's party (or if it is the player),
 /// false otherwise.
 ///
+/// # `reactions_remaining() -> Int`
+/// Returns the number of out of turn reactions this entity may still make before the
+/// start of its next turn.
+///
+/// # `use_reaction() -> Bool`
+/// Uses up one of this entity's reactions.  Returns false if the entity had no reactions
+/// remaining, in which case reaction scripts should not take any action.
+///
+/// # `ready_attack() -> Bool`
+/// Readies a weapon attack, which this entity will make against the next hostile to
+/// enter its reach before the start of its next turn.  This entity must be the current
+/// entity in combat.  Returns true if the attack was readied.  Readying does not end the
+/// entity's turn.
+///
+/// # `ready_ability(ability: ScriptAbility, func: String) -> Bool`
+/// Readies the `ability`, as `ready_attack`.  When triggered, `func` is called in the
+/// ability's script, with the entering hostile as the target.
+///
+/// # `awareness() -> String`
+/// Returns the awareness level of this entity, one of `unaware`, `suspicious`, or
+/// `alerted`.  Suspicious entities have heard or glimpsed a hostile but have not yet
+/// located it.
+///
+/// # `last_known_position() -> Table`
+/// Returns a table with `x` and `y` elements for the position where this entity last
+/// noticed a hostile, or `nil` if it is not aware of any such position.
+///
 /// # `use_ability(ability: ScriptAbility, allow_invalid: Bool (Optional)) -> Bool`
 /// The parent entity attempts to use the `ability`.  Returns true if the ability use was
 /// successful, false if it was not.  After activating, the script will often need to handle
@@ -762,6 +789,58 @@ impl UserData for ScriptEntity {
             Ok(is_member)
         });
 
+        methods.add_method("reactions_remaining", |_, entity, ()| {
+            let entity = entity.try_unwrap()?;
+            let remaining = entity.borrow().actor.reactions_remaining();
+            Ok(remaining)
+        });
+
+        methods.add_method("use_reaction", |_, entity, ()| {
+            let entity = entity.try_unwrap()?;
+            let result = entity.borrow_mut().actor.use_reaction();
+            Ok(result)
+        });
+
+        methods.add_method("ready_attack", |_, entity, ()| {
+            let index = entity.try_unwrap()?.borrow().index();
+            let mgr = GameState::turn_manager();
+            let result = mgr.borrow_mut().ready_action(index, ReadiedAction::Attack);
+            Ok(result)
+        });
+
+        methods.add_method(
+            "ready_ability",
+            |_, entity, (ability, func): (ScriptAbility, String)| {
+                let index = entity.try_unwrap()?.borrow().index();
+                let action = ReadiedAction::Ability {
+                    id: ability.id,
+                    func,
+                };
+                let mgr = GameState::turn_manager();
+                let result = mgr.borrow_mut().ready_action(index, action);
+                Ok(result)
+            },
+        );
+
+        methods.add_method("awareness", |_, entity, ()| {
+            let entity = entity.try_unwrap()?;
+            let level = entity.borrow().awareness().level();
+            Ok(level.to_str())
+        });
+
+        methods.add_method("last_known_position", |lua, entity, ()| {
+            let entity = entity.try_unwrap()?;
+            let pos = match entity.borrow().awareness().last_known_pos() {
+                None => return Ok(None),
+                Some(pos) => pos,
+            };
+
+            let table = lua.create_table()?;
+            table.set("x", pos.x)?;
+            table.set("y", pos.y)?;
+            Ok(Some(table))
+        });
+
         methods.add_method(
             "use_ability",
             |_, entity, (ability, allow_invalid): (ScriptAbility, Option<bool>)| {
@@ -1219,7 +1298,7 @@ impl UserData for ScriptEntity {
                 let parent = entity.try_unwrap()?;
 
                 let damage_kind = match damage_kind {
-                    None => DamageKind::Raw,
+                    None => DamageKind::default_kind(),
                     Some(ref kind) => DamageKind::unwrap_from_str(kind),
                 };
                 let attack_kind = AttackKind::from_str(&attack_kind, &accuracy_kind);
@@ -1693,51 +1772,25 @@ fn create_stats_table<'a>(
     stats.set("current_ap", parent.actor.ap())?;
     stats.set("current_xp", parent.actor.xp())?;
 
-    stats.set("strength", src.attributes.strength)?;
-    stats.set("dexterity", src.attributes.dexterity)?;
-    stats.set("endurance", src.attributes.endurance)?;
-    stats.set("perception", src.attributes.perception)?;
-    stats.set("intellect", src.attributes.intellect)?;
-    stats.set("wisdom", src.attributes.wisdom)?;
-
-    {
-        use self::Attribute::*;
-        stats.set(
-            "strength_bonus",
-            src.attributes.bonus(Strength, rules.base_attribute),
-        )?;
-        stats.set(
-            "dexterity_bonus",
-            src.attributes.bonus(Dexterity, rules.base_attribute),
-        )?;
-        stats.set(
-            "endurance_bonus",
-            src.attributes.bonus(Endurance, rules.base_attribute),
-        )?;
-        stats.set(
-            "perception_bonus",
-            src.attributes.bonus(Perception, rules.base_attribute),
-        )?;
-        stats.set(
-            "intellect_bonus",
-            src.attributes.bonus(Intellect, rules.base_attribute),
-        )?;
+    for attr in Attribute::iter() {
+        let id = attr.id().to_lowercase();
+        stats.set(id.as_str(), src.attributes.get(attr))?;
         stats.set(
-            "wisdom_bonus",
-            src.attributes.bonus(Wisdom, rules.base_attribute),
+            format!("{id}_bonus"),
+            src.attributes.bonus(attr, rules.base_attribute),
         )?;
     }
 
     stats.set("base_armor", src.armor.base())?;
     let armor = lua.create_table()?;
     for kind in DamageKind::iter() {
-        armor.set(kind.to_str(), src.armor.amount(*kind))?;
+        armor.set(kind.id(), src.armor.amount(kind))?;
     }
     stats.set("armor", armor)?;
 
     let resistance = lua.create_table()?;
     for kind in DamageKind::iter() {
-        resistance.set(kind.to_str(), src.resistance.amount(*kind))?;
+        resistance.set(kind.id(), src.resistance.amount(kind))?;
     }
     stats.set("resistance", resistance)?;
 
@@ -1762,6 +1815,10 @@ fn create_stats_table<'a>(
 
     stats.set("concealment", src.concealment)?;
     stats.set("concealment_ignore", src.concealment_ignore)?;
+    stats.set("stealth", src.stealth)?;
+    stats.set("detection", src.detection)?;
+    stats.set("mechanics", src.mechanics)?;
+    stats.set("reactions", src.reactions)?;
     stats.set("crit_chance", src.crit_chance)?;
     stats.set("graze_threshold", src.graze_threshold)?;
     stats.set("hit_threshold", src.hit_threshold)?;
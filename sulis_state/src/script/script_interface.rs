@@ -15,6 +15,7 @@
 //  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
 
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::rc::Rc;
 
 use rlua::{self, UserData, UserDataMethods};
@@ -23,7 +24,7 @@ use crate::script::*;
 use crate::{animation::Anim, AreaState, EntityState, GameState, Location};
 use sulis_core::{config::Config};
 use sulis_module::on_trigger::{self, QuestEntryState};
-use sulis_module::{Faction, ItemState, Module, OnTrigger, Time};
+use sulis_module::{DamageKind, Faction, ItemState, Module, OnTrigger, Time};
 
 /// The ScriptInterface, accessible in all Lua scripts as the global `game`.
 /// The following methods are available on this object (documentation WIP):
@@ -261,6 +262,29 @@ use sulis_module::{Faction, ItemState, Module, OnTrigger, Time};
 /// Toggles the enabled / disabled state of the prop at `x`, `y`.  See `enable_prop_at` and
 /// `disable_prop_at`
 ///
+/// # `lock_prop_at(x: Int, y: Int, area_id: String (Optional))`
+/// Locks the door or container at `x`, `y`.  This only has an effect on props
+/// that define a lock.
+///
+/// # `unlock_prop_at(x: Int, y: Int, area_id: String (Optional))`
+/// Unlocks the door or container at `x`, `y`, without requiring a key or check.
+///
+/// # `add_surface_type(id: String, points: Table, area_id: String (Optional))`
+/// Adds the surface type with the specified `id` at each of the `points`, which
+/// should be a table of tables with `x` and `y` coordinates.  Where a surface is
+/// already present, the two will interact as defined in the surface type
+/// definitions, i.e. fire on top of water will be extinguished.
+///
+/// # `damage_surface_types(damage_kind: String, points: Table, area_id: String (Optional))`
+/// Applies damage of the specified `damage_kind` to any surfaces at the `points`.
+/// This may transform the surfaces, i.e. fire damage will ignite oil.
+///
+/// # `remove_surface_types(points: Table, area_id: String (Optional))`
+/// Removes any surfaces present at the specified `points`.
+///
+/// # `surface_type_at(x: Int, y: Int, area_id: String (Optional)) -> String`
+/// Returns the ID of the surface type at `x`, `y`, or nil if there is none.
+///
 /// # `say_line(line: String, target: ScriptEntity (Optional))`
 /// The specified `target`, or the player if no target is specified, will say the line
 /// of text specified by `line`.  This is represented by the text appearing on the main
@@ -290,6 +314,11 @@ use sulis_module::{Faction, ItemState, Module, OnTrigger, Time};
 /// asynchronously on the next frame, so the remaineder of this script script will execute
 /// immediately.
 ///
+/// # `start_sequence(id: String)`
+/// Starts the sequence with the specified `id` in the current area.  This blocks the
+/// user interface until the sequence is complete.  Like `show_cutscene`, the sequence
+/// is launched on the next frame.
+///
 /// # `exit_to_menu()`
 /// Causes the game to exit to the main menu.
 ///
@@ -828,6 +857,73 @@ impl UserData for ScriptInterface {
             },
         );
 
+        methods.add_method(
+            "lock_prop_at",
+            |_, _, (x, y, id): (i32, i32, Option<String>)| set_prop_locked_at(x, y, id, true),
+        );
+
+        methods.add_method(
+            "unlock_prop_at",
+            |_, _, (x, y, id): (i32, i32, Option<String>)| set_prop_locked_at(x, y, id, false),
+        );
+
+        methods.add_method(
+            "add_surface_type",
+            |_, _, (id, points, area): (String, Vec<HashMap<String, i32>>, Option<String>)| {
+                let surface_type = match Module::surface_type(&id) {
+                    None => {
+                        warn!("Unable to add surface type '{}': not found", id);
+                        return Ok(());
+                    }
+                    Some(surface_type) => surface_type,
+                };
+                let points = unwrap_points(points)?;
+                let round = GameState::turn_manager().borrow().current_round();
+                let area_state = get_area(area)?;
+                area_state
+                    .borrow_mut()
+                    .add_surface_type(&surface_type, &points, round);
+                Ok(())
+            },
+        );
+
+        methods.add_method(
+            "damage_surface_types",
+            |_, _, (kind, points, area): (String, Vec<HashMap<String, i32>>, Option<String>)| {
+                let kind = DamageKind::unwrap_from_str(&kind);
+                let points = unwrap_points(points)?;
+                let round = GameState::turn_manager().borrow().current_round();
+                let area_state = get_area(area)?;
+                area_state
+                    .borrow_mut()
+                    .damage_surface_types(kind, &points, round);
+                Ok(())
+            },
+        );
+
+        methods.add_method(
+            "remove_surface_types",
+            |_, _, (points, area): (Vec<HashMap<String, i32>>, Option<String>)| {
+                let points = unwrap_points(points)?;
+                let area_state = get_area(area)?;
+                area_state.borrow_mut().remove_surface_types(&points);
+                Ok(())
+            },
+        );
+
+        methods.add_method(
+            "surface_type_at",
+            |_, _, (x, y, area): (i32, i32, Option<String>)| {
+                let area_state = get_area(area)?;
+                let area_state = area_state.borrow();
+                let id = area_state
+                    .surface_layer()
+                    .get(x, y)
+                    .map(|tile| tile.surface_type.id.to_string());
+                Ok(id)
+            },
+        );
+
         methods.add_method(
             "say_line",
             |_, _, (line, target): (String, Option<ScriptEntity>)| {
@@ -880,6 +976,13 @@ impl UserData for ScriptInterface {
             Ok(())
         });
 
+        methods.add_method("start_sequence", |_, _, id: String| {
+            let pc = GameState::player();
+            let cb = OnTrigger::StartSequence(id);
+            GameState::add_ui_callback(vec![cb], &pc, &pc);
+            Ok(())
+        });
+
         methods.add_method("exit_to_menu", |_, _, ()| {
             let pc = GameState::player();
             let cb = OnTrigger::ExitToMenu;
@@ -1080,6 +1183,38 @@ fn get_area(id: Option<String>) -> Result<Rc<RefCell<AreaState>>> {
     }
 }
 
+fn unwrap_points(points: Vec<HashMap<String, i32>>) -> Result<Vec<Point>> {
+    let mut result = Vec::new();
+    for point in points {
+        let (x, y) = script_entity::unwrap_point(point)?;
+        result.push(Point::new(x, y));
+    }
+    Ok(result)
+}
+
+fn set_prop_locked_at(x: i32, y: i32, id: Option<String>, locked: bool) -> Result<()> {
+    let area_state = get_area(id)?;
+    let mut area_state = area_state.borrow_mut();
+    let index = match area_state.props().index_at(x, y) {
+        None => {
+            warn!("Unable to find prop at {},{}", x, y);
+            return Ok(());
+        }
+        Some(index) => index,
+    };
+
+    let prop = area_state.props_mut().get_mut(index);
+    if prop.prop.lock.is_none() {
+        warn!(
+            "Prop '{}' at {},{} does not have a lock",
+            prop.prop.id, x, y
+        );
+        return Ok(());
+    }
+    prop.set_locked(locked);
+    Ok(())
+}
+
 fn entities_with_ids(ids: Vec<String>) -> Vec<ScriptEntity> {
     let mut result = Vec::new();
 
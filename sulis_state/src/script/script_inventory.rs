@@ -165,11 +165,11 @@ impl UserData for ScriptInventory {
             match item.kind {
                 ItemKind::Armor { kind } => {
                     stats.set("kind", "armor")?;
-                    stats.set("armor_kind", format!("{kind:?}").to_lowercase())?;
+                    stats.set("armor_kind", kind.id().to_lowercase())?;
                 }
                 ItemKind::Weapon { kind } => {
                     stats.set("kind", "weapon")?;
-                    stats.set("weapon_kind", format!("{kind:?}").to_lowercase())?;
+                    stats.set("weapon_kind", kind.id().to_lowercase())?;
                 }
                 ItemKind::Other => stats.set("kind", "other")?,
             }
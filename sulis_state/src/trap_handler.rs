@@ -0,0 +1,246 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2019 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::rc::Rc;
+
+use sulis_core::util::gen_rand;
+use sulis_module::prop::{Interactive, TrapEffect};
+use sulis_module::{DamageList, HitFlags, HitKind, Module};
+
+use crate::area_feedback_text::ColorKind;
+use crate::{dist, ActorState, AreaFeedbackText, AreaState, CombatLogKind, EntityState, GameState};
+
+/// Makes a detection check by `entity` against each hidden trap in `props`
+/// that is within the trap detection distance.  Each party member only gets
+/// one check against a given trap.  Returns the combat log text for each
+/// check, as the turn manager may be borrowed while this is called.
+pub(crate) fn check_detection(
+    area_state: &mut AreaState,
+    entity: &EntityState,
+    props: &HashSet<usize>,
+) -> Vec<String> {
+    let max_dist = Module::rules().stealth.trap_detection_dist;
+    let mut checks = Vec::new();
+
+    for index in props.iter() {
+        let index = *index;
+        let difficulty = {
+            let prop = area_state.props().get(index);
+            if !prop.is_hidden() || dist(entity, prop) > max_dist {
+                continue;
+            }
+
+            match prop.prop.interactive {
+                Interactive::Trap {
+                    detect_difficulty, ..
+                } => detect_difficulty,
+                _ => continue,
+            }
+        };
+
+        let prop = area_state.props_mut().get_mut(index);
+        if !prop.add_detect_attempt(entity.unique_id()) {
+            continue;
+        }
+
+        let detection = entity.actor.stats.detection;
+        let roll = gen_rand(1, 21);
+        let success = roll + detection >= difficulty;
+
+        let text = format!(
+            "{} {} {}: {} + {} vs {}",
+            entity.actor.actor.name,
+            if success {
+                "detects"
+            } else {
+                "fails to detect"
+            },
+            prop.prop.name,
+            roll,
+            detection,
+            difficulty,
+        );
+        checks.push(text);
+
+        if !success {
+            continue;
+        }
+
+        info!(
+            "'{}' detected trap '{}'",
+            entity.actor.actor.name, prop.prop.id
+        );
+        prop.set_trap_detected();
+
+        let mut feedback = AreaFeedbackText::with_prop(area_state.props().get(index), area_state);
+        feedback.add_entry("Trap Detected".to_string(), ColorKind::Info);
+        area_state.add_feedback_text(feedback);
+    }
+
+    checks
+}
+
+/// Springs each of the specified traps on the party member that moved onto it
+pub(crate) fn spring_traps(traps: Vec<(Rc<RefCell<EntityState>>, usize)>) {
+    for (entity, index) in traps {
+        let area_state = match GameState::get_area_state(&entity.borrow().location.area_id) {
+            None => continue,
+            Some(area_state) => area_state,
+        };
+
+        let prop = {
+            let mut area_state = area_state.borrow_mut();
+            if !area_state.props().index_valid(index) {
+                continue;
+            }
+
+            let prop = area_state.props_mut().get_mut(index);
+            if !prop.is_trap_armed() {
+                continue;
+            }
+            prop.set_trap_sprung();
+            Rc::clone(&prop.prop)
+        };
+
+        let effect = match prop.interactive {
+            Interactive::Trap { ref effect, .. } => effect,
+            _ => continue,
+        };
+
+        info!(
+            "'{}' sprung trap '{}'",
+            entity.borrow().actor.actor.name,
+            prop.id
+        );
+        let text = format!("{} springs {}", entity.borrow().actor.actor.name, prop.name);
+        GameState::add_combat_log_entry(CombatLogKind::Check, text);
+
+        fire_trap(&entity, &area_state, effect);
+    }
+}
+
+/// Attempts to disarm the detected trap at `index` in the area of `entity`
+/// with a mechanics check.  A failed check springs the trap.  Returns true
+/// if the trap was disarmed.
+pub fn try_disarm(entity: &Rc<RefCell<EntityState>>, index: usize) -> bool {
+    let area_state = match GameState::get_area_state(&entity.borrow().location.area_id) {
+        None => return false,
+        Some(area_state) => area_state,
+    };
+
+    let prop = {
+        let area_state = area_state.borrow();
+        if !area_state.props().index_valid(index) {
+            return false;
+        }
+
+        let prop = area_state.props().get(index);
+        if prop.is_hidden() || !prop.is_trap_armed() {
+            return false;
+        }
+        Rc::clone(&prop.prop)
+    };
+
+    let (difficulty, effect) = match prop.interactive {
+        Interactive::Trap {
+            disarm_difficulty: Some(difficulty),
+            ref effect,
+            ..
+        } => (difficulty, effect),
+        _ => return false,
+    };
+
+    let mechanics = entity.borrow().actor.stats.mechanics;
+    let roll = gen_rand(1, 21);
+    let success = roll + mechanics >= difficulty;
+
+    let text = format!(
+        "{} {} {}: {} + {} vs {}",
+        entity.borrow().actor.actor.name,
+        if success {
+            "disarms"
+        } else {
+            "fails to disarm"
+        },
+        prop.name,
+        roll,
+        mechanics,
+        difficulty,
+    );
+    GameState::add_combat_log_entry(CombatLogKind::Check, text);
+
+    let text = if success { "Disarmed" } else { "Failed" };
+    {
+        let mut area_state = area_state.borrow_mut();
+        let mut feedback = AreaFeedbackText::with_prop(area_state.props().get(index), &area_state);
+        let color = if success {
+            ColorKind::Info
+        } else {
+            ColorKind::Miss
+        };
+        feedback.add_entry(text.to_string(), color);
+        area_state.add_feedback_text(feedback);
+
+        let prop = area_state.props_mut().get_mut(index);
+        if success {
+            prop.set_trap_disarmed();
+        } else {
+            prop.set_trap_sprung();
+        }
+    }
+
+    if !success {
+        fire_trap(entity, &area_state, effect);
+    }
+
+    success
+}
+
+/// Applies the damage and triggers of a trap to `entity`
+pub(crate) fn fire_trap(
+    entity: &Rc<RefCell<EntityState>>,
+    area_state: &Rc<RefCell<AreaState>>,
+    effect: &TrapEffect,
+) {
+    if let Some(damage) = effect.damage {
+        let rules = Module::rules();
+        let amount = {
+            let stats = &entity.borrow().actor.stats;
+            let damage = DamageList::from(damage);
+            rules.roll_damage(&damage, &stats.armor, &stats.resistance, 1.0)
+        };
+
+        if !amount.is_empty() {
+            EntityState::remove_hp(entity, entity, HitKind::Auto, amount.clone());
+        }
+
+        let feedback = AreaFeedbackText::with_damage(
+            &entity.borrow(),
+            &area_state.borrow(),
+            HitKind::Auto,
+            HitFlags::default(),
+            &amount,
+        );
+        area_state.borrow_mut().add_feedback_text(feedback);
+        ActorState::check_death(entity, entity);
+    }
+
+    if !effect.on_trigger.is_empty() {
+        GameState::add_ui_callback(effect.on_trigger.clone(), entity, entity);
+    }
+}
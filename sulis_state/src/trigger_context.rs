@@ -0,0 +1,90 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2019 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use sulis_module::{condition::ConditionContext, on_trigger::QuestEntryState, Attribute, Module};
+
+use crate::{EntityState, GameState};
+
+/// Evaluates trigger conditions against the live game state, with `pc` as
+/// the player and `target` as the other participant
+pub struct TriggerContext<'a> {
+    pub pc: &'a Rc<RefCell<EntityState>>,
+    pub target: &'a Rc<RefCell<EntityState>>,
+}
+
+impl ConditionContext for TriggerContext<'_> {
+    fn player_flag(&self, flag: &str) -> bool {
+        self.pc.borrow().has_custom_flag(flag)
+    }
+
+    fn target_flag(&self, flag: &str) -> bool {
+        self.target.borrow().has_custom_flag(flag)
+    }
+
+    fn player_num_flag(&self, flag: &str) -> f32 {
+        self.pc.borrow().get_num_flag(flag)
+    }
+
+    fn target_num_flag(&self, flag: &str) -> f32 {
+        self.target.borrow().get_num_flag(flag)
+    }
+
+    fn player_attribute(&self, attribute: Attribute) -> i32 {
+        self.pc.borrow().actor.stats.attributes.get(attribute) as i32
+    }
+
+    fn player_level(&self, class: Option<&str>) -> u32 {
+        let pc = self.pc.borrow();
+        match class {
+            None => pc.actor.actor.total_level,
+            Some(id) => match Module::class(id) {
+                None => 0,
+                Some(class) => pc.actor.actor.levels(&class),
+            },
+        }
+    }
+
+    fn player_has_ability(&self, id: &str) -> bool {
+        let pc = self.pc.borrow();
+        pc.actor
+            .actor
+            .abilities
+            .iter()
+            .any(|ability| ability.ability.id == id)
+    }
+
+    fn has_party_member(&self, id: &str) -> bool {
+        GameState::has_party_member(id)
+    }
+
+    fn has_party_item(&self, id: &str) -> bool {
+        GameState::party_stash().borrow().has_item(id)
+    }
+
+    fn party_coins(&self) -> i32 {
+        GameState::party_coins()
+    }
+
+    fn quest_state(&self, quest: &str, entry: Option<&str>) -> QuestEntryState {
+        match entry {
+            None => GameState::get_quest_state(quest.to_string()),
+            Some(entry) => GameState::get_quest_entry_state(quest.to_string(), entry.to_string()),
+        }
+    }
+}
@@ -21,9 +21,12 @@ use std::rc::Rc;
 use serde::{Serialize, Deserialize};
 
 use crate::script::{CallbackData, FuncKind, TriggeredCallback};
-use crate::{AreaState, ChangeListener, ChangeListenerList, Effect, EntityState, GameState};
+use crate::{
+    awareness, AreaState, ChangeListener, ChangeListenerList, CombatLogKind, Effect, EntityState,
+    GameState, Reaction, ReactionKind, ReadiedAction,
+};
 use sulis_core::{config::Config, util::{gen_rand, Point}};
-use sulis_module::{Faction, Module, Time, ROUND_TIME_MILLIS, OnTrigger};
+use sulis_module::{Faction, InitiativeMode, Module, OnTrigger, Time, ROUND_TIME_MILLIS};
 
 fn add_campaign_elapsed_callback(cbs: &mut Vec<Rc<CallbackData>>) {
     let script_data = match Module::campaign().on_round_elapsed_script {
@@ -62,6 +65,10 @@ pub struct TurnManager {
     effects_remove_next_update: Vec<usize>,
     entities_move_callback_next_update: HashSet<usize>,
     triggered_cbs_next_update: Vec<TriggeredCallback>,
+    searches_next_update: Vec<(usize, Point)>,
+    reactions_next_update: Vec<Reaction>,
+    turns_started: HashSet<usize>,
+    pub(crate) readied: HashMap<usize, ReadiedAction>,
     combat_active: bool,
 
     pub time_listeners: ChangeListenerList<Time>,
@@ -133,6 +140,8 @@ impl TurnManager {
         self.auras.clear();
         self.effects_remove_next_update.clear();
         self.triggered_cbs_next_update.clear();
+        self.turns_started.clear();
+        self.readied.clear();
         self.combat_active = false;
         self.listeners = ChangeListenerList::default();
         self.time_listeners = ChangeListenerList::default();
@@ -256,6 +265,95 @@ impl TurnManager {
         result
     }
 
+    /// Returns the suspicious entities that should move to investigate
+    /// their last known position, along with that position.
+    pub fn drain_searches(&mut self) -> Vec<(Rc<RefCell<EntityState>>, Point)> {
+        let searches: Vec<_> = self.searches_next_update.drain(..).collect();
+        if self.combat_active {
+            return Vec::new();
+        }
+
+        searches
+            .into_iter()
+            .filter_map(|(index, pos)| self.entity_checked(index).map(|e| (e, pos)))
+            .filter(|(entity, _)| {
+                let entity = entity.borrow();
+                !entity.actor.is_dead() && !entity.is_ai_active()
+            })
+            .collect()
+    }
+
+    /// Queues a reaction by the `reactor` against the `target`, to be
+    /// resolved on the next update.  Reactions only occur in combat.
+    pub fn queue_reaction(&mut self, kind: ReactionKind, reactor: usize, target: usize) {
+        if !self.combat_active {
+            return;
+        }
+
+        self.reactions_next_update.push(Reaction {
+            kind,
+            reactor,
+            target,
+            from: None,
+        });
+    }
+
+    /// Queues a reaction by the `reactor` against a `target` that has just
+    /// stepped out of its reach from the square at `from`
+    pub fn queue_left_threat_reaction(&mut self, reactor: usize, target: usize, from: Point) {
+        if !self.combat_active {
+            return;
+        }
+
+        self.reactions_next_update.push(Reaction {
+            kind: ReactionKind::TargetLeftThreat,
+            reactor,
+            target,
+            from: Some(from),
+        });
+    }
+
+    /// Queues a reaction of the specified kind from each entity currently
+    /// threatening the `target`
+    pub fn queue_reactions_from_threateners(&mut self, kind: ReactionKind, target: usize) {
+        let threateners = match self.entity_checked(target) {
+            None => return,
+            Some(entity) => entity.borrow().actor.p_stats().threatened_by().to_vec(),
+        };
+
+        for reactor in threateners {
+            self.queue_reaction(kind, reactor, target);
+        }
+    }
+
+    /// Readies an action for the entity with the specified index, which must
+    /// be the current entity.  The action fires the next time a hostile enters
+    /// the entity's reach, before the start of its next turn.  Returns false
+    /// if the action could not be readied.
+    pub fn ready_action(&mut self, index: usize, action: ReadiedAction) -> bool {
+        match self.current() {
+            Some(current) if current.borrow().index() == index => (),
+            _ => return false,
+        }
+
+        debug!("Entity {} readied {:?}", index, action);
+        self.readied.insert(index, action);
+        self.listeners.notify(self);
+        true
+    }
+
+    pub fn readied_action(&self, index: usize) -> Option<&ReadiedAction> {
+        self.readied.get(&index)
+    }
+
+    pub fn take_readied_action(&mut self, index: usize) -> Option<ReadiedAction> {
+        self.readied.remove(&index)
+    }
+
+    pub fn drain_reactions(&mut self) -> Vec<Reaction> {
+        self.reactions_next_update.drain(..).collect()
+    }
+
     #[must_use]
     pub fn update_entity_move_callbacks(&mut self) -> Vec<Rc<CallbackData>> {
         let mut cbs = Vec::new();
@@ -360,6 +458,10 @@ impl TurnManager {
             None
         };
 
+        if new_round {
+            entity.awareness_mut().decay();
+        }
+
         entity.actor.elapse_time(elapsed_millis, &self.effects);
         (entity.is_marked_for_removal(), cb)
     }
@@ -372,18 +474,155 @@ impl TurnManager {
             return Vec::new();
         }
 
-        let cbs = self.iterate_to_next_entity();
+        let cbs = self.iterate_to_next_entity(false);
         self.init_turn_for_current_entity(&mut GameState::area_state().borrow_mut());
 
         self.listeners.notify(self);
         cbs
     }
 
+    /// Moves the current entity after the next active entity in the order,
+    /// without ending its turn.  An entity may not delay past the end of the
+    /// round.  Returns None if the current entity could not delay.
+    #[must_use]
+    pub fn delay_current(&mut self) -> Option<Vec<Rc<CallbackData>>> {
+        if !self.combat_active {
+            return None;
+        }
+
+        // the current entity's effects move along with it, and are placed
+        // after the effects of the entity it delays behind
+        let end = self.trailing_effects_end(0);
+
+        let mut next = None;
+        for (pos, entry) in self.order.iter().enumerate().skip(end) {
+            match entry {
+                Entry::TurnChange => break,
+                Entry::Effect(_) => (),
+                Entry::Entity(index) => {
+                    if self.is_active_entity(*index) {
+                        next = Some(pos);
+                        break;
+                    }
+                }
+            }
+        }
+
+        let insert_pos = self.trailing_effects_end(next?) - end;
+        let entries: Vec<Entry> = self.order.drain(..end).collect();
+        for (i, entry) in entries.into_iter().enumerate() {
+            self.order.insert(insert_pos + i, entry);
+        }
+
+        let cbs = self.iterate_to_next_entity(true);
+        self.init_turn_for_current_entity(&mut GameState::area_state().borrow_mut());
+
+        self.listeners.notify(self);
+        Some(cbs)
+    }
+
+    /// In group initiative mode, makes the specified party member the
+    /// current entity, provided it has not yet finished its turn in the
+    /// current phase.  Returns true if the party member was activated.
+    pub fn activate_in_phase(&mut self, entity: &Rc<RefCell<EntityState>>) -> bool {
+        if !self.combat_active || Module::rules().initiative_mode != InitiativeMode::Group {
+            return false;
+        }
+
+        let index = {
+            let entity = entity.borrow();
+            if !entity.is_party_member() || entity.actor.is_dead() {
+                return false;
+            }
+            entity.index()
+        };
+
+        let phase_len = self.current_phase_len();
+        let pos = self
+            .order
+            .iter()
+            .take(phase_len)
+            .position(|entry| matches!(entry, Entry::Entity(i) if *i == index));
+
+        let pos = match pos {
+            None | Some(0) => return false,
+            Some(pos) => pos,
+        };
+
+        // the effects directly after an entity are updated when its turn
+        // ends, so they move along with it
+        let end = self.trailing_effects_end(pos);
+        let entries: Vec<Entry> = self.order.drain(pos..end).collect();
+        for entry in entries.into_iter().rev() {
+            self.order.push_front(entry);
+        }
+        self.init_turn_for_current_entity(&mut GameState::area_state().borrow_mut());
+
+        self.listeners.notify(self);
+        true
+    }
+
+    /// Returns the position just past the entity at `pos` and the block of
+    /// effects directly following it in the order.
+    fn trailing_effects_end(&self, pos: usize) -> usize {
+        let mut end = pos + 1;
+        while let Some(Entry::Effect(_)) = self.order.get(end) {
+            end += 1;
+        }
+        end
+    }
+
+    /// Returns true if the specified entity takes its turn in the current
+    /// phase and has not yet finished it.  In individual initiative mode,
+    /// only the current entity is in the current phase.
+    pub fn is_in_current_phase(&self, entity: &Rc<RefCell<EntityState>>) -> bool {
+        if !self.combat_active {
+            return false;
+        }
+
+        let index = entity.borrow().index();
+        self.order
+            .iter()
+            .take(self.current_phase_len())
+            .any(|entry| matches!(entry, Entry::Entity(i) if *i == index))
+    }
+
+    /// Returns the number of entries at the front of the order that are part
+    /// of the current phase.
+    fn current_phase_len(&self) -> usize {
+        let faction = match self.current() {
+            None => return 0,
+            Some(entity) => entity.borrow().actor.faction(),
+        };
+
+        if Module::rules().initiative_mode != InitiativeMode::Group {
+            return 1;
+        }
+
+        let mut len = 0;
+        for entry in self.order.iter() {
+            match entry {
+                Entry::TurnChange => break,
+                Entry::Effect(_) => (),
+                Entry::Entity(index) => {
+                    let entity = self.entities[*index].as_ref().unwrap().borrow();
+                    let active = entity.is_party_member() || entity.is_ai_active();
+                    if active && entity.actor.faction() != faction {
+                        break;
+                    }
+                }
+            }
+            len += 1;
+        }
+
+        len
+    }
+
     fn init_turn_for_current_entity(&mut self, area_state: &mut AreaState) {
-        let current = match self.order.front() {
+        let (index, current) = match self.order.front() {
             Some(Entry::Entity(index)) => match self.entities[*index] {
                 None => unreachable!(),
-                Some(ref entity) => entity,
+                Some(ref entity) => (*index, entity),
             },
             _ => unreachable!(),
         };
@@ -409,6 +648,13 @@ impl TurnManager {
             GameState::add_ui_callback(vec![cb], current, current);
         }
 
+        // a party member switched away from in group initiative mode, or an
+        // entity that delayed, keeps its remaining AP when it becomes current
+        if !self.turns_started.insert(index) {
+            return;
+        }
+        self.readied.remove(&index);
+
         let mut current = current.borrow_mut();
         current.actor.init_turn();
         current.actor.elapse_time(ROUND_TIME_MILLIS, &self.effects);
@@ -430,10 +676,12 @@ impl TurnManager {
         }
     }
 
+    /// Advances the order to the next active entity.  If `current_ended` is
+    /// true, the previously current entity has already been moved elsewhere
+    /// in the order without ending its turn.
     #[must_use]
-    fn iterate_to_next_entity(&mut self) -> Vec<Rc<CallbackData>> {
+    fn iterate_to_next_entity(&mut self, mut current_ended: bool) -> Vec<Rc<CallbackData>> {
         let mut cbs = Vec::new();
-        let mut current_ended = false;
 
         loop {
             if current_ended && self.current_is_active_entity() {
@@ -456,6 +704,7 @@ impl TurnManager {
                     }
                 }
                 Entry::Entity(index) => {
+                    self.turns_started.remove(&index);
                     if let Some(entity) = &self.entities[index] {
                         entity.borrow_mut().actor.end_turn();
                         if let Some(cb) = entity.borrow().ai_callbacks() {
@@ -479,15 +728,22 @@ impl TurnManager {
 
     fn current_is_active_entity(&self) -> bool {
         if let Some(Entry::Entity(index)) = self.order.front() {
-            if let Some(entity) = &self.entities[*index] {
-                let entity = entity.borrow();
-                return entity.is_party_member() || entity.is_ai_active();
-            }
+            return self.is_active_entity(*index);
         }
 
         false
     }
 
+    fn is_active_entity(&self, index: usize) -> bool {
+        match &self.entities[index] {
+            None => false,
+            Some(entity) => {
+                let entity = entity.borrow();
+                entity.is_party_member() || entity.is_ai_active()
+            }
+        }
+    }
+
     pub fn check_ai_activation_for_party(&mut self, area_state: &mut AreaState) {
         for entity in GameState::party() {
             self.check_ai_activation(&entity, area_state);
@@ -499,9 +755,7 @@ impl TurnManager {
         mover: &Rc<RefCell<EntityState>>,
         area_state: &mut AreaState,
     ) {
-        if mover.borrow().actor.stats.hidden {
-            return;
-        }
+        let mover_hidden = mover.borrow().actor.stats.hidden;
 
         let mut groups_to_activate: HashSet<usize> = HashSet::new();
         let mut state_changed = false;
@@ -531,7 +785,25 @@ impl TurnManager {
             }
 
             let mover = mover.borrow();
-            if !area_state.has_visibility(&mover, &entity)
+            if mover_hidden {
+                // hidden entities must be noticed by the AI, rather than
+                // simply being seen by or seeing them
+                if entity.is_party_member() {
+                    continue;
+                }
+                if !area_state.has_visibility(&entity, &mover) {
+                    continue;
+                }
+                if !entity.awareness().in_vision_cone(&entity, &mover) {
+                    continue;
+                }
+
+                let points = awareness::detection_roll(&entity, &mover);
+                let pos = mover.location.to_point();
+                if !entity.awareness_mut().add_points(points, pos) {
+                    continue;
+                }
+            } else if !area_state.has_visibility(&mover, &entity)
                 && !area_state.has_visibility(&entity, &mover)
             {
                 continue;
@@ -616,12 +888,56 @@ impl TurnManager {
 
         trace!("Activate AI for {}", entity.actor.actor.name);
         entity.set_ai_active(true);
+        entity.awareness_mut().set_alerted();
 
         if let Some(group) = entity.ai_group() {
             groups.insert(group);
         }
     }
 
+    /// Alerts hostile, inactive AI entities within `radius` of the `source`
+    /// to its position.  Entities that become suspicious will search for
+    /// the source once they are able to.
+    pub fn make_noise(
+        &mut self,
+        source: &Rc<RefCell<EntityState>>,
+        radius: f32,
+        area_state: &AreaState,
+    ) {
+        let source = source.borrow();
+        let pos = source.location.to_point();
+
+        for (index, entity) in self.entities.iter().enumerate() {
+            let entity = match entity {
+                None => continue,
+                Some(ref entity) => entity,
+            };
+
+            if index == source.index() {
+                continue;
+            }
+
+            let mut entity = entity.borrow_mut();
+            if entity.is_party_member() || entity.is_ai_active() || entity.actor.is_dead() {
+                continue;
+            }
+            if entity.actor.actor.ai.is_none() {
+                continue;
+            }
+            if !entity.is_hostile(&source) || !entity.location.is_in(area_state) {
+                continue;
+            }
+            if crate::dist(&*entity, &*source) > radius {
+                continue;
+            }
+
+            if entity.awareness_mut().hear(pos) {
+                trace!("'{}' heard noise at {:?}", entity.actor.actor.name, pos);
+                self.searches_next_update.push((index, pos));
+            }
+        }
+    }
+
     pub fn is_combat_active(&self) -> bool {
         self.combat_active
     }
@@ -633,6 +949,8 @@ impl TurnManager {
 
         info!("Setting combat mode active = {}", active);
         self.combat_active = active;
+        self.turns_started.clear();
+        self.readied.clear();
 
         if !active {
             self.end_combat();
@@ -682,6 +1000,7 @@ impl TurnManager {
             entity.set_ai_active(false);
 
             if !entity.is_party_member() {
+                entity.awareness_mut().calm();
                 continue;
             }
 
@@ -716,23 +1035,29 @@ impl TurnManager {
 
     fn initiate_combat(&mut self) {
         // first, compute initiative for each entry in the list
-        let initiative_roll_max = Module::rules().initiative_roll_max;
+        let rules = Module::rules();
+        let initiative_roll_max = rules.initiative_roll_max;
         let mut initiative = vec![0; self.order.len()];
+        let mut factions = vec![None; self.order.len()];
+        let mut phase_initiative: HashMap<Faction, i32> = HashMap::new();
         let mut index = initiative.len();
         let mut last_initiative = 0;
+        let mut last_faction = None;
         for entry in self.order.iter().rev() {
             index -= 1;
             match entry {
                 Entry::Entity(entity_index) => {
-                    let base = self.entities[*entity_index]
-                        .as_ref()
-                        .unwrap()
-                        .borrow()
-                        .actor
-                        .stats
-                        .initiative;
+                    let entity = self.entities[*entity_index].as_ref().unwrap().borrow();
+                    let base = entity.actor.stats.initiative;
                     last_initiative = base + gen_rand(0, initiative_roll_max);
                     initiative[index] = 2 * last_initiative;
+
+                    let faction = entity.actor.faction();
+                    last_faction = Some(faction);
+                    if entity.is_party_member() || entity.is_ai_active() {
+                        let phase = phase_initiative.entry(faction).or_insert(last_initiative);
+                        *phase = (*phase).max(last_initiative);
+                    }
                 }
                 Entry::Effect(_) => {
                     // this effect should come just before the associated entity
@@ -740,12 +1065,39 @@ impl TurnManager {
                 }
                 Entry::TurnChange => (),
             }
+            factions[index] = last_faction;
         }
 
-        let mut entries: Vec<_> = self.order.drain(..).zip(initiative).collect();
-        entries.sort_by_key(|&(_, initiative)| initiative);
+        // in group mode, each faction acts as a phase in order of its best
+        // roll, with the party winning ties
+        let phase_key = |faction: Option<Faction>, initiative: i32| match rules.initiative_mode {
+            InitiativeMode::Individual => (0, 0),
+            InitiativeMode::Group => match faction {
+                None => (initiative / 2, 0),
+                Some(faction) => {
+                    let phase = phase_initiative.get(&faction).copied();
+                    let rank = match faction {
+                        Faction::Hostile => 0,
+                        Faction::Neutral => 1,
+                        Faction::Friendly => 2,
+                    };
+                    (phase.unwrap_or(initiative / 2), rank)
+                }
+            },
+        };
+
+        let mut entries: Vec<_> = self
+            .order
+            .drain(..)
+            .zip(initiative)
+            .zip(factions)
+            .map(|((entry, initiative), faction)| {
+                (entry, phase_key(faction, initiative), initiative)
+            })
+            .collect();
+        entries.sort_by_key(|&(_, phase, initiative)| (phase, initiative));
 
-        for (entry, _) in entries {
+        for (entry, _, _) in entries {
             if let Entry::TurnChange = entry {
                 continue;
             }
@@ -934,6 +1286,15 @@ impl TurnManager {
         let bonuses = self.effect(index).bonuses().clone();
         entity.borrow_mut().actor.add_effect(index, bonuses);
 
+        if self.effect(index).ui_visible {
+            let text = format!(
+                "{} gains {}",
+                entity.borrow().actor.actor.name,
+                self.effect(index).name
+            );
+            self.add_combat_log_entry(text);
+        }
+
         index
     }
 
@@ -964,6 +1325,16 @@ impl TurnManager {
         let cbs;
         let mut entities = HashSet::new();
         if let Some(effect) = &self.effects[index] {
+            let target = effect.entity.and_then(|i| self.entity_checked(i));
+            if let (true, Some(target)) = (effect.ui_visible, target) {
+                let text = format!(
+                    "{} expires on {}",
+                    effect.name,
+                    target.borrow().actor.actor.name
+                );
+                self.add_combat_log_entry(text);
+            }
+
             if let Some((area_id, points)) = effect.surface() {
                 let area = GameState::get_area_state(area_id).unwrap();
                 entities = area.borrow_mut().remove_surface(index, points);
@@ -993,6 +1364,12 @@ impl TurnManager {
         cbs
     }
 
+    fn add_combat_log_entry(&self, text: String) {
+        let log = GameState::combat_log();
+        log.borrow_mut()
+            .add(CombatLogKind::Effect, self.current_round(), text);
+    }
+
     fn check_encounter_cleared(&self, entity: &Rc<RefCell<EntityState>>) -> Option<usize> {
         let ai_group = match entity.borrow().ai_group() {
             None => return None,
@@ -23,10 +23,12 @@ use sulis_core::ui::{animation_state, Widget};
 use sulis_core::util::Point;
 use sulis_module::{
     area::{Destination, ToKind},
-    Faction, Module, ObjectSize, OnTrigger, Time, MOVE_TO_THRESHOLD,
+    prop, Faction, Module, ObjectSize, OnTrigger, Time, MOVE_TO_THRESHOLD,
+};
+use sulis_state::{can_attack, is_within, is_within_attack_dist};
+use sulis_state::{
+    lock_handler, trap_handler, AreaState, EntityState, GameState, PropState, ScriptCallback,
 };
-use sulis_state::{can_attack, is_within};
-use sulis_state::{AreaState, EntityState, GameState, PropState, ScriptCallback};
 
 pub fn get_action(x_f32: f32, y_f32: f32) -> Box<dyn ActionKind> {
     let (x, y) = (x_f32 as i32, y_f32 as i32);
@@ -76,6 +78,16 @@ fn get_prop_or_transition_action(x: i32, y: i32) -> Option<Box<dyn ActionKind>>
         return LootPropAction::create_if_valid(index, prop);
     }
 
+    if prop.is_destructible() && !prop.is_destroyed() {
+        return AttackPropAction::create_if_valid(index, prop);
+    }
+
+    if prop.is_trap() && !prop.is_hidden() && prop.is_trap_armed() {
+        if let Some(action) = DisarmTrapAction::create_if_valid(index, prop) {
+            return Some(action);
+        }
+    }
+
     if prop.is_door() {
         if !prop.is_active() {
             // open door action (if enabled)
@@ -222,7 +234,7 @@ impl ActionKind for SelectAction {
 
     fn fire_action(&mut self, _widget: &Rc<RefCell<Widget>>) -> bool {
         trace!("Firing select action.");
-        GameState::set_selected_party_member(Rc::clone(&self.target));
+        GameState::activate_party_member(Rc::clone(&self.target));
         false
     }
 }
@@ -350,6 +362,22 @@ impl ActionKind for DoorPropAction {
     }
 
     fn fire_action(&mut self, _widget: &Rc<RefCell<Widget>>) -> bool {
+        let locked = GameState::area_state()
+            .borrow()
+            .props()
+            .get(self.index)
+            .is_locked();
+        if locked {
+            let pc = match GameState::selected().first() {
+                None => return false,
+                Some(pc) => Rc::clone(pc),
+            };
+
+            if !lock_handler::try_unlock(&pc, self.index) {
+                return false;
+            }
+        }
+
         let area_state = GameState::area_state();
         let mut area_state = area_state.borrow_mut();
         area_state.toggle_prop_active(self.index);
@@ -357,6 +385,156 @@ impl ActionKind for DoorPropAction {
     }
 }
 
+struct AttackPropAction {
+    pc: Rc<RefCell<EntityState>>,
+    index: usize,
+    ap: i32,
+}
+
+impl AttackPropAction {
+    fn create_if_valid(index: usize, prop_state: &PropState) -> Option<Box<dyn ActionKind>> {
+        if !prop_state.is_destructible() || !prop_state.is_enabled() {
+            return None;
+        }
+
+        let pc = match GameState::selected().first() {
+            None => return None,
+            Some(pc) => Rc::clone(pc),
+        };
+
+        let ap = {
+            let pc = pc.borrow();
+            if !pc.actor.has_ap_to_attack() {
+                return None;
+            }
+            if pc.actor.stats.attack_disabled {
+                return None;
+            }
+            pc.actor.stats.attack_cost
+        };
+
+        if is_within_attack_dist(&pc.borrow(), prop_state) {
+            return Some(Box::new(AttackPropAction { pc, index, ap }));
+        }
+
+        let cb_action = Box::new(AttackPropAction {
+            pc: Rc::clone(&pc),
+            index,
+            ap,
+        });
+        let dist = pc.borrow().actor.stats.attack_distance();
+        MoveThenAction::create_if_valid(
+            &pc,
+            prop_state.location.to_point(),
+            &prop_state.prop.size,
+            dist,
+            cb_action,
+            animation_state::Kind::MouseAttack,
+        )
+    }
+}
+
+impl ActionKind for AttackPropAction {
+    fn cursor_state(&self) -> animation_state::Kind {
+        animation_state::Kind::MouseAttack
+    }
+
+    fn get_hover_info(&self) -> Option<ActionHoverInfo> {
+        let area_state = GameState::area_state();
+        let area_state = area_state.borrow();
+        let prop = area_state.props().get(self.index);
+        let point = prop.location.to_point();
+        let mut info = ActionHoverInfo::new(&prop.prop.size, point);
+        info.ap = self.ap;
+        info.total_ap = self.pc.borrow().actor.ap() as i32;
+        Some(info)
+    }
+
+    fn fire_action(&mut self, _widget: &Rc<RefCell<Widget>>) -> bool {
+        {
+            let area_state = GameState::area_state();
+            let area_state = area_state.borrow();
+            if !area_state.props().index_valid(self.index) {
+                return false;
+            }
+
+            let pc = self.pc.borrow();
+            let prop = area_state.props().get(self.index);
+            if prop.is_destroyed() || !pc.actor.has_ap_to_attack() {
+                return false;
+            }
+            if !is_within_attack_dist(&pc, prop) {
+                return false;
+            }
+        }
+
+        EntityState::attack_prop(&self.pc, self.index, true);
+        false
+    }
+
+    fn ap(&self) -> i32 {
+        self.ap
+    }
+}
+
+struct DisarmTrapAction {
+    pc: Rc<RefCell<EntityState>>,
+    index: usize,
+}
+
+impl DisarmTrapAction {
+    fn create_if_valid(index: usize, prop_state: &PropState) -> Option<Box<dyn ActionKind>> {
+        match prop_state.prop.interactive {
+            prop::Interactive::Trap {
+                disarm_difficulty: Some(_),
+                ..
+            } => (),
+            _ => return None,
+        }
+
+        let max_dist = Module::rules().max_prop_distance;
+        let pc = match GameState::selected().first() {
+            None => return None,
+            Some(pc) => Rc::clone(pc),
+        };
+        let cb_action = Box::new(DisarmTrapAction {
+            pc: Rc::clone(&pc),
+            index,
+        });
+        if !is_within(&*pc.borrow(), prop_state, max_dist) {
+            return MoveThenAction::create_if_valid(
+                &pc,
+                prop_state.location.to_point(),
+                &prop_state.prop.size,
+                max_dist,
+                cb_action,
+                animation_state::Kind::MouseInteract,
+            );
+        }
+
+        Some(cb_action)
+    }
+}
+
+impl ActionKind for DisarmTrapAction {
+    fn cursor_state(&self) -> animation_state::Kind {
+        animation_state::Kind::MouseInteract
+    }
+
+    fn get_hover_info(&self) -> Option<ActionHoverInfo> {
+        let area_state = GameState::area_state();
+        let area_state = area_state.borrow();
+        let prop = area_state.props().get(self.index);
+        let point = prop.location.to_point();
+        Some(ActionHoverInfo::new(&prop.prop.size, point))
+    }
+
+    fn fire_action(&mut self, _widget: &Rc<RefCell<Widget>>) -> bool {
+        trap_handler::try_disarm(&self.pc, self.index);
+        false
+    }
+}
+
 struct LootPropAction {
     index: usize,
 }
@@ -410,8 +588,13 @@ impl ActionKind for LootPropAction {
             let area_state = GameState::area_state();
             let mut area_state = area_state.borrow_mut();
             let state = area_state.props_mut().get_mut(self.index);
-            state.toggle_active();
-            state.is_active()
+            if state.is_locked() {
+                // show the lock status in the prop window
+                true
+            } else {
+                state.toggle_active();
+                state.is_active()
+            }
         };
 
         let (root, view) = Widget::parent_mut::<RootView>(widget);
@@ -22,6 +22,7 @@ use sulis_core::io::{event::ClickKind, GraphicsRenderer};
 use sulis_core::ui::{Widget, WidgetKind, WidgetState};
 use sulis_core::util::Point;
 use sulis_core::widgets::TextArea;
+use sulis_module::Cover;
 use sulis_state::{ChangeListener, EntityState, GameState};
 
 const NAME: &str = "area_mouseover";
@@ -81,10 +82,13 @@ impl AreaMouseover {
 
         match self.kind {
             Kind::Entity(ref entity) => {
-                let actor = &entity.borrow().actor;
-                state.add_text_arg("name", &actor.actor.name);
-                state.add_text_arg("cur_hp", &actor.hp().to_string());
-                state.add_text_arg("max_hp", &actor.stats.max_hp.to_string());
+                {
+                    let actor = &entity.borrow().actor;
+                    state.add_text_arg("name", &actor.actor.name);
+                    state.add_text_arg("cur_hp", &actor.hp().to_string());
+                    state.add_text_arg("max_hp", &actor.stats.max_hp.to_string());
+                }
+                add_ranged_text_args(state, entity);
             }
             Kind::Prop(index) => {
                 let area_state = GameState::area_state();
@@ -96,7 +100,11 @@ impl AreaMouseover {
 
                 let prop = area_state.props().get(index);
 
-                if !prop.is_hover() && !prop.might_contain_items() {
+                if prop.is_trap() {
+                    if !prop.is_trap_armed() {
+                        state.add_text_arg("status", "Inactive");
+                    }
+                } else if !prop.is_hover() && !prop.might_contain_items() {
                     state.add_text_arg("empty", "true");
                 }
                 state.add_text_arg("name", prop.name());
@@ -114,6 +122,43 @@ impl AreaMouseover {
     }
 }
 
+// Shows the cover and elevation modifiers that would apply if the selected
+// party member made a ranged attack against the target
+fn add_ranged_text_args(state: &mut WidgetState, target: &Rc<RefCell<EntityState>>) {
+    let selected = GameState::selected();
+    let parent = match selected.first() {
+        None => return,
+        Some(parent) => parent,
+    };
+
+    if Rc::ptr_eq(parent, target) {
+        return;
+    }
+
+    let parent = parent.borrow();
+    let target = target.borrow();
+    if !parent.is_hostile(&target) || !parent.actor.stats.attack_is_ranged() {
+        return;
+    }
+
+    let area_state = GameState::area_state();
+    let modifiers = area_state.borrow().ranged_modifiers(&parent, &target);
+
+    if modifiers.cover != Cover::None {
+        state.add_text_arg("cover", modifiers.cover.name());
+        let defense = modifiers.cover_defense.to_string();
+        state.add_text_arg("cover_defense", &defense);
+    }
+
+    if modifiers.elevation > 0 {
+        let accuracy = modifiers.accuracy.to_string();
+        state.add_text_arg("high_ground", &accuracy);
+    } else if modifiers.elevation < 0 {
+        let defense = modifiers.elevation_defense.to_string();
+        state.add_text_arg("low_ground", &defense);
+    }
+}
+
 impl WidgetKind for AreaMouseover {
     widget_kind!(NAME);
 
@@ -147,7 +147,8 @@ impl AreaOverlayHandler {
         } else if let Some(index) = area_state.props().index_at(x, y) {
             let interactive = {
                 let prop = area_state.props().get(index);
-                (prop.is_container() || prop.is_hover()) && prop.is_enabled()
+                let detected_trap = prop.is_trap() && !prop.is_hidden();
+                (prop.is_container() || prop.is_hover() || detected_trap) && prop.is_enabled()
             };
 
             if interactive {
@@ -17,6 +17,7 @@
 use std::any::Any;
 use std::cell::{RefCell, RefMut};
 use std::cmp::{self, Ordering};
+use std::collections::HashMap;
 use std::mem;
 use std::rc::Rc;
 use std::time;
@@ -33,10 +34,15 @@ use sulis_core::util::{self, Offset, Point, Rect, Scale};
 use sulis_core::widgets::Label;
 use sulis_module::{
     area::{Layer, Tile},
+    quest::QuestMarker,
     DamageKind, Module,
 };
-use sulis_state::{area_feedback_text, area_state::PCVisRedraw, RangeIndicatorImageSet};
-use sulis_state::{AreaDrawable, AreaState, EntityState, EntityTextureCache, GameState};
+use sulis_state::{
+    area_feedback_text, area_state::PCVisRedraw, quest_handler, RangeIndicatorImageSet,
+};
+use sulis_state::{
+    AreaDrawable, AreaState, ChangeListener, EntityState, EntityTextureCache, GameState,
+};
 
 use crate::{action_kind, window_fade, AreaOverlayHandler, ScreenShake, WindowFade};
 
@@ -57,6 +63,9 @@ pub struct AreaView {
 
     targeter_label: Rc<RefCell<Widget>>,
     targeter_tile: Option<Rc<dyn Image>>,
+    quest_marker: Option<Rc<dyn Image>>,
+    quest_marker_color: Color,
+    quest_markers: Vec<QuestMarker>,
     range_indicator_image_set: Option<RangeIndicatorImageSet>,
 
     scroll: Scrollable,
@@ -93,6 +102,9 @@ impl AreaView {
             layers: Vec::new(),
             scroll,
             targeter_tile: None,
+            quest_marker: None,
+            quest_marker_color: color::WHITE,
+            quest_markers: Vec::new(),
             range_indicator_image_set: None,
             active_entity: None,
             entity_see_through_alpha: 0.2,
@@ -373,6 +385,9 @@ impl AreaView {
         let mut to_draw: Vec<&dyn AreaDrawable> = Vec::new();
 
         for prop_state in state.props().iter() {
+            if prop_state.is_hidden() {
+                continue;
+            }
             to_draw.push(prop_state);
         }
 
@@ -423,6 +438,90 @@ impl AreaView {
         // info!("Entity & Prop draw time: {}", util::format_elapsed_secs(start_time.elapsed()));
     }
 
+    fn draw_surface_types(
+        &self,
+        renderer: &mut dyn GraphicsRenderer,
+        scale: Scale,
+        widget: &Widget,
+        state: &AreaState,
+        millis: u32,
+    ) {
+        let p = widget.state.inner_position();
+
+        let mut draw_lists: HashMap<&str, (Color, DrawList)> = HashMap::new();
+        for (pt, tile) in state.surface_layer().iter() {
+            if !state.is_pc_explored(pt.x, pt.y) {
+                continue;
+            }
+
+            let surface_type = &tile.surface_type;
+            let (_, draw_list) = draw_lists
+                .entry(&surface_type.id)
+                .or_insert_with(|| (surface_type.color, DrawList::empty_sprite()));
+
+            let rect = Rect {
+                x: (pt.x + p.x) as f32 - self.scroll.x(),
+                y: (pt.y + p.y) as f32 - self.scroll.y(),
+                w: 1.0,
+                h: 1.0,
+            };
+            surface_type.image.append_to_draw_list(
+                draw_list,
+                &animation_state::NORMAL,
+                rect,
+                millis,
+            );
+        }
+
+        for (_, (color, mut draw_list)) in draw_lists {
+            draw_list.set_color(color);
+            draw_list.set_scale(scale);
+            renderer.draw(draw_list);
+        }
+    }
+
+    fn draw_quest_markers(
+        &self,
+        renderer: &mut dyn GraphicsRenderer,
+        scale: Scale,
+        widget: &Widget,
+        state: &AreaState,
+        millis: u32,
+    ) {
+        let image = match self.quest_marker {
+            None => return,
+            Some(ref image) => image,
+        };
+
+        let p = widget.state.inner_position();
+        let (w, h) = (image.get_width_f32(), image.get_height_f32());
+
+        let mut draw_list = DrawList::empty_sprite();
+        for marker in self.quest_markers.iter() {
+            let point = match marker {
+                QuestMarker::Area {
+                    area,
+                    point: Some(point),
+                } if *area == state.area.area.id => point,
+                _ => continue,
+            };
+
+            let rect = Rect {
+                x: (point.x + p.x) as f32 - w / 2.0 - self.scroll.x(),
+                y: (point.y + p.y) as f32 - h / 2.0 - self.scroll.y(),
+                w,
+                h,
+            };
+            image.append_to_draw_list(&mut draw_list, &animation_state::NORMAL, rect, millis);
+        }
+
+        if !draw_list.is_empty() {
+            draw_list.set_scale(scale);
+            draw_list.set_color(self.quest_marker_color);
+            renderer.draw(draw_list);
+        }
+    }
+
     fn draw_selection(
         &mut self,
         selected: &Rc<RefCell<EntityState>>,
@@ -587,6 +686,11 @@ impl WidgetKind for AreaView {
             self.targeter_tile = ResourceSet::image(image_id);
         }
 
+        if let Some(image_id) = theme.custom.get("quest_marker_image") {
+            self.quest_marker = ResourceSet::image(image_id);
+        }
+        self.quest_marker_color = theme.get_custom_or_default("quest_marker_color", color::WHITE);
+
         self.entity_see_through_alpha = theme.get_custom_or_default("entity_see_through_alpha", 0.2);
         self.feedback_text_params.scale = theme.get_custom_or_default("feedback_text_scale", 1.0);
         self.feedback_text_params.ap_scale =
@@ -616,14 +720,19 @@ impl WidgetKind for AreaView {
         self.feedback_text_params.heal_color =
             theme.get_custom_or_default("feedback_text_heal_color", color::BLUE);
 
+        self.feedback_text_params.damage_colors.clear();
+        self.feedback_text_params.damage_icons.clear();
         for kind in DamageKind::iter() {
-            let id = format!(
-                "feedback_text_damage_{}_color",
-                kind.to_str().to_lowercase()
-            );
-            let index = kind.index();
-            self.feedback_text_params.damage_colors[index] =
-                theme.get_custom_or_default(&id, color::LIGHT_GRAY);
+            let id = format!("feedback_text_damage_{}_color", kind.id().to_lowercase());
+            self.feedback_text_params
+                .damage_colors
+                .push(theme.get_custom_or_default(&id, kind.color()));
+
+            let icon = match kind.icon() {
+                None => ResourceSet::empty_image(),
+                Some(image_id) => ResourceSet::image_else_empty(&image_id),
+            };
+            self.feedback_text_params.damage_icons.push(icon);
         }
 
         if let Some(image_id) = theme.custom.get("feedback_icon_concealment") {
@@ -652,10 +761,20 @@ impl WidgetKind for AreaView {
         }
     }
 
-    fn on_add(&mut self, _widget: &Rc<RefCell<Widget>>) -> Vec<Rc<RefCell<Widget>>> {
+    fn on_add(&mut self, widget: &Rc<RefCell<Widget>>) -> Vec<Rc<RefCell<Widget>>> {
         info!("Adding area to widget tree");
         self.overlay_handler = AreaOverlayHandler::default();
 
+        self.quest_markers = quest_handler::active_markers(&GameState::quest_state());
+        let widget_ref = Rc::clone(widget);
+        GameState::add_quest_state_change_listener(ChangeListener::new(
+            NAME,
+            Box::new(move |quests| {
+                let view = Widget::kind_mut::<AreaView>(&widget_ref);
+                view.quest_markers = quest_handler::active_markers(quests);
+            }),
+        ));
+
         let area_state = GameState::area_state();
         let area = &area_state.borrow().area;
 
@@ -776,6 +895,7 @@ impl WidgetKind for AreaView {
             y: scale_y,
         };
         self.draw_layer(renderer, scale, widget, BASE_LAYER_ID, area_color);
+        self.draw_surface_types(renderer, scale, widget, &state, millis);
         GameState::draw_below_entities(
             renderer,
             Offset {
@@ -827,6 +947,8 @@ impl WidgetKind for AreaView {
             renderer.draw(draw_list);
         }
 
+        self.draw_quest_markers(renderer, scale, widget, &state, millis);
+
         let active_entity = self.active_entity.clone();
         if let Some(ref entity) = active_entity {
             self.draw_selection(entity, renderer, scale, widget, millis);
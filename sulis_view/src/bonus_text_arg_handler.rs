@@ -18,7 +18,7 @@ use std::fmt::Display;
 
 use sulis_core::ui::WidgetState;
 use sulis_module::bonus::{AttackBuilder, AttackKindBuilder, Contingent};
-use sulis_module::{Armor, Bonus, BonusList, DamageKind, Module, PrereqList};
+use sulis_module::{Armor, Attribute, Bonus, BonusKind, BonusList, DamageKind, Module, PrereqList};
 
 pub fn format_bonus_or_penalty(amount: i32) -> String {
     if amount >= 0 {
@@ -126,7 +126,7 @@ fn add_bonus(
 ) {
     use sulis_module::BonusKind::*;
     match &bonus.kind {
-        Attribute { attribute, amount } => add(state, attribute.short_name(), amount),
+        Attribute { attribute, amount } => add(state, &attribute.short_name(), amount),
         ActionPoints(amount) => add(state, "action_points", Module::rules().format_ap(*amount)),
         Armor(amount) => armor.add_base(*amount),
         ArmorKind { kind, amount } => armor.add_kind(*kind, *amount),
@@ -171,6 +171,10 @@ fn add_bonus(
         Will(amount) => add(state, "will", amount),
         Concealment(amount) => add(state, "concealment", amount),
         ConcealmentIgnore(amount) => add(state, "concealment_ignore", amount),
+        Stealth(amount) => add(state, "stealth", amount),
+        Detection(amount) => add(state, "detection", amount),
+        Mechanics(amount) => add(state, "mechanics", amount),
+        Reactions(amount) => add(state, "reactions", amount),
         CritChance(amount) => add(state, "crit_chance", amount),
         HitThreshold(amount) => add(state, "hit_threshold", amount),
         GrazeThreshold(amount) => add(state, "graze_threshold", amount),
@@ -213,18 +217,10 @@ fn add_bonus(
             add(state, "class_stat_amount", amount);
         }
         ArmorProficiency(armor_kind) => {
-            add(
-                state,
-                &format!("armor_proficiency_{armor_kind:?}"),
-                "true",
-            );
+            add(state, &format!("armor_proficiency_{armor_kind}"), "true");
         }
         WeaponProficiency(weapon_kind) => {
-            add(
-                state,
-                &format!("weapon_proficiency_{weapon_kind:?}"),
-                "true",
-            );
+            add(state, &format!("weapon_proficiency_{weapon_kind}"), "true");
         }
         FlankingAngle(amount) => add(state, "flanking_angle", amount),
         FreeAbilityGroupUse => add(state, "free_ability_group_use", true),
@@ -290,6 +286,24 @@ pub fn add_prereq_text_args(prereqs: &PrereqList, state: &mut WidgetState) {
     }
 }
 
+/// Returns the total of each attribute bonus that always applies, in the
+/// order the attributes are defined in the rules
+pub fn attribute_bonuses(bonuses: &BonusList) -> Vec<(Attribute, i32)> {
+    let mut totals = vec![0; Attribute::count()];
+    for bonus in bonuses.iter() {
+        if let (BonusKind::Attribute { attribute, amount }, Contingent::Always) =
+            (&bonus.kind, &bonus.when)
+        {
+            totals[attribute.index()] += *amount as i32;
+        }
+    }
+
+    Attribute::iter()
+        .zip(totals)
+        .filter(|(_, amount)| *amount != 0)
+        .collect()
+}
+
 pub fn add_bonus_text_args(bonuses: &BonusList, widget_state: &mut WidgetState) {
     let mut group_uses_so_far = Vec::new();
     let mut damage_index = 0;
@@ -314,6 +328,14 @@ pub fn add_bonus_text_args(bonuses: &BonusList, widget_state: &mut WidgetState)
         add(widget_state, "any_accuracy", "true");
     }
 
+    let attributes: Vec<String> = attribute_bonuses(bonuses)
+        .into_iter()
+        .map(|(attribute, amount)| format!("{}: {}", attribute.name(), amount))
+        .collect();
+    if !attributes.is_empty() {
+        add(widget_state, "attribute_bonuses", attributes.join("\n"));
+    }
+
     if !armor.is_empty() {
         add(widget_state, "any_armor", "true");
     }
@@ -322,13 +344,13 @@ pub fn add_bonus_text_args(bonuses: &BonusList, widget_state: &mut WidgetState)
     }
 
     for kind in DamageKind::iter() {
-        if !armor.differs_from_base(*kind) {
+        if !armor.differs_from_base(kind) {
             continue;
         }
         add(
             widget_state,
             &format!("armor_{kind}").to_lowercase(),
-            armor.amount(*kind),
+            armor.amount(kind),
         );
     }
 }
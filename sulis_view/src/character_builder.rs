@@ -314,7 +314,7 @@ impl BuilderSet for CharacterCreator {
             race: Some(builder.race.as_ref().unwrap().id.to_string()),
             inline_race: None,
             sex: builder.sex,
-            attributes: builder.attributes.unwrap(),
+            attributes: builder.attributes.clone().unwrap(),
             faction: Some(Faction::Friendly),
             conversation: None,
             images: builder.images.clone(),
@@ -43,8 +43,7 @@ impl AttributeSelectorPane {
         let rules = Module::rules();
         let attrs = AttributeList::new(rules.base_attribute as u8);
 
-        let total = rules.base_attribute * (Attribute::iter().count() as i32);
-        let available = rules.builder_attribute_points - total;
+        let available = rules.builder_attribute_points - attrs.total();
 
         Rc::new(RefCell::new(AttributeSelectorPane {
             attrs,
@@ -58,11 +57,7 @@ impl AttributeSelectorPane {
     fn calculate_available(&mut self) {
         let rules = Module::rules();
 
-        let mut total = 0;
-        for attr in Attribute::iter() {
-            total += self.attrs.get(*attr) as i32;
-        }
-        self.available = rules.builder_attribute_points - total;
+        self.available = rules.builder_attribute_points - self.attrs.total();
     }
 
     fn set_next_enabled(&mut self, widget: &Rc<RefCell<Widget>>) {
@@ -84,7 +79,7 @@ impl BuilderPane for AttributeSelectorPane {
 
         if let Some(ref class) = self.selected_class {
             self.selected_kit = Some(0);
-            self.attrs = class.kits[0].default_attributes;
+            self.attrs = class.kits[0].default_attributes.clone();
         }
 
         builder.attributes = None;
@@ -111,7 +106,7 @@ impl BuilderPane for AttributeSelectorPane {
         };
 
         builder.kit = self.selected_kit;
-        builder.attributes = Some(self.attrs);
+        builder.attributes = Some(self.attrs.clone());
         builder.inventory = Some(kit.starting_inventory.clone());
         builder.next(&widget);
     }
@@ -172,7 +167,7 @@ impl WidgetKind for AttributeSelectorPane {
                 .add_callback(Callback::new(Rc::new(move |widget, _| {
                     let (parent, pane) = Widget::parent_mut::<AttributeSelectorPane>(widget);
                     pane.selected_kit = Some(index);
-                    pane.attrs = class_ref.kits[index].default_attributes;
+                    pane.attrs = class_ref.kits[index].default_attributes.clone();
                     pane.set_next_enabled(&parent);
 
                     parent.borrow_mut().invalidate_children();
@@ -218,16 +213,27 @@ impl WidgetKind for AttributeSelectorPane {
             }
         }
 
+        let attributes = Widget::empty("attributes");
         for attr in Attribute::iter() {
-            let value = self.attrs.get(*attr) as i32;
+            let row = Widget::empty("attribute");
+
+            let value = self.attrs.get(attr) as i32;
             let max = if self.available > 0 {
                 rules.builder_max_attribute
             } else {
                 value
             };
 
+            let label = Widget::with_theme(Label::empty(), "label");
+            {
+                let state = &mut label.borrow_mut().state;
+                state.add_text_arg("name", &attr.name());
+                state.add_text_arg("description", &attr.description());
+            }
+            Widget::add_child_to(&row, label);
+
             let spinner = Spinner::new(value, rules.builder_min_attribute, max);
-            let widget = Widget::with_theme(spinner, &format!("{}_spinner", attr.short_name()));
+            let widget = Widget::with_theme(spinner, "spinner");
             widget
                 .borrow_mut()
                 .state
@@ -236,44 +242,47 @@ impl WidgetKind for AttributeSelectorPane {
 
                     let (parent, pane) = Widget::parent_mut::<AttributeSelectorPane>(widget);
                     parent.borrow_mut().invalidate_children();
-                    pane.attrs.set(*attr, value as u8);
+                    pane.attrs.set(attr, value as u8);
                     pane.set_next_enabled(&parent);
                 })));
-            children.push(widget);
-
-            let label = Widget::with_theme(Label::empty(), &format!("{}_label", attr.short_name()));
-            children.push(label);
+            Widget::add_child_to(&row, widget);
 
-            let bonus = Widget::with_theme(Label::empty(), &format!("{}_bonus", attr.short_name()));
-            let bonus_value = *attr_bonuses.get(attr).unwrap_or(&0);
+            let bonus = Widget::with_theme(Label::empty(), "bonus");
+            let bonus_value = *attr_bonuses.get(&attr).unwrap_or(&0);
             bonus
                 .borrow_mut()
                 .state
                 .add_text_arg("value", &bonus_value.to_string());
-            children.push(bonus);
+            Widget::add_child_to(&row, bonus);
 
             let total_value = bonus_value + value;
-            let total = Widget::with_theme(Label::empty(), &format!("{}_total", attr.short_name()));
+            let total = Widget::with_theme(Label::empty(), "total");
             total
                 .borrow_mut()
                 .state
                 .add_text_arg("value", &total_value.to_string());
-            children.push(total);
+            Widget::add_child_to(&row, total);
+
+            Widget::add_child_to(&attributes, row);
         }
 
+        let points = Widget::empty("points");
         let points_label = Widget::with_theme(Label::empty(), "points_label");
         points_label
             .borrow_mut()
             .state
             .add_text_arg("points", &self.available.to_string());
-        children.push(points_label);
+        Widget::add_child_to(&points, points_label);
 
         let amount_label = Widget::with_theme(Label::empty(), "amount_label");
         amount_label
             .borrow_mut()
             .state
             .add_text_arg("points", &self.available.to_string());
-        children.push(amount_label);
+        Widget::add_child_to(&points, amount_label);
+        Widget::add_child_to(&attributes, points);
+
+        children.push(attributes);
 
         children
     }
@@ -59,17 +59,26 @@ impl BackstorySelectorPane {
     pub fn set_next_enabled(&mut self, widget: &Rc<RefCell<Widget>>) {
         let (_, builder) = Widget::parent_mut::<CharacterBuilder>(widget);
 
-        let next = self.complete || self.convo.responses(&self.cur_node).is_empty();
+        let next = self.complete || self.at_last_node();
 
         builder.finish.borrow_mut().state.set_enabled(next);
     }
+
+    /// Returns true if the current node has no responses, or does not
+    /// exist, so that the backstory can always be finished
+    fn at_last_node(&self) -> bool {
+        match self.convo.responses(&self.cur_node) {
+            Err(_) => true,
+            Ok(responses) => responses.is_empty(),
+        }
+    }
 }
 
 impl BuilderPane for BackstorySelectorPane {
     fn on_selected(&mut self, builder: &mut CharacterBuilder, widget: Rc<RefCell<Widget>>) {
         builder.abilities.clear();
 
-        let next = self.complete || self.convo.responses(&self.cur_node).is_empty();
+        let next = self.complete || self.at_last_node();
         builder.finish.borrow_mut().state.set_enabled(next);
         builder.next.borrow_mut().state.set_visible(false);
         builder.finish.borrow_mut().state.set_visible(true);
@@ -98,14 +107,25 @@ impl WidgetKind for BackstorySelectorPane {
     fn on_add(&mut self, _widget: &Rc<RefCell<Widget>>) -> Vec<Rc<RefCell<Widget>>> {
         let title = Widget::with_theme(Label::empty(), "title");
 
-        self.node.borrow_mut().text = Some(self.convo.text(&self.cur_node).to_string());
+        let (text, responses): (&str, &[Response]) = match (
+            self.convo.text(&self.cur_node),
+            self.convo.responses(&self.cur_node),
+        ) {
+            (Ok(text), Ok(responses)) => (text, responses),
+            (Err(e), _) | (_, Err(e)) => {
+                error!("{} in the campaign backstory", e);
+                ("", &[])
+            }
+        };
+
+        self.node.borrow_mut().text = Some(text.to_string());
         let node_widget = Widget::with_theme(self.node.clone(), "node");
-        let responses = Widget::empty("responses");
+        let responses_widget = Widget::empty("responses");
         {
-            for response in self.convo.responses(&self.cur_node) {
+            for response in responses {
                 let response_button = ResponseButton::new(response);
                 let widget = Widget::with_defaults(response_button);
-                Widget::add_child_to(&responses, widget);
+                Widget::add_child_to(&responses_widget, widget);
             }
         }
 
@@ -122,7 +142,7 @@ impl WidgetKind for BackstorySelectorPane {
                 parent.borrow_mut().invalidate_children();
             })));
 
-        vec![title, node_widget, responses, start_over]
+        vec![title, node_widget, responses_widget, start_over]
     }
 }
 
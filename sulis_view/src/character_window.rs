@@ -247,7 +247,7 @@ fn export_character(pc: &ActorState) {
         race: Some(pc.actor.race.id.to_string()),
         inline_race: None,
         sex: Some(pc.actor.sex),
-        attributes: pc.actor.attributes,
+        attributes: pc.actor.attributes.clone(),
         faction: Some(pc.actor.faction()),
         conversation: None,
         images: pc.actor.builder_images.clone(),
@@ -436,6 +436,19 @@ pub fn create_details_text_box(pc: &ActorState, is_pc: bool) -> Rc<RefCell<Widge
             Widget::add_child_to(&details, export);
         }
 
+        let attributes = Widget::empty("attributes");
+        for attribute in Attribute::iter() {
+            let row = Widget::with_theme(TextArea::empty(), "attribute");
+            {
+                let state = &mut row.borrow_mut().state;
+                state.add_text_arg("name", &attribute.name());
+                let value = pc.stats.attributes.get(attribute);
+                state.add_text_arg("value", &value.to_string());
+            }
+            Widget::add_child_to(&attributes, row);
+        }
+        Widget::add_child_to(&details, attributes);
+
         let rules = Module::rules();
         let state = &mut details.borrow_mut().state;
         let stats = &pc.stats;
@@ -450,8 +463,8 @@ pub fn create_details_text_box(pc: &ActorState, is_pc: bool) -> Rc<RefCell<Widge
 
         for attribute in Attribute::iter() {
             state.add_text_arg(
-                attribute.short_name(),
-                &stats.attributes.get(*attribute).to_string(),
+                &attribute.short_name(),
+                &stats.attributes.get(attribute).to_string(),
             )
         }
 
@@ -550,19 +563,23 @@ pub fn create_details_text_box(pc: &ActorState, is_pc: bool) -> Rc<RefCell<Widge
         state.add_text_arg("caster_level", &stats.caster_level.to_string());
 
         state.add_text_arg("armor", &stats.armor.base().to_string());
+        let mut armor_kinds = Vec::new();
         for kind in DamageKind::iter() {
-            if !stats.armor.differs_from_base(*kind) {
+            if kind.ignores_armor() || !stats.armor.differs_from_base(kind) {
                 continue;
             }
 
-            state.add_text_arg(
-                &format!("armor_{kind}").to_lowercase(),
-                &stats.armor.amount(*kind).to_string(),
-            );
+            let amount = stats.armor.amount(kind);
+            state.add_text_arg(&format!("armor_{kind}").to_lowercase(), &amount.to_string());
+            armor_kinds.push(format!("({}: {})", kind.name(), amount));
+        }
+        if !armor_kinds.is_empty() {
+            state.add_text_arg("armor_kinds", &armor_kinds.join(" "));
         }
 
+        let mut resistances = Vec::new();
         for kind in DamageKind::iter() {
-            let amount = stats.resistance.amount(*kind);
+            let amount = stats.resistance.amount(kind);
             if amount == 0 {
                 continue;
             }
@@ -571,6 +588,10 @@ pub fn create_details_text_box(pc: &ActorState, is_pc: bool) -> Rc<RefCell<Widge
                 &format!("resistance_{kind}").to_lowercase(),
                 &amount.to_string(),
             );
+            resistances.push(format!("({}: {}%)", kind.name(), amount));
+        }
+        if !resistances.is_empty() {
+            state.add_text_arg("resistances", &resistances.join(" "));
         }
 
         state.add_text_arg("melee_accuracy", &stats.melee_accuracy.to_string());
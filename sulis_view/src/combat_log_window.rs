@@ -0,0 +1,174 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2018 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use sulis_core::ui::{Callback, Widget, WidgetKind};
+use sulis_core::widgets::{Button, Label, ScrollDirection, ScrollPane, TextArea};
+use sulis_state::{ChangeListener, CombatLog, CombatLogEntry, CombatLogKind, GameState};
+
+pub const NAME: &str = "combat_log_window";
+
+pub struct CombatLogWindow {
+    shown: Vec<CombatLogKind>,
+    export_status: Option<String>,
+    entries_pane: Rc<RefCell<ScrollPane>>,
+
+    // the displayed entry widgets with their log indices, oldest first
+    entry_widgets: VecDeque<(usize, Rc<RefCell<Widget>>)>,
+    next_index: usize,
+}
+
+impl CombatLogWindow {
+    pub fn new() -> Rc<RefCell<CombatLogWindow>> {
+        Rc::new(RefCell::new(CombatLogWindow {
+            shown: CombatLogKind::iter().copied().collect(),
+            export_status: None,
+            entries_pane: ScrollPane::new(ScrollDirection::Vertical),
+            entry_widgets: VecDeque::new(),
+            next_index: 0,
+        }))
+    }
+
+    /// Adds widgets for any entries added to the log since the last update,
+    /// and removes the widgets of entries that have been dropped from it
+    fn add_new_entries(&mut self, log: &CombatLog) {
+        let mut next_index = self.next_index;
+        for (index, entry) in log.iter_from(self.next_index) {
+            next_index = index + 1;
+            if !self.shown.contains(&entry.kind) {
+                continue;
+            }
+
+            // show the most recent entries first
+            let widget = entry_widget(entry);
+            self.entries_pane
+                .borrow()
+                .add_to_content_front(Rc::clone(&widget));
+            self.entry_widgets.push_back((index, widget));
+        }
+        self.next_index = next_index;
+
+        while let Some((index, widget)) = self.entry_widgets.front() {
+            if *index >= log.first_index() {
+                break;
+            }
+
+            widget.borrow_mut().mark_for_removal();
+            self.entry_widgets.pop_front();
+        }
+    }
+}
+
+fn entry_widget(entry: &CombatLogEntry) -> Rc<RefCell<Widget>> {
+    let widget = Widget::with_theme(TextArea::empty(), "entry");
+    {
+        let state = &mut widget.borrow_mut().state;
+        state.add_text_arg("round", &entry.round.to_string());
+        state.add_text_arg("kind", entry.kind.to_str());
+        state.add_text_arg("text", &entry.text);
+    }
+    widget
+}
+
+impl WidgetKind for CombatLogWindow {
+    widget_kind!(NAME);
+
+    fn on_remove(&mut self, _widget: &Rc<RefCell<Widget>>) {
+        GameState::combat_log().borrow_mut().listeners.remove(NAME);
+    }
+
+    fn on_add(&mut self, widget: &Rc<RefCell<Widget>>) -> Vec<Rc<RefCell<Widget>>> {
+        let log = GameState::combat_log();
+        let widget_ref = Rc::clone(widget);
+        log.borrow_mut().listeners.add(ChangeListener::new(
+            NAME,
+            Box::new(move |log| {
+                let window = Widget::kind_mut::<CombatLogWindow>(&widget_ref);
+                window.add_new_entries(log);
+            }),
+        ));
+
+        let close = Widget::with_theme(Button::empty(), "close");
+        close
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(|widget, _| {
+                let (parent, _) = Widget::parent::<CombatLogWindow>(widget);
+                parent.borrow_mut().mark_for_removal();
+            })));
+
+        let mut widgets = vec![close];
+
+        for kind in CombatLogKind::iter() {
+            let kind = *kind;
+            let filter = Widget::with_theme(Button::empty(), &format!("{}_filter", kind.to_str()));
+            filter
+                .borrow_mut()
+                .state
+                .set_active(self.shown.contains(&kind));
+            filter
+                .borrow_mut()
+                .state
+                .add_callback(Callback::new(Rc::new(move |widget, _| {
+                    let (parent, window) = Widget::parent_mut::<CombatLogWindow>(widget);
+                    if let Some(index) = window.shown.iter().position(|k| *k == kind) {
+                        window.shown.remove(index);
+                    } else {
+                        window.shown.push(kind);
+                    }
+                    parent.borrow_mut().invalidate_children();
+                })));
+            widgets.push(filter);
+        }
+
+        let export = Widget::with_theme(Button::empty(), "export");
+        export
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(|widget, _| {
+                let (parent, window) = Widget::parent_mut::<CombatLogWindow>(widget);
+                let result = GameState::combat_log().borrow().export(&window.shown);
+                window.export_status = Some(match result {
+                    Ok(path) => format!("Exported to {}", path.to_string_lossy()),
+                    Err(e) => {
+                        warn!("Error exporting combat log: {}", e);
+                        "Error exporting combat log".to_string()
+                    }
+                });
+                parent.borrow_mut().invalidate_children();
+            })));
+        widgets.push(export);
+
+        if let Some(status) = &self.export_status {
+            let label = Widget::with_theme(Label::empty(), "export_status");
+            label.borrow_mut().state.add_text_arg("status", status);
+            widgets.push(label);
+        }
+
+        self.entries_pane = ScrollPane::new(ScrollDirection::Vertical);
+        self.entry_widgets.clear();
+        self.next_index = 0;
+        let entries_widget = Widget::with_theme(self.entries_pane.clone(), "entries");
+        self.add_new_entries(&log.borrow());
+        widgets.push(entries_widget);
+
+        widgets
+    }
+}
@@ -18,16 +18,22 @@ use std::any::Any;
 use std::cell::RefCell;
 use std::rc::Rc;
 
+use sulis_core::io::Audio;
 use sulis_core::ui::{Callback, Widget, WidgetKind};
 use sulis_core::widgets::{Button, TextArea};
+use sulis_module::cutscene::Frame;
 use sulis_module::Cutscene;
 use sulis_state::GameState;
 
+use crate::{window_fade, WindowFade};
+
 pub const NAME: &str = "cutscene_window";
 
 pub struct CutsceneWindow {
     cutscene: Rc<Cutscene>,
     frame_index: usize,
+    frame_millis: u32,
+    shown_index: Option<usize>,
 }
 
 impl CutsceneWindow {
@@ -35,8 +41,16 @@ impl CutsceneWindow {
         Rc::new(RefCell::new(CutsceneWindow {
             cutscene,
             frame_index: 0,
+            frame_millis: 0,
+            shown_index: None,
         }))
     }
+
+    fn next_frame(&mut self, widget: &Rc<RefCell<Widget>>) {
+        self.frame_index += 1;
+        self.frame_millis = 0;
+        widget.borrow_mut().invalidate_children();
+    }
 }
 
 pub fn add_on_end_cbs(cutscene: &Rc<Cutscene>) {
@@ -46,9 +60,42 @@ pub fn add_on_end_cbs(cutscene: &Rc<Cutscene>) {
     }
 }
 
+fn start_frame(frame: &Frame) {
+    if frame.stop_music {
+        Audio::stop_music();
+    }
+
+    if let Some(ref music) = frame.music {
+        Audio::play_music(music, 1.0);
+    }
+
+    if let Some(ref sfx) = frame.sfx {
+        Audio::play_sfx(sfx, 1.0);
+    }
+
+    if !frame.on_show.is_empty() {
+        let pc = GameState::player();
+        GameState::add_ui_callback(frame.on_show.clone(), &pc, &pc);
+    }
+}
+
 impl WidgetKind for CutsceneWindow {
     widget_kind!(NAME);
 
+    fn update(&mut self, widget: &Rc<RefCell<Widget>>, millis: u32) {
+        let duration = match self.cutscene.frames.get(self.frame_index) {
+            Some(frame) => frame.duration,
+            None => return,
+        };
+
+        if let Some(duration) = duration {
+            self.frame_millis += millis;
+            if self.frame_millis >= duration {
+                self.next_frame(widget);
+            }
+        }
+    }
+
     fn on_add(&mut self, widget: &Rc<RefCell<Widget>>) -> Vec<Rc<RefCell<Widget>>> {
         let frame = self.cutscene.frames.get(self.frame_index);
         let frame = match frame {
@@ -60,6 +107,16 @@ impl WidgetKind for CutsceneWindow {
             Some(ref frame) => frame,
         };
 
+        // on_add is also called on relayout, so only start the frame once
+        let first_show = self.shown_index != Some(self.frame_index);
+        if first_show {
+            self.shown_index = Some(self.frame_index);
+            start_frame(frame);
+        }
+
+        let image = Widget::empty("image");
+        image.borrow_mut().state.set_background(frame.image.clone());
+
         let close = Widget::with_theme(Button::empty(), "close");
 
         let cutscene = Rc::clone(&self.cutscene);
@@ -79,14 +136,18 @@ impl WidgetKind for CutsceneWindow {
             .state
             .add_callback(Callback::new(Rc::new(|widget, _| {
                 let (parent, window) = Widget::parent_mut::<CutsceneWindow>(widget);
-                window.frame_index += 1;
-                parent.borrow_mut().invalidate_children();
+                window.next_frame(&parent);
             })));
 
         let text_area = Widget::with_defaults(TextArea::empty());
 
         text_area.borrow_mut().state.add_text_arg("0", &frame.text);
 
-        vec![close, text_area, next_button]
+        let mut children = vec![image, close, text_area, next_button];
+        if first_show && frame.fade_in {
+            let fade = WindowFade::new(window_fade::Mode::In);
+            children.push(Widget::with_theme(fade, "fade"));
+        }
+        children
     }
 }
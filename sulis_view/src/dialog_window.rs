@@ -16,6 +16,7 @@
 
 use std::any::Any;
 use std::cell::RefCell;
+use std::io::Error;
 use std::rc::Rc;
 
 use sulis_core::io::{event, InputActionKind};
@@ -23,8 +24,8 @@ use sulis_core::ui::{theme, Widget, WidgetKind};
 use sulis_core::widgets::TextArea;
 use sulis_module::{conversation::Response, Conversation, OnTrigger};
 use sulis_state::{
-    area_feedback_text::ColorKind, script::entity_with_id, AreaFeedbackText, ChangeListener,
-    EntityState, GameState,
+    area_feedback_text::ColorKind, check_handler, quest_handler, script::entity_with_id,
+    AreaFeedbackText, ChangeListener, EntityState, GameState,
 };
 
 use crate::trigger_activator::{activate, is_match, scroll_view};
@@ -35,6 +36,7 @@ pub const NAME: &str = "dialog_window";
 pub struct DialogWindow {
     pc: Rc<RefCell<EntityState>>,
     entity: Rc<RefCell<EntityState>>,
+    speaker: Rc<RefCell<EntityState>>,
     convo: Rc<Conversation>,
     cur_node: String,
 
@@ -48,10 +50,12 @@ impl DialogWindow {
         convo: Rc<Conversation>,
     ) -> Rc<RefCell<DialogWindow>> {
         let cur_node = get_initial_node(&convo, pc, entity);
+        let speaker = node_speaker(&convo, &cur_node, pc, entity);
 
         Rc::new(RefCell::new(DialogWindow {
             pc: Rc::clone(pc),
             entity: Rc::clone(entity),
+            speaker,
             convo,
             node: TextArea::empty(),
             cur_node,
@@ -86,8 +90,18 @@ impl WidgetKind for DialogWindow {
             .listeners
             .add(ChangeListener::invalidate(NAME, widget));
 
-        let cur_text = self.convo.text(&self.cur_node);
-        let responses = self.convo.responses(&self.cur_node);
+        let (cur_text, responses, on_view) = match (
+            self.convo.text(&self.cur_node),
+            self.convo.responses(&self.cur_node),
+            self.convo.on_view(&self.cur_node),
+        ) {
+            (Ok(text), Ok(responses), Ok(on_view)) => (text, responses, on_view),
+            (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => {
+                log_invalid_node(&e, &self.entity);
+                widget.borrow_mut().mark_for_removal();
+                return Vec::new();
+            }
+        };
 
         let node_widget = Widget::with_theme(self.node.clone(), "node");
         {
@@ -99,6 +113,7 @@ impl WidgetKind for DialogWindow {
 
             node.add_text_arg("player_name", &self.pc.borrow().actor.actor.name);
             node.add_text_arg("target_name", &entity.actor.actor.name);
+            node.add_text_arg("speaker_name", &self.speaker.borrow().actor.actor.name);
         }
 
         let cur_text = theme::expand_text_args(cur_text, &node_widget.borrow().state);
@@ -107,7 +122,8 @@ impl WidgetKind for DialogWindow {
             widget.borrow_mut().mark_for_removal();
 
             let area = GameState::area_state();
-            let mut feedback = AreaFeedbackText::with_target(&self.entity.borrow(), &area.borrow());
+            let mut feedback =
+                AreaFeedbackText::with_target(&self.speaker.borrow(), &area.borrow());
             feedback.add_entry(cur_text, ColorKind::Info);
             area.borrow_mut().add_feedback_text(feedback);
             return Vec::new();
@@ -115,12 +131,7 @@ impl WidgetKind for DialogWindow {
 
         self.node.borrow_mut().text = Some(cur_text);
 
-        activate(
-            widget,
-            self.convo.on_view(&self.cur_node),
-            &self.pc,
-            &self.entity,
-        );
+        activate(widget, on_view, &self.pc, &self.entity);
 
         let responses_widget = Widget::empty("responses");
         {
@@ -140,9 +151,7 @@ impl WidgetKind for DialogWindow {
 }
 
 struct ResponseButton {
-    text: String,
-    to: Option<String>,
-    on_select: Vec<OnTrigger>,
+    response: Response,
     pc: Rc<RefCell<EntityState>>,
     convo: Rc<Conversation>,
 }
@@ -154,24 +163,31 @@ impl ResponseButton {
         pc: &Rc<RefCell<EntityState>>,
     ) -> Rc<RefCell<ResponseButton>> {
         Rc::new(RefCell::new(ResponseButton {
-            text: response.text.to_string(),
-            to: response.to.clone(),
-            on_select: response.on_select.clone(),
+            response: response.clone(),
             pc: Rc::clone(pc),
             convo: Rc::clone(convo),
         }))
     }
 
-    fn check_switch_speaker(&self, node: &str, area: &Rc<RefCell<AreaView>>) {
-        let speaker = match self.convo.switch_speaker(node) {
-            None => return,
-            Some(ref speaker) => speaker,
+    fn check_switch_speaker(
+        &self,
+        node: &str,
+        window: &DialogWindow,
+        area: &Rc<RefCell<AreaView>>,
+    ) -> Option<Rc<RefCell<EntityState>>> {
+        // an invalid node is reported when the dialog window is rebuilt
+        let ids = match self.convo.switch_speaker(node) {
+            Err(_) | Ok([]) => return None,
+            Ok(ids) => ids,
         };
 
-        let speaker = match entity_with_id(speaker.to_string()) {
+        let speaker = match find_speaker(ids, &window.pc, &window.entity) {
             None => {
-                warn!("Attempted to switch to invalid speaker '{}'", speaker);
-                return;
+                warn!(
+                    "Attempted to switch to invalid speaker '{}'",
+                    ids.join(", ")
+                );
+                return None;
             }
             Some(speaker) => speaker,
         };
@@ -184,6 +200,7 @@ impl ResponseButton {
         GameState::add_ui_callback(vec![cb], &self.pc, &speaker);
         area.borrow_mut()
             .set_active_entity(Some(Rc::clone(&speaker)));
+        Some(speaker)
     }
 }
 
@@ -198,7 +215,12 @@ impl WidgetKind for ResponseButton {
             .borrow_mut()
             .state
             .add_text_arg("player_name", &self.pc.borrow().actor.actor.name);
-        let cur_text = theme::expand_text_args(&self.text, &text_area_widget.borrow().state);
+        let mut cur_text =
+            theme::expand_text_args(&self.response.text, &text_area_widget.borrow().state);
+
+        if let Some(ref check) = self.response.check {
+            cur_text = format!("\\[{}\\] {}", check.label(), cur_text);
+        }
 
         text_area.borrow_mut().text = Some(cur_text);
         vec![text_area_widget]
@@ -215,18 +237,26 @@ impl WidgetKind for ResponseButton {
 
         let (parent, window) = Widget::parent_mut::<DialogWindow>(widget);
 
-        activate(widget, &self.on_select, &window.pc, &window.entity);
+        activate(widget, &self.response.on_select, &window.pc, &window.entity);
 
         let (_, view) = Widget::parent_mut::<RootView>(&parent);
         let (area, _) = view.area_view();
 
-        match self.to {
+        let check_passed = self
+            .response
+            .check
+            .as_ref()
+            .map(|check| check_handler::roll_check(&window.pc, check));
+
+        match self.response.next_node(check_passed) {
             None => {
                 parent.borrow_mut().mark_for_removal();
                 area.borrow_mut().set_active_entity(None);
             }
-            Some(ref to) => {
-                self.check_switch_speaker(to, &area);
+            Some(to) => {
+                if let Some(speaker) = self.check_switch_speaker(to, window, &area) {
+                    window.speaker = speaker;
+                }
                 window.cur_node = to.to_string();
                 parent.borrow_mut().invalidate_children()
             }
@@ -243,11 +273,24 @@ pub fn show_convo(
     widget: &Rc<RefCell<Widget>>,
 ) {
     let initial_node = get_initial_node(&convo, pc, target);
-    if convo.responses(&initial_node).is_empty() {
+    let (text, responses) = match (convo.text(&initial_node), convo.responses(&initial_node)) {
+        (Ok(text), Ok(responses)) => (text, responses),
+        (Err(e), _) | (_, Err(e)) => {
+            log_invalid_node(&e, target);
+            return;
+        }
+    };
+
+    let target_id = target.borrow().actor.actor.id.to_string();
+    quest_handler::on_talk(&target_id);
+
+    let speaker = node_speaker(&convo, &initial_node, pc, target);
+
+    if responses.is_empty() {
         let area = GameState::area_state();
 
-        let mut feedback = AreaFeedbackText::with_target(&target.borrow(), &area.borrow());
-        feedback.add_entry(convo.text(&initial_node).to_string(), ColorKind::Info);
+        let mut feedback = AreaFeedbackText::with_target(&speaker.borrow(), &area.borrow());
+        feedback.add_entry(text.to_string(), ColorKind::Info);
         area.borrow_mut().add_feedback_text(feedback);
     } else {
         let window = Widget::with_defaults(DialogWindow::new(pc, target, convo));
@@ -257,10 +300,10 @@ pub fn show_convo(
         let (area, _) = view.area_view();
         area.borrow_mut().clear_mouse_state();
         area.borrow_mut()
-            .set_active_entity(Some(Rc::clone(target)));
+            .set_active_entity(Some(Rc::clone(&speaker)));
 
         let (x, y) = {
-            let loc = &target.borrow().location;
+            let loc = &speaker.borrow().location;
             (loc.x, loc.y)
         };
         scroll_view(&root, x, y);
@@ -268,6 +311,71 @@ pub fn show_convo(
     }
 }
 
+fn log_invalid_node(error: &Error, target: &Rc<RefCell<EntityState>>) {
+    error!(
+        "{} when talking to '{}'.  Ending the conversation.",
+        error,
+        target.borrow().actor.actor.id
+    );
+}
+
+fn node_speaker(
+    convo: &Conversation,
+    node: &str,
+    pc: &Rc<RefCell<EntityState>>,
+    target: &Rc<RefCell<EntityState>>,
+) -> Rc<RefCell<EntityState>> {
+    let speaker = match convo.switch_speaker(node) {
+        Err(_) | Ok([]) => None,
+        Ok(ids) => find_speaker(ids, pc, target),
+    };
+
+    speaker.unwrap_or_else(|| Rc::clone(target))
+}
+
+/// Finds the first of the specified speakers that is alive and in the
+/// current area.  `player` and `target` refer to the conversation
+/// participants, `party` to any other party member, and anything else to a
+/// party member's actor ID or an entity's unique ID.
+pub fn find_speaker(
+    ids: &[String],
+    pc: &Rc<RefCell<EntityState>>,
+    target: &Rc<RefCell<EntityState>>,
+) -> Option<Rc<RefCell<EntityState>>> {
+    let area = GameState::area_state();
+    let can_speak = |entity: &Rc<RefCell<EntityState>>| {
+        let entity = entity.borrow();
+        !entity.actor.is_dead() && entity.location.is_in(&area.borrow())
+    };
+
+    let party = GameState::party();
+    for id in ids {
+        let speaker = match id.as_str() {
+            "player" => Some(Rc::clone(pc)),
+            "target" => Some(Rc::clone(target)),
+            "party" => party
+                .iter()
+                .find(|member| {
+                    !Rc::ptr_eq(member, pc) && !Rc::ptr_eq(member, target) && can_speak(member)
+                })
+                .cloned(),
+            _ => party
+                .iter()
+                .find(|member| member.borrow().actor.actor.id == *id)
+                .cloned()
+                .or_else(|| entity_with_id(id.to_string())),
+        };
+
+        if let Some(speaker) = speaker {
+            if can_speak(&speaker) {
+                return Some(speaker);
+            }
+        }
+    }
+
+    None
+}
+
 pub fn get_initial_node(
     convo: &Rc<Conversation>,
     pc: &Rc<RefCell<EntityState>>,
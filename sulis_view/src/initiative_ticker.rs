@@ -22,6 +22,7 @@ use sulis_core::io::event::ClickKind;
 use sulis_core::io::GraphicsRenderer;
 use sulis_core::ui::{Widget, WidgetKind};
 use sulis_core::util::{Offset, Point, Scale};
+use sulis_module::{InitiativeMode, Module};
 use sulis_state::{ChangeListener, EntityState, GameState};
 
 pub const NAME: &str = "initiative_ticker";
@@ -70,10 +71,28 @@ impl WidgetKind for InitiativeTicker {
             .listeners
             .add(ChangeListener::invalidate(NAME, widget));
 
+        let group_mode = Module::rules().initiative_mode == InitiativeMode::Group;
+
         let pane = Widget::empty("pane");
+        let mgr = mgr.borrow();
         let mut first = true;
-        for entity in mgr.borrow().active_iter() {
-            let theme = if first { "current_entry" } else { "entry" };
+        let mut last_faction = None;
+        for entity in mgr.active_iter() {
+            if group_mode {
+                let faction = entity.borrow().actor.faction();
+                if last_faction.is_some() && last_faction != Some(faction) {
+                    Widget::add_child_to(&pane, Widget::empty("phase_separator"));
+                }
+                last_faction = Some(faction);
+            }
+
+            let theme = if first {
+                "current_entry"
+            } else if group_mode && mgr.is_in_current_phase(entity) {
+                "phase_entry"
+            } else {
+                "entry"
+            };
             let widget = Widget::with_theme(TickerLabel::new(entity), theme);
             Widget::add_child_to(&pane, widget);
             first = false;
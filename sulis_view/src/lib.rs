@@ -54,6 +54,9 @@ pub use self::character_window::CharacterWindow;
 mod class_pane;
 pub use self::class_pane::ClassPane;
 
+mod combat_log_window;
+pub use self::combat_log_window::CombatLogWindow;
+
 mod console_window;
 pub use self::console_window::ConsoleWindow;
 
@@ -124,6 +127,9 @@ pub use self::screen_shake::ScreenShake;
 mod script_menu;
 pub use self::script_menu::ScriptMenu;
 
+mod sequence_runner;
+pub use self::sequence_runner::SequenceRunner;
+
 pub mod trigger_activator;
 
 mod window_fade;
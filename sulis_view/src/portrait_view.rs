@@ -169,7 +169,7 @@ impl WidgetKind for PortraitView {
             let mut targeter = targeter.borrow_mut();
             targeter.on_activate();
         } else {
-            GameState::set_selected_party_member(Rc::clone(&self.entity));
+            GameState::activate_party_member(Rc::clone(&self.entity));
         }
 
         true
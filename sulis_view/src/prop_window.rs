@@ -21,7 +21,7 @@ use std::rc::Rc;
 use crate::{item_list_pane::Filter, ItemListPane, RootView};
 use sulis_core::ui::{Callback, Widget, WidgetKind};
 use sulis_core::widgets::{Button, Label};
-use sulis_state::{ChangeListener, EntityState, GameState};
+use sulis_state::{lock_handler, ChangeListener, EntityState, GameState};
 
 pub const NAME: &str = "prop_window";
 
@@ -101,6 +101,43 @@ impl WidgetKind for PropWindow {
                     parent.borrow_mut().mark_for_removal();
                 })));
 
+            if prop.is_locked() {
+                let lock_status = Widget::with_theme(Label::empty(), "lock_status");
+                let unlock = Widget::with_theme(Button::empty(), "unlock");
+
+                if let Some(ref lock) = prop.prop.lock {
+                    let mut status = lock_status.borrow_mut();
+                    if let Some(ref key) = lock.key {
+                        status.state.add_text_arg("key", &key.name);
+                    }
+                    if let Some(difficulty) = lock.difficulty {
+                        status
+                            .state
+                            .add_text_arg("difficulty", &difficulty.to_string());
+                    }
+                }
+
+                let prop_index = self.prop_index;
+                let player = Rc::clone(&self.player);
+                unlock
+                    .borrow_mut()
+                    .state
+                    .add_callback(Callback::new(Rc::new(move |_, _| {
+                        if !lock_handler::try_unlock(&player, prop_index) {
+                            return;
+                        }
+
+                        let area_state = GameState::area_state();
+                        let mut area_state = area_state.borrow_mut();
+                        let prop = area_state.props_mut().get_mut(prop_index);
+                        if !prop.is_active() {
+                            prop.toggle_active();
+                        }
+                    })));
+
+                return vec![icon, close, lock_status, unlock];
+            }
+
             let prop_index = self.prop_index;
             take_all
                 .borrow_mut()
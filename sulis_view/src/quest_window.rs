@@ -19,15 +19,17 @@ use std::cell::RefCell;
 use std::rc::Rc;
 
 use sulis_core::ui::{Callback, Widget, WidgetKind};
-use sulis_core::widgets::{Button, Label, ScrollDirection, ScrollPane, TextArea};
+use sulis_core::widgets::{Button, InputField, Label, ScrollDirection, ScrollPane, TextArea};
 use sulis_module::{on_trigger::QuestEntryState, Module, Quest};
-use sulis_state::{ChangeListener, GameState};
+use sulis_state::{ChangeListener, GameState, QuestStateSet};
 
 pub const NAME: &str = "quest_window";
 
 pub struct QuestWindow {
     active_quest: Option<Rc<Quest>>,
     show_completed: bool,
+    show_notes: bool,
+    note_text: String,
 }
 
 impl QuestWindow {
@@ -35,8 +37,20 @@ impl QuestWindow {
         Rc::new(RefCell::new(QuestWindow {
             active_quest: None,
             show_completed: false,
+            show_notes: false,
+            note_text: String::new(),
         }))
     }
+
+    fn add_note(&mut self) {
+        let note = self.note_text.trim().to_string();
+        self.note_text.clear();
+        if note.is_empty() {
+            return;
+        }
+
+        GameState::add_journal_note(note);
+    }
 }
 
 impl WidgetKind for QuestWindow {
@@ -82,6 +96,20 @@ impl WidgetKind for QuestWindow {
 
         let show_completed_label = Widget::with_theme(Label::empty(), "show_completed_label");
 
+        let notes_toggle = Widget::with_theme(Button::empty(), "notes_toggle");
+        notes_toggle.borrow_mut().state.set_active(self.show_notes);
+        notes_toggle
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(|widget, _| {
+                let (parent, window) = Widget::parent_mut::<QuestWindow>(widget);
+                let cur = window.show_notes;
+                window.show_notes = !cur;
+                parent.borrow_mut().invalidate_children();
+            })));
+
+        let notes_label = Widget::with_theme(Label::empty(), "notes_label");
+
         let quest_list_pane = ScrollPane::new(ScrollDirection::Vertical);
         let quest_list_widget = Widget::with_theme(quest_list_pane.clone(), "quest_list");
 
@@ -99,7 +127,7 @@ impl WidgetKind for QuestWindow {
                 QuestEntryState::Hidden => continue,
                 QuestEntryState::Visible => selected,
                 QuestEntryState::Active => true,
-                QuestEntryState::Complete => {
+                QuestEntryState::Complete | QuestEntryState::Failed => {
                     if !self.show_completed {
                         continue;
                     }
@@ -117,6 +145,7 @@ impl WidgetKind for QuestWindow {
                 .add_callback(Callback::new(Rc::new(move |widget, _| {
                     let (window, quest_window) = Widget::parent_mut::<QuestWindow>(widget);
                     quest_window.active_quest = Some(Rc::clone(&quest_ref));
+                    quest_window.show_notes = false;
                     window.borrow_mut().invalidate_children();
                 })));
 
@@ -126,11 +155,17 @@ impl WidgetKind for QuestWindow {
                 .state
                 .add_text_arg("name", &quest.name);
 
-            if let QuestEntryState::Complete = quests.state(&quest.id) {
-                text_area
-                    .borrow_mut()
-                    .state
-                    .add_text_arg("complete", "true");
+            match quests.state(&quest.id) {
+                QuestEntryState::Complete => {
+                    text_area
+                        .borrow_mut()
+                        .state
+                        .add_text_arg("complete", "true");
+                }
+                QuestEntryState::Failed => {
+                    text_area.borrow_mut().state.add_text_arg("failed", "true");
+                }
+                _ => (),
             }
 
             Widget::add_child_to(&button, text_area);
@@ -138,17 +173,31 @@ impl WidgetKind for QuestWindow {
             quest_list_pane.borrow().add_to_content(button);
         }
 
+        if self.show_notes {
+            let mut children = self.add_notes_widgets(&quests);
+            children.append(&mut vec![
+                close,
+                quest_list_widget,
+                show_completed_toggle,
+                show_completed_label,
+                notes_toggle,
+                notes_label,
+            ]);
+            return children;
+        }
+
         let quest_entries_pane = ScrollPane::new(ScrollDirection::Vertical);
         let quest_entries_widget = Widget::with_theme(quest_entries_pane.clone(), "quest_entries");
 
         if let Some(ref quest) = self.active_quest {
             if let Some(quest_state) = quests.quest(&quest.id) {
                 for (id, _quest_entry) in quest_state.iter().rev() {
-                    let active = match quests.entry_state(&quest.id, id) {
+                    let entry_state = quests.entry_state(&quest.id, id);
+                    let active = match entry_state {
                         QuestEntryState::Hidden => continue,
                         QuestEntryState::Visible => false,
                         QuestEntryState::Active => true,
-                        QuestEntryState::Complete => false,
+                        QuestEntryState::Complete | QuestEntryState::Failed => false,
                     };
 
                     let entry = Widget::with_theme(TextArea::empty(), "quest_entry");
@@ -159,6 +208,23 @@ impl WidgetKind for QuestWindow {
 
                         if let Some(quest_data) = quest.entries.get(id) {
                             state.add_text_arg("description", &quest_data.description);
+
+                            if quest_data.optional {
+                                state.add_text_arg("optional", "true");
+                            }
+
+                            // only show a counter for objectives needing more than one
+                            if let Some(ref objective) = quest_data.objective {
+                                let count = objective.count();
+                                if count > 1 {
+                                    let progress = quests.entry_progress(&quest.id, id).min(count);
+                                    state.add_text_arg("progress", &format!("{progress}/{count}"));
+                                }
+                            }
+                        }
+
+                        if let QuestEntryState::Failed = entry_state {
+                            state.add_text_arg("failed", "true");
                         }
                     }
 
@@ -173,6 +239,65 @@ impl WidgetKind for QuestWindow {
             quest_entries_widget,
             show_completed_toggle,
             show_completed_label,
+            notes_toggle,
+            notes_label,
         ]
     }
 }
+
+impl QuestWindow {
+    fn add_notes_widgets(&self, quests: &QuestStateSet) -> Vec<Rc<RefCell<Widget>>> {
+        let notes_pane = ScrollPane::new(ScrollDirection::Vertical);
+        let notes_widget = Widget::with_theme(notes_pane.clone(), "notes");
+
+        for (index, note) in quests.notes().iter().enumerate().rev() {
+            let note_widget = Widget::with_theme(TextArea::empty(), "note");
+            note_widget.borrow_mut().state.add_text_arg("note", note);
+
+            let delete = Widget::with_theme(Button::empty(), "delete");
+            delete
+                .borrow_mut()
+                .state
+                .add_callback(Callback::new(Rc::new(move |_, _| {
+                    GameState::remove_journal_note(index);
+                })));
+            Widget::add_child_to(&note_widget, delete);
+
+            notes_pane.borrow().add_to_content(note_widget);
+        }
+
+        let field = InputField::new(&self.note_text);
+        field
+            .borrow_mut()
+            .set_enter_callback(Callback::new(Rc::new(|widget, kind| {
+                let field = Widget::downcast_mut::<InputField>(kind);
+                let text = field.text();
+                field.clear(widget);
+
+                let (_, window) = Widget::parent_mut::<QuestWindow>(widget);
+                window.note_text = text;
+                window.add_note();
+            })));
+
+        let note_field = Widget::with_theme(field, "note_field");
+        note_field
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(|widget, kind| {
+                let (_, window) = Widget::parent_mut::<QuestWindow>(widget);
+                let field = Widget::downcast::<InputField>(kind);
+                window.note_text = field.text.to_string();
+            })));
+
+        let add_note = Widget::with_theme(Button::empty(), "add_note");
+        add_note
+            .borrow_mut()
+            .state
+            .add_callback(Callback::new(Rc::new(|widget, _| {
+                let (_, window) = Widget::parent_mut::<QuestWindow>(widget);
+                window.add_note();
+            })));
+
+        vec![notes_widget, note_field, add_note]
+    }
+}
@@ -22,7 +22,7 @@ use sulis_core::ui::{Widget, WidgetKind};
 use sulis_core::widgets::TextArea;
 use sulis_module::Race;
 
-use crate::bonus_text_arg_handler::{add_attack_text_args, add_bonus_text_args};
+use crate::bonus_text_arg_handler::{add_attack_text_args, add_bonus_text_args, attribute_bonuses};
 
 pub const NAME: &str = "race_pane";
 
@@ -74,6 +74,18 @@ impl WidgetKind for RacePane {
 
         let stats = Widget::with_theme(TextArea::empty(), "stats");
         {
+            let attributes = Widget::empty("attributes");
+            for (attribute, amount) in attribute_bonuses(&race.base_stats) {
+                let row = Widget::with_theme(TextArea::empty(), "attribute");
+                {
+                    let state = &mut row.borrow_mut().state;
+                    state.add_text_arg("name", &attribute.name());
+                    state.add_text_arg("value", &amount.to_string());
+                }
+                Widget::add_child_to(&attributes, row);
+            }
+            Widget::add_child_to(&stats, attributes);
+
             let state = &mut stats.borrow_mut().state;
             state.add_text_arg("name", &race.name);
             state.add_text_arg("movement_rate", &format!("{:.2}", race.movement_rate));
@@ -18,10 +18,11 @@ use std::collections::HashMap;
 use std::{any::Any, cell::RefCell, rc::Rc, time::Instant};
 
 use crate::{
-    character_window, formation_window, inventory_window, merchant_window, prop_window,
-    quest_window, world_map_window, AbilitiesBar, ApBar, AreaView, CharacterWindow, ConsoleWindow,
-    FormationWindow, GameOverWindow, InGameMenu, InitiativeTicker, InventoryWindow, MerchantWindow,
-    PortraitPane, PropWindow, QuestWindow, QuickItemBar, WorldMapWindow,
+    character_window, combat_log_window, formation_window, inventory_window, merchant_window,
+    prop_window, quest_window, world_map_window, AbilitiesBar, ApBar, AreaView, CharacterWindow,
+    CombatLogWindow, ConsoleWindow, FormationWindow, GameOverWindow, InGameMenu, InitiativeTicker,
+    InventoryWindow, MerchantWindow, PortraitPane, PropWindow, QuestWindow, QuickItemBar,
+    WorldMapWindow,
 };
 use sulis_core::config::Config;
 use sulis_core::io::{keyboard_event::Key, InputActionKind};
@@ -32,14 +33,15 @@ use sulis_module::{area::OnRest, Module};
 use sulis_state::{
     area_feedback_text::ColorKind, save_file::create_save, script::script_callback,
     script::ScriptEntity, AreaFeedbackText, ChangeListener, EntityState, GameState, NextGameStep,
-    Script,
+    ReadiedAction, Script,
 };
 
-const WINDOW_NAMES: [&str; 7] = [
+const WINDOW_NAMES: [&str; 8] = [
     self::formation_window::NAME,
     self::inventory_window::NAME,
     self::character_window::NAME,
     self::quest_window::NAME,
+    self::combat_log_window::NAME,
     self::world_map_window::NAME,
     self::merchant_window::NAME,
     self::prop_window::NAME,
@@ -182,6 +184,15 @@ impl RootView {
         });
     }
 
+    pub fn set_combat_log_window(&mut self, widget: &Rc<RefCell<Widget>>, desired_state: bool) {
+        self.set_window(
+            widget,
+            self::combat_log_window::NAME,
+            desired_state,
+            &|| Some(CombatLogWindow::new()),
+        );
+    }
+
     pub fn set_formation_window(&mut self, widget: &Rc<RefCell<Widget>>, desired_state: bool) {
         self.set_window(widget, self::formation_window::NAME, desired_state, &|| {
             Some(FormationWindow::new())
@@ -266,6 +277,11 @@ impl RootView {
         self.set_quest_window(widget, desired_state);
     }
 
+    pub fn toggle_combat_log_window(&mut self, widget: &Rc<RefCell<Widget>>) {
+        let desired_state = !Widget::has_child_with_name(widget, self::combat_log_window::NAME);
+        self.set_combat_log_window(widget, desired_state);
+    }
+
     pub fn toggle_map_window(&mut self, widget: &Rc<RefCell<Widget>>) {
         let desired_state = !Widget::has_child_with_name(widget, self::world_map_window::NAME);
         self.set_map_window(widget, desired_state, false);
@@ -308,6 +324,47 @@ impl RootView {
         }
     }
 
+    pub fn delay_turn(&mut self) {
+        self.cancel_targeter();
+
+        if GameState::is_pc_current() {
+            let mgr = GameState::turn_manager();
+            let cbs = mgr.borrow_mut().delay_current();
+            match cbs {
+                None => self.add_status_text("Cannot delay past the end of the round."),
+                Some(cbs) => script_callback::fire_round_elapsed(cbs),
+            }
+        }
+    }
+
+    /// Readies a weapon attack for the current party member and ends its turn
+    pub fn ready_attack(&self) {
+        self.cancel_targeter();
+
+        if !GameState::is_pc_current() {
+            return;
+        }
+
+        let mgr = GameState::turn_manager();
+        let current = match mgr.borrow().current() {
+            None => return,
+            Some(current) => current,
+        };
+
+        let index = current.borrow().index();
+        if !mgr.borrow_mut().ready_action(index, ReadiedAction::Attack) {
+            return;
+        }
+
+        let area = GameState::area_state();
+        let mut feedback = AreaFeedbackText::with_target(&current.borrow(), &area.borrow());
+        feedback.add_entry("Ready".to_string(), ColorKind::Info);
+        area.borrow_mut().add_feedback_text(feedback);
+
+        let cbs = mgr.borrow_mut().next();
+        script_callback::fire_round_elapsed(cbs);
+    }
+
     fn cancel_targeter(&self) {
         let area = GameState::area_state();
         let area = area.borrow();
@@ -354,7 +411,7 @@ impl RootView {
         let party = GameState::party();
 
         if let Some(member) = party.get(index) {
-            GameState::set_selected_party_member(Rc::clone(member));
+            GameState::activate_party_member(Rc::clone(member));
         }
     }
 }
@@ -468,8 +525,11 @@ impl WidgetKind for RootView {
             ToggleCharacter => self.toggle_character_window(widget),
             ToggleMap => self.toggle_map_window(widget),
             ToggleJournal => self.toggle_quest_window(widget),
+            ToggleCombatLog => self.toggle_combat_log_window(widget),
             ToggleFormation => self.toggle_formation_window(widget),
             EndTurn => self.end_turn(),
+            Delay => self.delay_turn(),
+            Ready => self.ready_attack(),
             Rest => self.rest(),
             Exit => self.show_exit(widget),
             SelectAll => GameState::select_party_members(GameState::party()),
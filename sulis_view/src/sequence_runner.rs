@@ -0,0 +1,202 @@
+//  This file is part of Sulis, a turn based RPG written in Rust.
+//  Copyright 2019 Jared Stephen
+//
+//  Sulis is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  Sulis is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with Sulis.  If not, see <http://www.gnu.org/licenses/>
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use sulis_core::ui::{Widget, WidgetKind};
+use sulis_core::util::ExtInt;
+use sulis_module::sequence::Step;
+use sulis_module::Sequence;
+use sulis_state::{
+    animation::{particle_generator::Param, Anim},
+    area_feedback_text::ColorKind,
+    AreaFeedbackText, EntityState, GameState,
+};
+
+use crate::dialog_window::find_speaker;
+use crate::trigger_activator::{activate, scroll_view};
+
+pub const NAME: &str = "sequence_runner";
+
+enum Wait {
+    Millis(u32),
+    Entity(Rc<RefCell<EntityState>>),
+    All,
+}
+
+/// Plays the steps of a `Sequence` on the current area.  This widget is
+/// modal, blocking player input until the last step completes.
+pub struct SequenceRunner {
+    sequence: Rc<Sequence>,
+    step_index: usize,
+    wait: Option<Wait>,
+}
+
+impl SequenceRunner {
+    pub fn new(sequence: Rc<Sequence>) -> Rc<RefCell<SequenceRunner>> {
+        Rc::new(RefCell::new(SequenceRunner {
+            sequence,
+            step_index: 0,
+            wait: None,
+        }))
+    }
+
+    /// Returns true if still waiting, updating the wait by `millis`
+    fn is_waiting(&mut self, millis: u32) -> bool {
+        let waiting = match self.wait {
+            None => false,
+            Some(Wait::Millis(ref mut remaining)) => {
+                *remaining = remaining.saturating_sub(millis);
+                *remaining > 0
+            }
+            Some(Wait::Entity(ref entity)) => GameState::has_blocking_animations(entity),
+            Some(Wait::All) => GameState::has_any_blocking_animations(),
+        };
+
+        if !waiting {
+            self.wait = None;
+        }
+        waiting
+    }
+
+    fn run_step(&mut self, widget: &Rc<RefCell<Widget>>, step: &Step) {
+        match step {
+            Step::Move { entity, x, y } => {
+                let entity = match find_entity(entity) {
+                    None => return,
+                    Some(entity) => entity,
+                };
+                let dest = GameState::get_point_dest(&entity.borrow(), *x as f32, *y as f32);
+                if !GameState::move_towards_dest(&entity, &[], dest, None) {
+                    warn!(
+                        "Unable to move '{}' to {},{}",
+                        entity.borrow().unique_id(),
+                        x,
+                        y
+                    );
+                }
+            }
+            Step::Speak { entity, text } => {
+                let entity = match find_entity(entity) {
+                    None => return,
+                    Some(entity) => entity,
+                };
+                let area = GameState::area_state();
+                let mut feedback = AreaFeedbackText::with_target(&entity.borrow(), &area.borrow());
+                feedback.add_entry(text.to_string(), ColorKind::Info);
+                area.borrow_mut().add_feedback_text(feedback);
+            }
+            Step::Color {
+                entity,
+                color,
+                millis,
+            } => {
+                let entity = match find_entity(entity) {
+                    None => return,
+                    Some(entity) => entity,
+                };
+                let color = [
+                    Param::fixed(color.r),
+                    Param::fixed(color.g),
+                    Param::fixed(color.b),
+                    Param::fixed(color.a),
+                ];
+                let color_sec = [Param::fixed(0.0); 4];
+                let anim = Anim::new_entity_color(&entity, ExtInt::Int(*millis), color, color_sec);
+                GameState::add_animation(anim);
+            }
+            Step::Subpos {
+                entity,
+                x,
+                y,
+                millis,
+            } => {
+                let entity = match find_entity(entity) {
+                    None => return,
+                    Some(entity) => entity,
+                };
+                let secs = *millis as f32 / 1000.0;
+                let x = Param::with_speed(0.0, x / secs);
+                let y = Param::with_speed(0.0, y / secs);
+                let anim = Anim::new_entity_subpos(&entity, ExtInt::Int(*millis), x, y);
+                GameState::add_animation(anim);
+            }
+            Step::ScrollView { x, y } => scroll_view(widget, *x, *y),
+            Step::Focus(entity) => {
+                if let Some(entity) = find_entity(entity) {
+                    let (x, y) = {
+                        let loc = &entity.borrow().location;
+                        (loc.x, loc.y)
+                    };
+                    scroll_view(widget, x, y);
+                }
+            }
+            Step::Wait(millis) => self.wait = Some(Wait::Millis(*millis)),
+            Step::WaitFor(entity) => {
+                if let Some(entity) = find_entity(entity) {
+                    self.wait = Some(Wait::Entity(entity));
+                }
+            }
+            Step::WaitAll => self.wait = Some(Wait::All),
+            Step::Fire(triggers) => {
+                let pc = GameState::player();
+                activate(widget, triggers, &pc, &pc);
+            }
+        }
+    }
+}
+
+fn find_entity(id: &str) -> Option<Rc<RefCell<EntityState>>> {
+    let pc = GameState::player();
+    let entity = find_speaker(&[id.to_string()], &pc, &pc);
+    if entity.is_none() {
+        warn!(
+            "Unable to find entity '{}' in the current area for sequence",
+            id
+        );
+    }
+    entity
+}
+
+impl WidgetKind for SequenceRunner {
+    widget_kind!(NAME);
+
+    fn update(&mut self, widget: &Rc<RefCell<Widget>>, millis: u32) {
+        if self.is_waiting(millis) {
+            return;
+        }
+
+        let sequence = Rc::clone(&self.sequence);
+        while let Some(step) = sequence.steps.get(self.step_index) {
+            self.step_index += 1;
+            self.run_step(widget, step);
+
+            // animations are added on the next game update, so check
+            // waits starting from the next frame
+            if self.wait.is_some() {
+                return;
+            }
+        }
+
+        widget.borrow_mut().mark_for_removal();
+        if !sequence.on_end.is_empty() {
+            let pc = GameState::player();
+            GameState::add_ui_callback(sequence.on_end.clone(), &pc, &pc);
+        }
+    }
+}
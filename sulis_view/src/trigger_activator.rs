@@ -19,127 +19,35 @@ use std::rc::Rc;
 
 use sulis_core::ui::{Callback, Widget};
 use sulis_module::{
+    condition,
     on_trigger::{self, Kind, ModuleLoadData, QuestStateData},
-    Actor, ItemState, MerchantData, Module, OnTrigger,
+    Actor, Condition, ItemState, MerchantData, Module, OnTrigger,
 };
 use sulis_state::{
     area_feedback_text::ColorKind,
     script::{entity_with_id, CallbackData, FuncKind, ScriptEntity},
-    AreaFeedbackText, EntityState, GameState, NextGameStep, Script,
+    AreaFeedbackText, EntityState, GameState, NextGameStep, Script, TriggerContext,
 };
 
 use crate::{
     ap_bar, character_window, dialog_window, window_fade, ConfirmationWindow, CutsceneWindow,
-    GameOverWindow, LoadingScreen, RootView, ScriptMenu, UIBlocker, WindowFade,
+    GameOverWindow, LoadingScreen, RootView, ScriptMenu, SequenceRunner, UIBlocker, WindowFade,
 };
 
+pub fn is_condition_met(
+    condition: &Condition,
+    pc: &Rc<RefCell<EntityState>>,
+    target: &Rc<RefCell<EntityState>>,
+) -> bool {
+    condition.evaluate(&TriggerContext { pc, target })
+}
+
 pub fn is_match(
     on_trigger: &[OnTrigger],
     pc: &Rc<RefCell<EntityState>>,
     target: &Rc<RefCell<EntityState>>,
 ) -> bool {
-    for trigger in on_trigger.iter() {
-        use sulis_module::OnTrigger::*;
-        match trigger {
-            PlayerCoins(amount) => {
-                let cur = GameState::party_coins();
-                if cur < *amount {
-                    return false;
-                }
-            }
-            PartyMember(ref id) => {
-                if !GameState::has_party_member(id) {
-                    return false;
-                }
-            }
-            PartyItem(ref id) => {
-                let stash = GameState::party_stash();
-                if !stash.borrow().has_item(id) {
-                    return false;
-                }
-            }
-            TargetNumFlag(ref data) => {
-                if target.borrow().get_num_flag(&data.flag) < data.val {
-                    return false;
-                }
-            }
-            PlayerNumFlag(ref data) => {
-                if pc.borrow().get_num_flag(&data.flag) < data.val {
-                    return false;
-                }
-            }
-            NotTargetNumFlag(ref data) => {
-                if target.borrow().get_num_flag(&data.flag) >= data.val {
-                    return false;
-                }
-            }
-            NotPlayerNumFlag(ref data) => {
-                if pc.borrow().get_num_flag(&data.flag) >= data.val {
-                    return false;
-                }
-            }
-            NotTargetFlag(ref flag) => {
-                if target.borrow().has_custom_flag(flag) {
-                    return false;
-                }
-            }
-            NotPlayerFlag(ref flag) => {
-                if pc.borrow().has_custom_flag(flag) {
-                    return false;
-                }
-            }
-            TargetFlag(ref flag) => {
-                if !target.borrow().has_custom_flag(flag) {
-                    return false;
-                }
-            }
-            PlayerFlag(ref flag) => {
-                if !pc.borrow().has_custom_flag(flag) {
-                    return false;
-                }
-            }
-            PlayerAbility(ref ability_to_find) => {
-                let mut has_ability = false;
-                for ability in pc.borrow().actor.actor.abilities.iter() {
-                    if &ability.ability.id == ability_to_find {
-                        has_ability = true;
-                        break;
-                    }
-                }
-
-                if !has_ability {
-                    return false;
-                }
-            }
-            QuestState(ref data) => {
-                let state = if let Some(ref entry) = data.entry {
-                    GameState::get_quest_entry_state(data.quest.to_string(), entry.to_string())
-                } else {
-                    GameState::get_quest_state(data.quest.to_string())
-                };
-
-                if state != data.state {
-                    return false;
-                }
-            }
-            NotQuestState(ref data) => {
-                let state = if let Some(ref entry) = data.entry {
-                    GameState::get_quest_entry_state(data.quest.to_string(), entry.to_string())
-                } else {
-                    GameState::get_quest_state(data.quest.to_string())
-                };
-
-                if state == data.state {
-                    return false;
-                }
-            }
-            _ => {
-                warn!("Unsupported OnTrigger kind '{:?}' in validator", trigger);
-            }
-        }
-    }
-
-    true
+    condition::triggers_match(on_trigger, &TriggerContext { pc, target })
 }
 
 pub fn activate(
@@ -237,6 +145,7 @@ pub fn activate(
                 area.borrow_mut().add_feedback_text(feedback);
             }
             ShowCutscene(ref cutscene) => show_cutscene(widget, cutscene),
+            StartSequence(ref sequence) => start_sequence(widget, sequence),
             FireScript(ref script) => fire_script(&script.id, &script.func, pc, target),
             GameOverWindow(ref text) => game_over_window(widget, text.to_string()),
             ExitToMenu => exit_to_menu(widget),
@@ -262,6 +171,12 @@ pub fn activate(
             NotQuestState(_) => {
                 warn!("NotQuestState invalid for trigger/dialog on_activate");
             }
+            Condition(ref condition) => {
+                // conditions gate all remaining triggers in the list
+                if !is_condition_met(condition, pc, target) {
+                    return;
+                }
+            }
         }
     }
 }
@@ -522,6 +437,27 @@ fn show_cutscene(widget: &Rc<RefCell<Widget>>, cutscene_id: &str) {
     Widget::add_child_to(&root, window);
 }
 
+fn start_sequence(widget: &Rc<RefCell<Widget>>, sequence_id: &str) {
+    let sequence = match Module::sequence(sequence_id) {
+        None => {
+            warn!("Unable to find sequence '{}' for on_trigger", sequence_id);
+            return;
+        }
+        Some(sequence) => sequence,
+    };
+
+    info!(
+        "Starting sequence '{}' with {} steps.",
+        sequence_id,
+        sequence.steps.len()
+    );
+
+    let root = Widget::get_root(widget);
+    let runner = Widget::with_defaults(SequenceRunner::new(sequence));
+    runner.borrow_mut().state.set_modal(true);
+    Widget::add_child_to(&root, runner);
+}
+
 fn start_convo(
     widget: &Rc<RefCell<Widget>>,
     convo_id: &str,
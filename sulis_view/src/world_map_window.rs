@@ -21,8 +21,8 @@ use std::rc::Rc;
 use sulis_core::ui::{animation_state, Callback, Widget, WidgetKind};
 use sulis_core::util::Point;
 use sulis_core::widgets::{Button, TextArea};
-use sulis_module::{campaign::WorldMapLocation, Module, Time};
-use sulis_state::GameState;
+use sulis_module::{campaign::WorldMapLocation, quest::QuestMarker, Module, Time};
+use sulis_state::{quest_handler, GameState};
 
 pub const NAME: &str = "world_map_window";
 
@@ -106,6 +106,7 @@ impl WidgetKind for WorldMapWindow {
 
         let area_state = GameState::area_state();
         let cur_location_id = area_state.borrow().area.area.world_map_location.clone();
+        let markers = quest_handler::active_markers(&GameState::quest_state());
 
         for location in map.locations.iter() {
             let button = Widget::with_theme(Button::empty(), "location");
@@ -141,6 +142,11 @@ impl WidgetKind for WorldMapWindow {
                     .add_text_arg("name", &location.name);
                 label.borrow_mut().state.set_visible(is_visible);
 
+                if has_quest_marker(&markers, location) {
+                    state.add_text_arg("quest", "true");
+                    label.borrow_mut().state.add_text_arg("quest", "true");
+                }
+
                 (
                     self.transition_enabled && is_enabled && is_visible && !is_active,
                     label,
@@ -170,6 +176,22 @@ impl WidgetKind for WorldMapWindow {
     }
 }
 
+fn has_quest_marker(markers: &[QuestMarker], location: &WorldMapLocation) -> bool {
+    markers.iter().any(|marker| match marker {
+        QuestMarker::Location(id) => id == &location.id,
+        QuestMarker::Area { area, .. } => {
+            if location.linked_area.as_ref() == Some(area) {
+                return true;
+            }
+
+            match Module::area(area) {
+                None => false,
+                Some(area) => area.world_map_location.as_ref() == Some(&location.id),
+            }
+        }
+    })
+}
+
 fn add_travel_callback(
     cur_location_id: &Option<String>,
     location: &WorldMapLocation,